@@ -1,15 +1,38 @@
+use std::collections::BTreeSet;
 use std::convert::TryFrom;
-use std::fs::read;
+use std::fs::{read, write, File};
+use std::io::{stderr, BufReader, ErrorKind, IsTerminal, Write};
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 use structopt::StructOpt;
 use yaml_validator::{
-    yaml_rust::{Yaml, YamlLoader},
-    Context, Validate,
+    load_from_reader, yaml_rust::Yaml, Context, LoadError, TaggedDocuments, Validate,
+    ValidationOptions,
 };
 
 mod error;
+mod progress;
 use error::Error;
+use progress::Progress;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Format {
+    Human,
+    Tap,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "tap" => Ok(Format::Tap),
+            other => Err(format!("unknown output format `{}`", other)),
+        }
+    }
+}
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -27,7 +50,11 @@ struct Opt {
     )]
     schemas: Vec<PathBuf>,
 
-    #[structopt(short, long, help = "URI of the schema to validate the files against.")]
+    #[structopt(
+        short,
+        long,
+        help = "URI of the schema to validate the files against, optionally followed by a pointer to a node within it, as in `person#/items/address`."
+    )]
     uri: String,
 
     #[structopt(
@@ -35,6 +62,169 @@ struct Opt {
         help = "Files to validate against the selected schemas."
     )]
     files: Vec<PathBuf>,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "human",
+        possible_values = &["human", "tap"],
+        help = "Output format. `tap` emits one Test Anything Protocol test line per file."
+    )]
+    format: Format,
+
+    #[structopt(
+        long,
+        help = "Include the schema rule responsible for each error in the output."
+    )]
+    explain: bool,
+
+    #[structopt(
+        long,
+        help = "Print a JSON summary of the number of files passed and failed after the output."
+    )]
+    summary_json: bool,
+
+    #[structopt(
+        parse(from_os_str),
+        long,
+        help = "Failures recorded in this baseline file are reported as known, rather than failing the run."
+    )]
+    baseline: Option<PathBuf>,
+
+    #[structopt(
+        parse(from_os_str),
+        long,
+        help = "Record every current failure in this baseline file, for use with --baseline. The run succeeds once the baseline is written, even if files failed."
+    )]
+    write_baseline: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        overrides_with = "no-fail-fast",
+        help = "Stop at the first file which fails validation, rather than reporting every failing file."
+    )]
+    fail_fast: bool,
+
+    #[structopt(
+        long,
+        overrides_with = "fail-fast",
+        help = "Validate and report every file, even after one has failed. This is the default."
+    )]
+    no_fail_fast: bool,
+
+    #[structopt(
+        short,
+        long,
+        help = "Do not print the number of files validated so far. Progress is only shown when stderr is a terminal."
+    )]
+    quiet: bool,
+}
+
+/// Output produced by a complete run, along with whether every file passed.
+#[derive(Debug, PartialEq, Eq)]
+struct Report {
+    output: String,
+    passed: bool,
+}
+
+/// Identifies a failure by the file, its location within the document and the kind of error,
+/// so that failures recorded in a baseline can be recognised on later runs.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Fingerprint {
+    file: String,
+    path: String,
+    kind: String,
+}
+
+impl Fingerprint {
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(3, '\t');
+
+        Some(Fingerprint {
+            file: parts.next()?.into(),
+            path: parts.next()?.into(),
+            kind: parts.next()?.into(),
+        })
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}\t{}", self.file, self.path, self.kind)
+    }
+}
+
+/// The outcome of validating a single file against the selected schema.
+struct FileResult {
+    name: String,
+    error: Option<String>,
+    // Every failure in the file, including those suppressed by the baseline.
+    fingerprints: Vec<Fingerprint>,
+    suppressed: usize,
+}
+
+fn format_tap(results: &[FileResult]) -> String {
+    let mut output = format!("TAP version 13\n1..{}\n", results.len());
+
+    for (i, result) in results.iter().enumerate() {
+        match &result.error {
+            None => output.push_str(&format!("ok {} - {}\n", i + 1, result.name)),
+            Some(err) => {
+                output.push_str(&format!("not ok {} - {}\n", i + 1, result.name));
+                output.push_str("  ---\n  message: |\n");
+                for line in err.lines() {
+                    output.push_str(&format!("    {}\n", line));
+                }
+                output.push_str("  ...\n");
+            }
+        }
+    }
+
+    output
+}
+
+/// Quote a string for use in JSON output.
+fn json_string(value: &str) -> String {
+    let mut output = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+    output
+}
+
+fn format_summary(results: &[FileResult]) -> String {
+    let passed = results
+        .iter()
+        .filter(|result| result.error.is_none())
+        .count();
+
+    let files: Vec<String> = results
+        .iter()
+        .map(|result| {
+            format!(
+                "{{\"name\": {}, \"passed\": {}}}",
+                json_string(&result.name),
+                result.error.is_none()
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"total\": {}, \"passed\": {}, \"failed\": {}, \"files\": [{}]}}\n",
+        results.len(),
+        passed,
+        results.len() - passed,
+        files.join(", ")
+    )
 }
 
 fn read_file(filename: &Path) -> Result<String, Error> {
@@ -57,26 +247,100 @@ fn read_file(filename: &Path) -> Result<String, Error> {
     Ok(utf8)
 }
 
-fn load_yaml(filenames: &[PathBuf]) -> Result<Vec<Yaml>, Vec<Error>> {
-    let (yaml, errs): (Vec<_>, Vec<_>) = filenames
+fn read_baseline(filename: &Path) -> Result<BTreeSet<Fingerprint>, Error> {
+    read_file(filename)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            Fingerprint::parse(line).ok_or_else(|| {
+                Error::File(format!(
+                    "baseline {} has a malformed entry on line {}\n",
+                    filename.to_string_lossy(),
+                    i + 1
+                ))
+            })
+        })
+        .collect()
+}
+
+fn write_baseline(filename: &Path, results: &[FileResult]) -> Result<usize, Error> {
+    let fingerprints: BTreeSet<&Fingerprint> = results
+        .iter()
+        .flat_map(|result| &result.fingerprints)
+        .collect();
+
+    let contents: String = fingerprints
         .iter()
-        .map(|file| {
-            read_file(file)
-                .and_then(|source| YamlLoader::load_from_str(&source).map_err(Error::from))
+        .map(|fingerprint| format!("{}\n", fingerprint))
+        .collect();
+
+    write(filename, contents).map_err(|e| {
+        Error::File(format!(
+            "could not write baseline {}: {}\n",
+            filename.to_string_lossy(),
+            e
+        ))
+    })?;
+
+    Ok(fingerprints.len())
+}
+
+fn load_file<T>(
+    filename: &Path,
+    load: fn(BufReader<File>) -> Result<T, LoadError>,
+) -> Result<T, Error> {
+    let io_error = |e: std::io::Error| {
+        Error::File(if e.kind() == ErrorKind::InvalidData {
+            format!(
+                "file {} did not contain valid utf8: {}\n",
+                filename.to_string_lossy(),
+                e
+            )
+        } else {
+            format!(
+                "could not read file {}: {}\n",
+                filename.to_string_lossy(),
+                e
+            )
         })
+    };
+
+    let file = File::open(filename).map_err(io_error)?;
+
+    load(BufReader::new(file)).map_err(|e| match e {
+        LoadError::Io(e) => io_error(e),
+        LoadError::Yaml(e) => Error::from(e),
+    })
+}
+
+fn load_documents<T: std::fmt::Debug>(
+    filenames: &[PathBuf],
+    load: fn(BufReader<File>) -> Result<T, LoadError>,
+) -> Result<Vec<T>, Vec<Error>> {
+    let (yaml, errs): (Vec<_>, Vec<_>) = filenames
+        .iter()
+        .map(|file| load_file(file, load))
         .partition(Result::is_ok);
 
     if !errs.is_empty() {
         Err(errs.into_iter().map(Result::unwrap_err).collect())
     } else {
-        Ok(yaml.into_iter().flat_map(Result::unwrap).collect())
+        Ok(yaml.into_iter().map(Result::unwrap).collect())
     }
 }
 
-// Ideally this would just be the real main function, but since errors are
-// automatically printed using the Debug trait rather than Display, the error
-// messages are not very easy to read.
-fn actual_main(opt: Opt) -> Result<(), Error> {
+fn load_yaml(filenames: &[PathBuf]) -> Result<Vec<Yaml>, Vec<Error>> {
+    Ok(load_documents(filenames, load_from_reader)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn validate_files<W: Write>(
+    opt: &Opt,
+    progress: &mut Progress<W>,
+) -> Result<Vec<FileResult>, Error> {
     if opt.schemas.is_empty() {
         return Err(Error::Validation(
             "no schemas supplied, see the --schema option for information\n".into(),
@@ -92,8 +356,18 @@ fn actual_main(opt: Opt) -> Result<(), Error> {
     let yaml_schemas = load_yaml(&opt.schemas).map_err(Error::Multiple)?;
     let context = Context::try_from(&yaml_schemas[..])?;
 
+    // Only a uri pointing to a node within a schema, as in `person#/items/address`,
+    // is looked up as a reference, so that explanations of whole schemas start at their uri.
+    let fragment;
     let schema = {
-        if let Some(schema) = context.get_schema(&opt.uri) {
+        let schema = if opt.uri.contains('#') {
+            fragment = context.get_schema_at(&opt.uri);
+            fragment.as_ref()
+        } else {
+            context.get_schema(&opt.uri)
+        };
+
+        if let Some(schema) = schema {
             schema
         } else {
             return Err(Error::Validation(format!(
@@ -103,86 +377,286 @@ fn actual_main(opt: Opt) -> Result<(), Error> {
         }
     };
 
-    let documents = opt
+    let baseline = match &opt.baseline {
+        Some(filename) => read_baseline(filename)?,
+        None => BTreeSet::new(),
+    };
+
+    // Documents are loaded along with their tags, for schemas which use the `tag` keyword.
+    let documents =
+        load_documents(&opt.files, TaggedDocuments::load_from_reader).map_err(Error::Multiple)?;
+
+    // An empty file would otherwise have nothing to fail validation, and pass silently.
+    let empty: Vec<Error> = opt
         .files
         .iter()
-        .zip(load_yaml(&opt.files).map_err(Error::Multiple)?);
-
-    for (name, doc) in documents {
-        schema.validate(&context, &doc).map_err(|err| {
-            Error::Validation(format!(
-                "{name}:\n{err}",
-                name = name.to_string_lossy(),
-                err = err
+        .zip(&documents)
+        .filter(|(_, docs)| docs.documents().is_empty())
+        .map(|(name, _)| {
+            Error::File(format!(
+                "file {} contained no documents\n",
+                name.to_string_lossy()
             ))
-        })?;
+        })
+        .collect();
+
+    if !empty.is_empty() {
+        return Err(Error::Multiple(empty));
     }
 
-    Ok(())
+    let results = opt.files.iter().zip(&documents).map(|(name, docs)| {
+        let name = name.to_string_lossy().into_owned();
+
+        // Each failure is looked at on its own, so that only those missing
+        // from the baseline are reported.
+        let failures: Vec<(Fingerprint, String)> = docs
+            .iter()
+            .filter_map(|(doc, tags)| {
+                let opts = ValidationOptions::default().tags(tags);
+                schema.validate_with(&context, doc, &opts).err()
+            })
+            .flat_map(|err| schema.explain(&context, &err))
+            .map(|explanation| {
+                let fingerprint = Fingerprint {
+                    file: name.clone(),
+                    path: explanation.document_path.clone(),
+                    kind: explanation
+                        .keyword
+                        .map(String::from)
+                        .unwrap_or_else(|| explanation.message.replace(&['\t', '\n'][..], " ")),
+                };
+
+                let line = if opt.explain {
+                    format!("{}\n", explanation)
+                } else {
+                    format!("{}: {}\n", explanation.document_path, explanation.message)
+                };
+
+                (fingerprint, line)
+            })
+            .collect();
+
+        let errors: Vec<&str> = failures
+            .iter()
+            .filter(|(fingerprint, _)| !baseline.contains(fingerprint))
+            .map(|(_, line)| line.as_str())
+            .collect();
+
+        FileResult {
+            suppressed: failures.len() - errors.len(),
+            error: if errors.is_empty() {
+                None
+            } else {
+                Some(errors.concat())
+            },
+            fingerprints: failures
+                .into_iter()
+                .map(|(fingerprint, _)| fingerprint)
+                .collect(),
+            name,
+        }
+    });
+
+    let mut collected = Vec::new();
+    for result in results {
+        let failed = result.error.is_some();
+        collected.push(result);
+        progress.update(collected.len(), opt.files.len());
+
+        // The remaining files are left unvalidated, and do not appear in the output.
+        if failed && opt.fail_fast && !opt.no_fail_fast {
+            break;
+        }
+    }
+
+    // Failures are only printed once every file has been validated, by which
+    // point the progress line has been erased.
+    progress.finish();
+
+    Ok(collected)
+}
+
+fn report(opt: &Opt, results: &[FileResult]) -> Result<Report, Error> {
+    let passed = results.iter().all(|result| result.error.is_none());
+
+    match opt.format {
+        Format::Human => {
+            let failures: Vec<String> = results
+                .iter()
+                .filter_map(|result| {
+                    result
+                        .error
+                        .as_ref()
+                        .map(|err| format!("{name}:\n{err}", name = result.name, err = err))
+                })
+                .collect();
+
+            if !failures.is_empty() {
+                return Err(Error::Validation(failures.concat()));
+            }
+
+            let suppressed: usize = results.iter().map(|result| result.suppressed).sum();
+
+            Ok(Report {
+                output: if suppressed > 0 {
+                    format!(
+                        "all files validated successfully, apart from {} known failures in the baseline!\n",
+                        suppressed
+                    )
+                } else {
+                    "all files validated successfully!\n".into()
+                },
+                passed,
+            })
+        }
+        Format::Tap => Ok(Report {
+            output: format_tap(results),
+            passed,
+        }),
+    }
 }
 
+// Errors returned from main are automatically printed using the Debug trait
+// rather than Display, which makes the messages not very easy to read, so
+// they are printed here instead.
 fn main() {
     let opt = Opt::from_args();
 
-    match actual_main(opt) {
-        Ok(()) => println!("all files validated successfully!"),
+    let mut progress = Progress::new(stderr(), !opt.quiet && stderr().is_terminal());
+
+    let results = match validate_files(&opt, &mut progress) {
+        Ok(results) => results,
         Err(e) => {
             eprint!("{}", e);
             std::process::exit(1);
         }
+    };
+
+    let passed = match &opt.write_baseline {
+        // Every failure is known once recorded in the baseline, so the run succeeds.
+        Some(filename) => match write_baseline(filename, &results) {
+            Ok(count) => {
+                println!(
+                    "recorded {} failures in baseline {}",
+                    count,
+                    filename.to_string_lossy()
+                );
+                true
+            }
+            Err(e) => {
+                eprint!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None => match report(&opt, &results) {
+            Ok(report) => {
+                print!("{}", report.output);
+                report.passed
+            }
+            Err(e) => {
+                eprint!("{}", e);
+                false
+            }
+        },
+    };
+
+    if opt.summary_json {
+        print!("{}", format_summary(&results));
+    }
+
+    if !passed {
+        std::process::exit(1);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn actual_results(opt: &Opt) -> Vec<FileResult> {
+        validate_files(opt, &mut Progress::new(std::io::sink(), false)).unwrap()
+    }
+
+    /// Options validating the given files, with every flag left at its default.
+    fn opt(schemas: &[&str], files: &[&str], uri: &str) -> Opt {
+        Opt {
+            schemas: schemas.iter().map(PathBuf::from).collect(),
+            files: files.iter().map(PathBuf::from).collect(),
+            uri: uri.into(),
+            format: Format::Human,
+            explain: false,
+            summary_json: false,
+            baseline: None,
+            write_baseline: None,
+            fail_fast: false,
+            no_fail_fast: false,
+            quiet: false,
+        }
+    }
+
+    /// A path in the temporary directory which no other test, or concurrent test run, uses.
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "yaml-validator-cli-test-{}-{}",
+            std::process::id(),
+            name
+        ))
+    }
+
+    fn actual_main(opt: Opt) -> Result<Report, Error> {
+        report(
+            &opt,
+            &validate_files(&opt, &mut Progress::new(std::io::sink(), false))?,
+        )
+    }
+
     #[test]
     fn test_all_types_example() {
-        actual_main(Opt {
-            schemas: vec!["../examples/all-types/schema.yaml".into()],
-            files: vec!["../examples/all-types/customers.yaml".into()],
-            uri: "customer-list".into(),
-        })
+        actual_main(opt(
+            &["../examples/all-types/schema.yaml"],
+            &["../examples/all-types/customers.yaml"],
+            "customer-list",
+        ))
         .unwrap();
     }
 
     #[test]
     fn test_multiple_schemas_example() {
-        actual_main(Opt {
-            schemas: vec![
-                "../examples/multiple-schemas/person-schema.yaml".into(),
-                "../examples/multiple-schemas/phonebook-schema.yaml".into(),
+        actual_main(opt(
+            &[
+                "../examples/multiple-schemas/person-schema.yaml",
+                "../examples/multiple-schemas/phonebook-schema.yaml",
             ],
-            files: vec!["../examples/multiple-schemas/mybook.yaml".into()],
-            uri: "phonebook".into(),
-        })
+            &["../examples/multiple-schemas/mybook.yaml"],
+            "phonebook",
+        ))
         .unwrap();
     }
 
     #[test]
     fn test_nesting_example() {
-        actual_main(Opt {
-            schemas: vec!["../examples/nesting/schema.yaml".into()],
-            files: vec!["../examples/nesting/mybook.yaml".into()],
-            uri: "phonebook".into(),
-        })
+        actual_main(opt(
+            &["../examples/nesting/schema.yaml"],
+            &["../examples/nesting/mybook.yaml"],
+            "phonebook",
+        ))
         .unwrap();
     }
 
     #[test]
     fn test_locating_errors_example() {
         assert_eq!(
-            actual_main(Opt {
-                schemas: vec!["../examples/locating-errors/schema.yaml".into()],
-                files: vec!["../examples/locating-errors/phonebook.yaml".into()],
-                uri: "phonebook".into(),
-            })
+            actual_main(opt(
+                &["../examples/locating-errors/schema.yaml"],
+                &["../examples/locating-errors/phonebook.yaml"],
+                "phonebook"
+            ))
             .unwrap_err(),
             Error::Validation(
                 "../examples/locating-errors/phonebook.yaml:
 #[1].age: wrong type, expected integer got real
-#[2].age: wrong type, expected integer got string
 #[2].name: wrong type, expected string got integer
+#[2].age: wrong type, expected integer got string
 "
                 .into()
             )
@@ -192,11 +666,11 @@ mod tests {
     #[test]
     fn test_branching_examples() {
         assert_eq!(
-            actual_main(Opt {
-                schemas: vec!["../examples/branching/schema.yaml".into()],
-                files: vec!["../examples/branching/usernames.yaml".into()],
-                uri: "user-list".into(),
-            })
+            actual_main(opt(
+                &["../examples/branching/schema.yaml"],
+                &["../examples/branching/usernames.yaml"],
+                "user-list"
+            ))
             .unwrap_err(),
             Error::Validation(
                 "../examples/branching/usernames.yaml:
@@ -208,14 +682,59 @@ mod tests {
     }
 
     #[test]
-    fn test_non_existent_schema_file() {
+    fn test_scalar_documents() {
+        actual_main(opt(
+            &["../examples/scalars/schema.yaml"],
+            &["../examples/scalars/port.yaml"],
+            "port",
+        ))
+        .unwrap();
+
         assert_eq!(
-            actual_main(Opt {
-                schemas: vec!["not_found.yaml".into()],
-                files: vec!["".into()],
-                uri: "".into(),
-            })
+            actual_main(opt(
+                &["../examples/scalars/schema.yaml"],
+                &["../examples/scalars/out-of-range-port.yaml"],
+                "port"
+            ))
+            .unwrap_err(),
+            Error::Validation(
+                "../examples/scalars/out-of-range-port.yaml:
+#: special requirements for field not met: value 70000 is above maximum 65535 (inclusive)
+"
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_tagged_documents() {
+        actual_main(opt(
+            &["../examples/tags/schema.yaml"],
+            &["../examples/tags/tagged.yaml"],
+            "credentials",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            actual_main(opt(
+                &["../examples/tags/schema.yaml"],
+                &["../examples/tags/untagged.yaml"],
+                "credentials"
+            ))
             .unwrap_err(),
+            Error::Validation(
+                "../examples/tags/untagged.yaml:
+#.password: special requirements for field not met: expected tag '!Secret', but the value is untagged
+"
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_non_existent_schema_file() {
+        assert_eq!(
+            actual_main(opt(&["not_found.yaml"], &[""], "")).unwrap_err(),
             Error::Multiple(vec![Error::File(
                 "could not read file not_found.yaml: No such file or directory (os error 2)\n"
                     .into()
@@ -226,11 +745,11 @@ mod tests {
     #[test]
     fn test_non_existent_file() {
         assert_eq!(
-            actual_main(Opt {
-                schemas: vec!["../examples/nesting/schema.yaml".into()],
-                files: vec!["not_found.yaml".into()],
-                uri: "person".into(),
-            })
+            actual_main(opt(
+                &["../examples/nesting/schema.yaml"],
+                &["not_found.yaml"],
+                "person"
+            ))
             .unwrap_err(),
             Error::Multiple(vec![Error::File(
                 "could not read file not_found.yaml: No such file or directory (os error 2)\n"
@@ -240,15 +759,280 @@ mod tests {
     }
 
     #[test]
-    fn test_unknown_schema_uri() {
+    fn test_invalid_utf8_file() {
+        let file = temp_file("invalid-utf8.yaml");
+        write(&file, b"name: \xff").unwrap();
+
         assert_eq!(
             actual_main(Opt {
-                schemas: vec!["../examples/nesting/schema.yaml".into()],
-                files: vec!["../examples/nesting/mybook.yaml".into()],
-                uri: "not-found".into(),
+                files: vec![file.clone()],
+                ..opt(&["../examples/nesting/schema.yaml"], &[], "person")
             })
             .unwrap_err(),
+            Error::Multiple(vec![Error::File(format!(
+                "file {} did not contain valid utf8: stream did not contain valid UTF-8\n",
+                file.to_string_lossy()
+            ))])
+        );
+
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_schema_uri() {
+        assert_eq!(
+            actual_main(opt(
+                &["../examples/nesting/schema.yaml"],
+                &["../examples/nesting/mybook.yaml"],
+                "not-found"
+            ))
+            .unwrap_err(),
             Error::Validation("schema referenced by uri `not-found` not found in context\n".into())
         );
     }
+
+    #[test]
+    fn test_tap_format() {
+        assert_eq!(
+            actual_main(Opt {
+                format: Format::Tap,
+                ..opt(
+                    &["../examples/locating-errors/schema.yaml"],
+                    &[
+                        "../examples/locating-errors/valid-phonebook.yaml",
+                        "../examples/locating-errors/phonebook.yaml"
+                    ],
+                    "phonebook"
+                )
+            })
+            .unwrap(),
+            Report {
+                output: "TAP version 13
+1..2
+ok 1 - ../examples/locating-errors/valid-phonebook.yaml
+not ok 2 - ../examples/locating-errors/phonebook.yaml
+  ---
+  message: |
+    #[1].age: wrong type, expected integer got real
+    #[2].name: wrong type, expected string got integer
+    #[2].age: wrong type, expected integer got string
+  ...
+"
+                .into(),
+                passed: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            actual_main(Opt {
+                explain: true,
+                ..opt(
+                    &["../examples/locating-errors/schema.yaml"],
+                    &["../examples/locating-errors/phonebook.yaml"],
+                    "phonebook"
+                )
+            })
+            .unwrap_err(),
+            Error::Validation(
+                "../examples/locating-errors/phonebook.yaml:
+#[1].age: wrong type, expected integer got real (schema: phonebook -> items -> person -> age: type)
+#[2].name: wrong type, expected string got integer (schema: phonebook -> items -> person -> name: type)
+#[2].age: wrong type, expected integer got string (schema: phonebook -> items -> person -> age: type)
+"
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_empty_file() {
+        assert_eq!(
+            actual_main(opt(
+                &["../examples/locating-errors/schema.yaml"],
+                &[
+                    "../examples/locating-errors/valid-phonebook.yaml",
+                    "../examples/locating-errors/empty.yaml"
+                ],
+                "phonebook"
+            ))
+            .unwrap_err(),
+            Error::Multiple(vec![Error::File(
+                "file ../examples/locating-errors/empty.yaml contained no documents\n".into()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_summary_json() {
+        let options = Opt {
+            summary_json: true,
+            ..opt(
+                &["../examples/locating-errors/schema.yaml"],
+                &[
+                    "../examples/locating-errors/valid-phonebook.yaml",
+                    "../examples/locating-errors/phonebook.yaml",
+                ],
+                "phonebook",
+            )
+        };
+
+        assert_eq!(
+            format_summary(&actual_results(&options)),
+            "{\"total\": 2, \"passed\": 1, \"failed\": 1, \"files\": [\
+             {\"name\": \"../examples/locating-errors/valid-phonebook.yaml\", \"passed\": true}, \
+             {\"name\": \"../examples/locating-errors/phonebook.yaml\", \"passed\": false}]}\n"
+        );
+    }
+
+    #[test]
+    fn test_baseline() {
+        let baseline = temp_file("baseline.tsv");
+
+        let options = |baseline: Option<PathBuf>| Opt {
+            baseline,
+            ..opt(
+                &["../examples/locating-errors/schema.yaml"],
+                &["../examples/locating-errors/phonebook.yaml"],
+                "phonebook",
+            )
+        };
+
+        assert_eq!(
+            write_baseline(&baseline, &actual_results(&options(None))).unwrap(),
+            3
+        );
+
+        assert_eq!(
+            read_file(&baseline).unwrap(),
+            "../examples/locating-errors/phonebook.yaml\t#[1].age\ttype
+../examples/locating-errors/phonebook.yaml\t#[2].age\ttype
+../examples/locating-errors/phonebook.yaml\t#[2].name\ttype
+"
+        );
+
+        assert_eq!(
+            actual_main(options(Some(baseline.clone()))).unwrap(),
+            Report {
+                output: "all files validated successfully, apart from 3 known failures in the baseline!\n"
+                    .into(),
+                passed: true,
+            }
+        );
+
+        // Failures missing from the baseline are still reported, on their own.
+        write(
+            &baseline,
+            "../examples/locating-errors/phonebook.yaml\t#[1].age\ttype\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual_main(options(Some(baseline.clone()))).unwrap_err(),
+            Error::Validation(
+                "../examples/locating-errors/phonebook.yaml:
+#[2].name: wrong type, expected string got integer
+#[2].age: wrong type, expected integer got string
+"
+                .into()
+            )
+        );
+
+        std::fs::remove_file(&baseline).unwrap();
+    }
+
+    #[test]
+    fn test_uri_pointer() {
+        let person = temp_file("uri-pointer.yaml");
+        write(&person, "name: Lisa\nage: forty\n").unwrap();
+
+        assert_eq!(
+            actual_main(Opt {
+                files: vec![person.clone()],
+                ..opt(
+                    &["../examples/locating-errors/schema.yaml"],
+                    &[],
+                    "phonebook#/items"
+                )
+            })
+            .unwrap_err(),
+            Error::Validation(format!(
+                "{}:\n#.age: wrong type, expected integer got string\n",
+                person.to_string_lossy()
+            ))
+        );
+
+        std::fs::remove_file(&person).unwrap();
+    }
+
+    #[test]
+    fn test_fail_fast() {
+        let ages = temp_file("fail-fast.yaml");
+        write(&ages, "- name: Lisa\n  age: forty\n").unwrap();
+
+        let options = |fail_fast: bool, format: Format| Opt {
+            files: vec![
+                "../examples/locating-errors/phonebook.yaml".into(),
+                "../examples/locating-errors/valid-phonebook.yaml".into(),
+                ages.clone(),
+            ],
+            format,
+            fail_fast,
+            ..opt(
+                &["../examples/locating-errors/schema.yaml"],
+                &[],
+                "phonebook",
+            )
+        };
+
+        let first = "../examples/locating-errors/phonebook.yaml:
+#[1].age: wrong type, expected integer got real
+#[2].name: wrong type, expected string got integer
+#[2].age: wrong type, expected integer got string
+";
+
+        assert_eq!(
+            actual_main(options(false, Format::Human)).unwrap_err(),
+            Error::Validation(format!(
+                "{}{}:\n#[0].age: wrong type, expected integer got string\n",
+                first,
+                ages.to_string_lossy()
+            ))
+        );
+
+        assert_eq!(
+            actual_main(options(true, Format::Human)).unwrap_err(),
+            Error::Validation(first.into())
+        );
+
+        // Files after the first failure are not validated at all.
+        let report = actual_main(options(true, Format::Tap)).unwrap();
+        assert!(!report.passed);
+        assert!(report
+            .output
+            .starts_with("TAP version 13\n1..1\nnot ok 1 - "));
+
+        std::fs::remove_file(&ages).unwrap();
+    }
+
+    #[test]
+    fn test_fail_fast_flags() {
+        let parse = |flags: &[&str]| {
+            let args = ["yaml-validator-cli", "--uri", "phonebook"];
+            Opt::from_iter(args.iter().chain(flags)).fail_fast
+        };
+
+        assert!(!parse(&[]));
+        assert!(parse(&["--fail-fast"]));
+        assert!(!parse(&["--no-fail-fast"]));
+        assert!(!parse(&["--fail-fast", "--no-fail-fast"]));
+        assert!(parse(&["--no-fail-fast", "--fail-fast"]));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("say \"hi\"\\\n"), r#""say \"hi\"\\\n""#);
+    }
 }
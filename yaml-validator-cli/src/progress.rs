@@ -0,0 +1,76 @@
+use std::io::Write;
+
+/// Count of files validated so far, redrawn in place on a single line.
+///
+/// Nothing is written while disabled, so callers decide whether the writer is a terminal.
+pub struct Progress<W: Write> {
+    out: W,
+    enabled: bool,
+    drawn: usize,
+}
+
+impl<W: Write> Progress<W> {
+    pub fn new(out: W, enabled: bool) -> Self {
+        Progress {
+            out,
+            enabled,
+            drawn: 0,
+        }
+    }
+
+    pub fn update(&mut self, done: usize, total: usize) {
+        if !self.enabled {
+            return;
+        }
+
+        let line = format!("validated {}/{} files", done, total);
+        // Failing to draw progress should never fail the run itself.
+        let _ = write!(self.out, "\r{}", line).and_then(|_| self.out.flush());
+        self.drawn = line.len();
+    }
+
+    /// Erase the progress line, so that it is not mixed up with any output that follows.
+    pub fn finish(&mut self) {
+        if self.drawn > 0 {
+            let _ =
+                write!(self.out, "\r{}\r", " ".repeat(self.drawn)).and_then(|_| self.out.flush());
+            self.drawn = 0;
+        }
+    }
+
+    #[cfg(test)]
+    fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::new(Vec::new(), true);
+        progress.update(1, 12);
+        progress.update(12, 12);
+        progress.finish();
+        progress.finish();
+
+        assert_eq!(
+            String::from_utf8(progress.into_inner()).unwrap(),
+            format!(
+                "\rvalidated 1/12 files\rvalidated 12/12 files\r{}\r",
+                " ".repeat(21)
+            )
+        );
+    }
+
+    #[test]
+    fn test_progress_disabled() {
+        let mut progress = Progress::new(Vec::new(), false);
+        progress.update(1, 1);
+        progress.finish();
+
+        assert!(progress.into_inner().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+//! Rough timings of validation for a few kinds of schemas, run with `cargo bench`.
+//!
+//! Schemas and documents are generated in memory, so only validation itself is measured.
+//! Allocations are counted as well, since valid documents should not need any.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use yaml_validator::yaml_rust::{Yaml, YamlLoader};
+use yaml_validator::{Context, Validate};
+
+const ITERATIONS: u32 = 20;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn load(source: &str) -> Vec<Yaml> {
+    YamlLoader::load_from_str(source).unwrap()
+}
+
+fn bench(name: &str, schemas: &str, uri: &str, document: &str) {
+    let schemas = load(schemas);
+    let context = Context::try_from(&schemas[..]).unwrap();
+    let schema = context.get_schema(uri).unwrap();
+    let document = load(document).remove(0);
+
+    // Make sure the benchmark measures successful validation, not early failure.
+    schema.validate(&context, &document).unwrap();
+
+    let mut total = Duration::default();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        schema.validate(&context, &document).unwrap();
+        total += start.elapsed();
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<24} {:>12?} per validation, {:>8} allocations",
+        name,
+        total / ITERATIONS,
+        allocations / ITERATIONS as usize
+    );
+}
+
+fn large_array() {
+    let document: String = (0..100_000).map(|i| format!("- {}\n", i)).collect();
+
+    bench(
+        "large array",
+        "uri: numbers\nschema:\n  type: array\n  items:\n    type: integer\n    minimum: 0\n",
+        "numbers",
+        &document,
+    );
+}
+
+fn nested_objects() {
+    let depth = 100;
+    let mut schema = String::from("uri: nested\nschema:\n");
+    let mut document = String::new();
+
+    for level in 0..depth {
+        let indent = "  ".repeat(level * 2 + 1);
+        schema.push_str(&format!("{}type: object\n{}items:\n", indent, indent));
+        schema.push_str(&format!("{}  child:\n", indent));
+        document.push_str(&format!("{}child:\n", "  ".repeat(level)));
+    }
+
+    let indent = "  ".repeat(depth * 2 + 1);
+    schema.push_str(&format!("{}type: string\n", indent));
+    document.push_str(&format!("{}value\n", "  ".repeat(depth)));
+
+    bench("nested objects", &schema, "nested", &document);
+}
+
+fn references() {
+    let document: String = (0..20_000)
+        .map(|i| format!("- name: person {}\n  age: {}\n", i, i % 100))
+        .collect();
+
+    bench(
+        "references",
+        r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+---
+uri: people
+schema:
+  type: array
+  items:
+    $ref: person
+"#,
+        "people",
+        &document,
+    );
+}
+
+fn required_fields() {
+    let fields = 50;
+    let mut schema = String::from("uri: record\nschema:\n  type: object\n  items:\n");
+    let mut document = String::new();
+
+    for field in 0..fields {
+        schema.push_str(&format!("    field{}:\n      type: integer\n", field));
+        document.push_str(&format!("field{}: {}\n", field, field));
+    }
+
+    let required: Vec<String> = (0..fields).map(|field| format!("field{}", field)).collect();
+    schema.push_str(&format!("  required: [{}]\n", required.join(", ")));
+
+    bench("required fields", &schema, "record", &document);
+}
+
+#[cfg(feature = "regex")]
+fn regex_strings() {
+    let document: String = (0..20_000)
+        .map(|i| format!("- user{}@example{}.com\n", i, i % 10))
+        .collect();
+
+    bench(
+        "regex strings",
+        r#"
+uri: emails
+schema:
+  type: array
+  items:
+    type: string
+    pattern: "^[a-z0-9.]+@[a-z0-9]+\\.[a-z]+$"
+"#,
+        "emails",
+        &document,
+    );
+}
+
+fn main() {
+    large_array();
+    nested_objects();
+    references();
+    required_fields();
+    #[cfg(feature = "regex")]
+    regex_strings();
+}
@@ -55,6 +55,11 @@ impl<'a> Breadcrumb<'a> {
     pub fn push(&mut self, segment: BreadcrumbSegment<'a>) {
         self.segments.push(segment);
     }
+
+    /// Segments from the innermost to the outermost level of the document.
+    pub(crate) fn segments(&self) -> &[BreadcrumbSegment<'a>] {
+        &self.segments
+    }
 }
 
 impl<'a> std::fmt::Display for Breadcrumb<'a> {
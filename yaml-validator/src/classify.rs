@@ -0,0 +1,152 @@
+use crate::errors::ValidationError;
+use crate::{Context, Validate};
+use thiserror::Error;
+use yaml_rust::Yaml;
+
+/// Errors encountered while classifying a document with [Context::classify].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ClassifyError<'a> {
+    #[error("context contains no schemas")]
+    NoSchemas,
+    /// The document fits none of the schemas. The closest is the one producing the fewest errors,
+    /// as chosen by [Context::validate_best].
+    #[error("document does not validate against any schema, the closest is '{closest}':\n{error}")]
+    NoMatch {
+        closest: &'a str,
+        error: ValidationError<'a>,
+    },
+    #[error("document validates against more than one schema: {}", .uris.join(", "))]
+    Ambiguous { uris: Vec<&'a str> },
+}
+
+impl<'schema> Context<'schema> {
+    /// Validate a document against every schema in the context, returning the uri of the only
+    /// schema it validates against, much like a `oneOf` of every schema in the context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    /// ---
+    /// uri: number
+    /// schema:
+    ///     type: integer
+    /// ---
+    /// uri: text
+    /// schema:
+    ///     type: string
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let document = YamlLoader::load_from_str("hello").unwrap().remove(0);
+    ///
+    /// assert_eq!(context.classify(&document), Ok("text"));
+    /// ```
+    pub fn classify<'yaml>(
+        &'schema self,
+        yaml: &'yaml Yaml,
+    ) -> Result<&'schema str, ClassifyError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        let (matches, failures): (Vec<_>, Vec<_>) = self
+            .uris
+            .iter()
+            .map(|(uri, index)| (*uri, self.schemas[*index].validate(self, yaml)))
+            .partition(|(_, result)| result.is_ok());
+
+        match &matches[..] {
+            [(uri, _)] => return Ok(uri),
+            [] => {}
+            _ => {
+                return Err(ClassifyError::Ambiguous {
+                    uris: matches.into_iter().map(|(uri, _)| uri).collect(),
+                })
+            }
+        }
+
+        failures
+            .into_iter()
+            .filter_map(|(uri, result)| Some((uri, result.err()?)))
+            .min_by_key(|(_, error)| error.leaf_count())
+            .map_or(Err(ClassifyError::NoSchemas), |(closest, error)| {
+                Err(ClassifyError::NoMatch { closest, error })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ValidationErrorKind;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn classify_documents() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+  required: [name, age]
+---
+uri: pet
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    species:
+      type: string
+  required: [name, species]
+---
+uri: tag
+schema:
+  type: string
+---
+uri: title
+schema:
+  type: string
+  maxLength: 20
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let documents =
+            YamlLoader::load_from_str("name: Bob\nage: 30\n---\nname: Rex\n---\nRex").unwrap();
+
+        assert_eq!(ctx.classify(&documents[0]), Ok("person"));
+
+        assert_eq!(
+            ctx.classify(&documents[1]),
+            Err(ClassifyError::NoMatch {
+                closest: "person",
+                error: ValidationErrorKind::FieldMissing { field: "age" }.into()
+            })
+        );
+
+        assert_eq!(
+            ctx.classify(&documents[2]),
+            Err(ClassifyError::Ambiguous {
+                uris: vec!["tag", "title"]
+            })
+        );
+
+        assert_eq!(
+            Context::default().classify(&documents[0]),
+            Err(ClassifyError::NoSchemas)
+        );
+    }
+}
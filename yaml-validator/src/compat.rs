@@ -0,0 +1,800 @@
+use crate::diff::{constraints, list, strip_nullable, type_name};
+use crate::types::object::Conditional;
+use crate::utils::{Limit, UnitValue};
+use crate::visit::{children, Child};
+use crate::{PropertyType, Schema, SchemaObject};
+use std::fmt::Display;
+use std::ops::Sub;
+
+/// A change to a schema which may reject documents that the previous version accepted,
+/// as found by [is_backward_compatible].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    /// Names leading from the uri of the schema to the node which changed
+    pub schema_path: Vec<String>,
+    pub kind: IncompatibilityKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IncompatibilityKind {
+    /// A field which was optional, or not listed at all, is now required
+    RequiredAdded {
+        field: String,
+    },
+    /// A property was removed from an object which does not accept unlisted properties
+    PropertyRemoved {
+        name: String,
+    },
+    /// The node describes a different kind of value, such as a string rather than an integer
+    TypeNarrowed {
+        old: String,
+        new: String,
+    },
+    NullableRemoved,
+    EnumValueRemoved {
+        value: String,
+    },
+    /// A constraint was added, or now accepts fewer values. Constraints which can not be
+    /// compared, such as patterns, are reported whenever they change.
+    ConstraintTightened {
+        keyword: &'static str,
+        old: Option<String>,
+        new: String,
+    },
+}
+
+impl std::fmt::Display for IncompatibilityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IncompatibilityKind::RequiredAdded { field } => {
+                write!(f, "field '{}' is now required", field)
+            }
+            IncompatibilityKind::PropertyRemoved { name } => {
+                write!(f, "property '{}' removed", name)
+            }
+            IncompatibilityKind::TypeNarrowed { old, new } => {
+                write!(f, "type changed from {} to {}", old, new)
+            }
+            IncompatibilityKind::NullableRemoved => write!(f, "no longer nullable"),
+            IncompatibilityKind::EnumValueRemoved { value } => {
+                write!(f, "enum value {} removed", value)
+            }
+            IncompatibilityKind::ConstraintTightened {
+                keyword,
+                old: Some(old),
+                new,
+            } => write!(f, "{} tightened from {} to {}", keyword, old, new),
+            IncompatibilityKind::ConstraintTightened {
+                keyword,
+                old: None,
+                new,
+            } => write!(f, "{} {} added", keyword, new),
+        }
+    }
+}
+
+impl std::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.schema_path.join(" -> "), self.kind)
+    }
+}
+
+fn limit_keyword<T>(
+    limit: &Limit<T>,
+    inclusive: &'static str,
+    exclusive: &'static str,
+) -> &'static str
+where
+    T: UnitValue,
+    <T as Sub>::Output: UnitValue,
+{
+    if limit.is_inclusive() {
+        inclusive
+    } else {
+        exclusive
+    }
+}
+
+struct Checker {
+    path: Vec<String>,
+    incompatibilities: Vec<Incompatibility>,
+}
+
+impl Checker {
+    fn push(&mut self, kind: IncompatibilityKind) {
+        self.incompatibilities.push(Incompatibility {
+            schema_path: self.path.clone(),
+            kind,
+        });
+    }
+
+    fn tightened(&mut self, keyword: &'static str, old: Option<impl Display>, new: impl Display) {
+        self.push(IncompatibilityKind::ConstraintTightened {
+            keyword,
+            old: old.map(|old| old.to_string()),
+            new: new.to_string(),
+        });
+    }
+
+    fn nested(&mut self, name: String, old: &PropertyType, new: &PropertyType) {
+        self.path.push(name);
+        self.check(old, new);
+        self.path.pop();
+    }
+
+    fn min_bound(&mut self, keyword: &'static str, old: Option<usize>, new: Option<usize>) {
+        if let Some(new) = new {
+            if old.is_none_or(|old| new > old) {
+                self.tightened(keyword, old, new);
+            }
+        }
+    }
+
+    fn max_bound(&mut self, keyword: &'static str, old: Option<usize>, new: Option<usize>) {
+        if let Some(new) = new {
+            if old.is_none_or(|old| new < old) {
+                self.tightened(keyword, old, new);
+            }
+        }
+    }
+
+    fn limits<T>(
+        &mut self,
+        (old_minimum, old_maximum): (&Option<Limit<T>>, &Option<Limit<T>>),
+        (new_minimum, new_maximum): (&Option<Limit<T>>, &Option<Limit<T>>),
+    ) where
+        T: UnitValue + Display,
+        <T as Sub>::Output: UnitValue,
+    {
+        // A limit is tighter when its threshold moves inwards, or stays put but becomes exclusive.
+        let tighter = |old: &Limit<T>, new: &Limit<T>, inwards: bool| {
+            (new.value() != old.value() && inwards)
+                || (new.value() == old.value() && old.is_inclusive() && !new.is_inclusive())
+        };
+
+        if let Some(new) = new_minimum {
+            if old_minimum
+                .as_ref()
+                .is_none_or(|old| tighter(old, new, new.value() > old.value()))
+            {
+                self.tightened(
+                    limit_keyword(new, "minimum", "exclusiveMinimum"),
+                    old_minimum.as_ref().map(Limit::value),
+                    new.value(),
+                );
+            }
+        }
+
+        if let Some(new) = new_maximum {
+            if old_maximum
+                .as_ref()
+                .is_none_or(|old| tighter(old, new, new.value() < old.value()))
+            {
+                self.tightened(
+                    limit_keyword(new, "maximum", "exclusiveMaximum"),
+                    old_maximum.as_ref().map(Limit::value),
+                    new.value(),
+                );
+            }
+        }
+    }
+
+    fn enumeration<T: PartialEq + Display>(&mut self, old: &Option<Vec<T>>, new: &Option<Vec<T>>) {
+        match (old, new) {
+            (Some(old), Some(new)) => {
+                for value in old.iter().filter(|value| !new.contains(value)) {
+                    self.push(IncompatibilityKind::EnumValueRemoved {
+                        value: value.to_string(),
+                    });
+                }
+            }
+            (None, Some(new)) => self.tightened(
+                "enum",
+                None::<String>,
+                format!(
+                    "[{}]",
+                    new.iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Reports a change in the number of schemas listed under the keyword. With `widens`,
+    /// as for anyOf, additional schemas accept more documents, and otherwise any change is reported.
+    fn count(&mut self, keyword: &'static str, old: usize, new: usize, widens: bool) {
+        if new < old || (new > old && !widens) {
+            self.tightened(
+                keyword,
+                Some(format!("{} schemas", old)),
+                format!("{} schemas", new),
+            );
+        }
+    }
+
+    fn check(&mut self, old: &PropertyType, new: &PropertyType) {
+        let (old, old_nullable) = strip_nullable(old);
+        let (new, new_nullable) = strip_nullable(new);
+
+        let (old_type, new_type) = (type_name(old), type_name(new));
+        if old_type != new_type {
+            self.push(IncompatibilityKind::TypeNarrowed {
+                old: old_type,
+                new: new_type,
+            });
+            return;
+        }
+
+        if old_nullable && !new_nullable {
+            self.push(IncompatibilityKind::NullableRemoved);
+        }
+
+        // Keywords compared below, with any others reported whenever they are added or changed.
+        let compared: &[&str] = match (old, new) {
+            (PropertyType::Object(old), PropertyType::Object(new)) => {
+                let old_required = old.required.as_deref().unwrap_or_default();
+
+                for field in new.required.iter().flatten() {
+                    if !old_required.contains(field) {
+                        self.push(IncompatibilityKind::RequiredAdded {
+                            field: field.to_string(),
+                        });
+                    }
+                }
+
+                self.object(old, new);
+                &[
+                    "unevaluatedProperties",
+                    "aliases",
+                    "additionalPropertyNamePattern",
+                    "conditional",
+                ]
+            }
+            (PropertyType::Array(old), PropertyType::Array(new)) => {
+                self.min_bound("minItems", old.min_items, new.min_items);
+                self.max_bound("maxItems", old.max_items, new.max_items);
+                self.count(
+                    "prefixItems",
+                    old.prefix_items.len(),
+                    new.prefix_items.len(),
+                    false,
+                );
+                &["minItems", "maxItems"]
+            }
+            (PropertyType::Hash(old), PropertyType::Hash(new)) => {
+                for field in &new.required_keys {
+                    if !old.required_keys.contains(field) {
+                        self.push(IncompatibilityKind::RequiredAdded {
+                            field: field.to_string(),
+                        });
+                    }
+                }
+                &[]
+            }
+            (PropertyType::String(old), PropertyType::String(new)) => {
+                self.min_bound("minLength", old.min_length, new.min_length);
+                self.max_bound("maxLength", old.max_length, new.max_length);
+                self.enumeration(&old.enumeration, &new.enumeration);
+                &["minLength", "maxLength", "enum"]
+            }
+            (PropertyType::Integer(old), PropertyType::Integer(new)) => {
+                self.limits((&old.minimum, &old.maximum), (&new.minimum, &new.maximum));
+                self.enumeration(&old.enumeration, &new.enumeration);
+
+                if old.coerce && !new.coerce {
+                    self.tightened("coerce", Some(true), false);
+                }
+
+                &[
+                    "minimum",
+                    "exclusiveMinimum",
+                    "maximum",
+                    "exclusiveMaximum",
+                    "enum",
+                    "coerce",
+                ]
+            }
+            (PropertyType::Real(old), PropertyType::Real(new)) => {
+                self.limits((&old.minimum, &old.maximum), (&new.minimum, &new.maximum));
+                self.enumeration(&old.enumeration, &new.enumeration);
+                &[
+                    "minimum",
+                    "exclusiveMinimum",
+                    "maximum",
+                    "exclusiveMaximum",
+                    "enum",
+                ]
+            }
+            (PropertyType::OneOf(old), PropertyType::OneOf(new)) => {
+                self.count("oneOf", old.items.len(), new.items.len(), false);
+                &[]
+            }
+            (PropertyType::AllOf(old), PropertyType::AllOf(new)) => {
+                self.count("allOf", old.items.len(), new.items.len(), false);
+                &[]
+            }
+            (PropertyType::AnyOf(old), PropertyType::AnyOf(new)) => {
+                self.count("anyOf", old.items.len(), new.items.len(), true);
+                &[]
+            }
+            (
+                PropertyType::Composite {
+                    base: old_base,
+                    modifiers: old_modifiers,
+                },
+                PropertyType::Composite {
+                    base: new_base,
+                    modifiers: new_modifiers,
+                },
+            ) => {
+                let old_nodes = old_base.iter().map(AsRef::as_ref).chain(old_modifiers);
+                let new_nodes = new_base.iter().map(AsRef::as_ref).chain(new_modifiers);
+
+                for (old, new) in old_nodes.zip(new_nodes) {
+                    self.check(old, new);
+                }
+                &[]
+            }
+            _ => &[],
+        };
+
+        self.uncompared(old, new, compared);
+        self.children(old, new);
+    }
+
+    /// Reports every constraint which is not among the compared keywords, and was either
+    /// added or changed. Most cannot be ordered, such as patterns, references and constants,
+    /// and the rest are conservatively treated the same way.
+    fn uncompared(&mut self, old: &PropertyType, new: &PropertyType, compared: &[&str]) {
+        let old = constraints(old);
+
+        for (keyword, value) in constraints(new) {
+            if compared.contains(&keyword) {
+                continue;
+            }
+
+            match old.iter().find(|(other, _)| *other == keyword) {
+                Some((_, other)) if *other == value => {}
+                other => self.tightened(keyword, other.map(|(_, other)| other), value),
+            }
+        }
+    }
+
+    /// Compares the keywords of objects which accept more documents when present, along
+    /// with the schemas of their conditionals.
+    fn object(&mut self, old: &SchemaObject, new: &SchemaObject) {
+        if old.unevaluated_properties == Some(true) && new.unevaluated_properties != Some(true) {
+            self.tightened(
+                "unevaluatedProperties",
+                Some(true),
+                new.unevaluated_properties.unwrap_or(false),
+            );
+        }
+
+        for (name, old_aliases) in &old.aliases {
+            let new_aliases = new.aliases.get(name).map(Vec::as_slice).unwrap_or_default();
+
+            if old_aliases.iter().any(|alias| !new_aliases.contains(alias)) {
+                self.tightened(
+                    "aliases",
+                    Some(format!("{}: {}", name, list(old_aliases))),
+                    format!("{}: {}", name, list(new_aliases)),
+                );
+            }
+        }
+
+        #[cfg(feature = "regex")]
+        if let Some(old_pattern) = &old.additional_property_name_pattern {
+            let new_pattern = new.additional_property_name_pattern.as_ref();
+
+            if new_pattern.map(regex::Regex::as_str) != Some(old_pattern.as_str()) {
+                self.tightened(
+                    "additionalPropertyNamePattern",
+                    Some(format!("'{}'", old_pattern)),
+                    new_pattern.map_or("none".into(), |pattern| format!("'{}'", pattern)),
+                );
+            }
+        }
+
+        for (name, new_conditional) in &new.conditionals {
+            let condition = |conditional: &Conditional| {
+                format!(
+                    "{}: if {} equals {:?}",
+                    name, conditional.field, conditional.equals
+                )
+            };
+
+            let old_conditional = match old.conditionals.get(name) {
+                Some(old_conditional)
+                    if old_conditional.field == new_conditional.field
+                        && old_conditional.equals == new_conditional.equals =>
+                {
+                    old_conditional
+                }
+                other => {
+                    self.tightened(
+                        "conditional",
+                        other.map(condition),
+                        condition(new_conditional),
+                    );
+                    continue;
+                }
+            };
+
+            self.path.push("conditional".into());
+            self.path.push(name.to_string());
+            self.nested("then".into(), &old_conditional.then, &new_conditional.then);
+
+            match (&old_conditional.otherwise, &new_conditional.otherwise) {
+                (Some(old), Some(new)) => self.nested("else".into(), old, new),
+                (None, Some(new)) => self.tightened("else", None::<String>, type_name(new)),
+                _ => {}
+            }
+
+            self.path.truncate(self.path.len() - 2);
+        }
+    }
+
+    /// Compares the nested schemas of both versions, reporting those which no longer accept
+    /// what they did, or which were added and may reject values that were previously unchecked.
+    fn children(&mut self, old: &PropertyType, new: &PropertyType) {
+        let old_children = children(old);
+        let new_children = children(new);
+        let find = |children: &[(Child, _)], child| {
+            children
+                .iter()
+                .find(|(other, _)| *other == child)
+                .map(|(_, node)| *node)
+        };
+
+        // Properties of objects which accept unlisted properties may still be present once removed.
+        let accepts_unlisted = matches!(new, PropertyType::Object(object) if object.unevaluated_properties == Some(true));
+
+        for (child, old_node) in &old_children {
+            match (child, find(&new_children, *child)) {
+                // The inner schema of a not must accept no more than before, so its versions are swapped.
+                (Child::Not, Some(new_node)) => self.nested(child.to_string(), new_node, old_node),
+                (_, Some(new_node)) => self.nested(child.to_string(), old_node, new_node),
+                (Child::Property(name), None) if !accepts_unlisted => {
+                    self.push(IncompatibilityKind::PropertyRemoved {
+                        name: name.to_string(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        for (child, new_node) in &new_children {
+            let keyword = match child {
+                Child::Items => "items",
+                Child::Contains => "contains",
+                _ => continue,
+            };
+
+            if find(&old_children, *child).is_none() {
+                self.tightened(keyword, None::<String>, type_name(new_node));
+            }
+        }
+    }
+}
+
+/// Check whether every document which validates against the old version of a schema also
+/// validates against the new version, listing each change which may reject such documents.
+///
+/// The check is conservative: constraints which can not be compared, such as patterns and
+/// references, are considered incompatible whenever they are added or changed, and so are
+/// any others which are not compared explicitly, such as `uniqueItems` or `multipleOf`. Nodes are compared by their
+/// position within the schemas, as with [Schema::diff].
+///
+/// # Examples
+///
+/// ```rust
+/// # use yaml_rust::YamlLoader;
+/// # use std::convert::TryFrom;
+/// # use yaml_validator::{is_backward_compatible, Schema};
+/// #
+/// let versions = YamlLoader::load_from_str(r#"
+/// ---
+/// uri: port
+/// schema:
+///     type: integer
+///     maximum: 1024
+/// ---
+/// uri: port
+/// schema:
+///     type: integer
+///     maximum: 65535
+/// "#).unwrap();
+///
+/// let old = Schema::try_from(&versions[0]).unwrap();
+/// let new = Schema::try_from(&versions[1]).unwrap();
+///
+/// assert!(is_backward_compatible(&old, &new).is_ok());
+/// assert_eq!(
+///     is_backward_compatible(&new, &old).unwrap_err()[0].to_string(),
+///     "port: maximum tightened from 65535 to 1024"
+/// );
+/// ```
+pub fn is_backward_compatible(old: &Schema, new: &Schema) -> Result<(), Vec<Incompatibility>> {
+    let mut checker = Checker {
+        path: vec![new.uri.to_string()],
+        incompatibilities: Vec::new(),
+    };
+
+    checker.check(&old.schema, &new.schema);
+
+    if checker.incompatibilities.is_empty() {
+        Ok(())
+    } else {
+        Err(checker.incompatibilities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    fn versions(source: &str) -> Vec<yaml_rust::Yaml> {
+        YamlLoader::load_from_str(source).unwrap()
+    }
+
+    #[test]
+    fn compatible_widening() {
+        let yaml = versions(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+      maxLength: 20
+    age:
+      type: integer
+      minimum: 18
+    role:
+      type: string
+      enum: [admin, user]
+  required: [name, age]
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+      maxLength: 40
+    age:
+      type: integer
+      minimum: 0
+    role:
+      type: string
+      enum: [admin, user, guest]
+      nullable: true
+    email:
+      type: string
+  required: [name]
+"#,
+        );
+
+        let old = Schema::try_from(&yaml[0]).unwrap();
+        let new = Schema::try_from(&yaml[1]).unwrap();
+
+        is_backward_compatible(&old, &new).unwrap();
+    }
+
+    #[test]
+    fn incompatible_tightening() {
+        let yaml = versions(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+      exclusiveMinimum: 0
+    role:
+      type: string
+      enum: [admin, user, guest]
+      nullable: true
+    tags:
+      type: array
+      items:
+        type: string
+  required: [name]
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+      minLength: 1
+    age:
+      type: real
+    role:
+      type: string
+      enum: [admin, user]
+    tags:
+      type: array
+      maxItems: 5
+      items:
+        type: string
+  required: [name, role]
+"#,
+        );
+
+        let old = Schema::try_from(&yaml[0]).unwrap();
+        let new = Schema::try_from(&yaml[1]).unwrap();
+
+        let incompatibilities: Vec<String> = is_backward_compatible(&old, &new)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            incompatibilities,
+            vec![
+                "person: field 'role' is now required",
+                "person -> age: type changed from integer to real",
+                "person -> name: minLength 1 added",
+                "person -> role: no longer nullable",
+                "person -> role: enum value guest removed",
+                "person -> tags: maxItems 5 added",
+            ]
+        );
+    }
+
+    #[test]
+    fn tightened_limits() {
+        let yaml = versions(
+            r#"
+---
+uri: ratio
+schema:
+  type: real
+  minimum: 0.0
+  maximum: 1.0
+---
+uri: ratio
+schema:
+  type: real
+  exclusiveMinimum: 0.0
+  maximum: 1.0
+"#,
+        );
+
+        let old = Schema::try_from(&yaml[0]).unwrap();
+        let new = Schema::try_from(&yaml[1]).unwrap();
+
+        assert_eq!(
+            is_backward_compatible(&old, &new).unwrap_err(),
+            vec![Incompatibility {
+                schema_path: vec!["ratio".into()],
+                kind: IncompatibilityKind::ConstraintTightened {
+                    keyword: "exclusiveMinimum",
+                    old: Some("0".into()),
+                    new: "0".into(),
+                }
+            }]
+        );
+
+        is_backward_compatible(&new, &old).unwrap();
+    }
+
+    #[test]
+    fn uncompared_constraints() {
+        let yaml = versions(
+            r#"
+---
+uri: order
+schema:
+  type: object
+  unevaluatedProperties: true
+  items:
+    kind:
+      type: string
+    code:
+      type: string
+    password:
+      type: string
+    confirm:
+      type: string
+    lines:
+      type: array
+      items:
+        type: integer
+  conditional:
+    code:
+      if:
+        field: kind
+        equals: product
+      then:
+        type: string
+        maxLength: 10
+---
+uri: order
+schema:
+  type: object
+  items:
+    kind:
+      type: string
+      singleLine: true
+    code:
+      type: string
+      maxByteLength: 8
+    password:
+      type: string
+    confirm:
+      type: string
+      equalTo: password
+    lines:
+      type: array
+      uniqueItems: true
+      minUniqueItems: 2
+      items:
+        type: integer
+        multipleOf: 5
+  conditional:
+    code:
+      if:
+        field: kind
+        equals: product
+      then:
+        type: string
+        maxLength: 5
+"#,
+        );
+
+        let old = Schema::try_from(&yaml[0]).unwrap();
+        let new = Schema::try_from(&yaml[1]).unwrap();
+
+        let incompatibilities: Vec<String> = is_backward_compatible(&old, &new)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            incompatibilities,
+            vec![
+                "order: unevaluatedProperties tightened from true to false",
+                "order -> conditional -> code -> then: maxLength tightened from 10 to 5",
+                "order: equalTo {confirm: password} added",
+                "order -> code: maxByteLength 8 added",
+                "order -> kind: singleLine true added",
+                "order -> lines: uniqueItems true added",
+                "order -> lines: minUniqueItems 2 added",
+                "order -> lines -> items: multipleOf [5] added",
+            ]
+        );
+
+        is_backward_compatible(&new, &old).unwrap();
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn require_nfc_added() {
+        let yaml = versions("---\nuri: name\nschema:\n  type: string\n---\nuri: name\nschema:\n  type: string\n  requireNfc: true\n");
+
+        let old = Schema::try_from(&yaml[0]).unwrap();
+        let new = Schema::try_from(&yaml[1]).unwrap();
+
+        assert_eq!(
+            is_backward_compatible(&old, &new).unwrap_err()[0].to_string(),
+            "name: requireNfc true added"
+        );
+    }
+}
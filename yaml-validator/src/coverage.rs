@@ -0,0 +1,359 @@
+use crate::visit::children;
+use crate::{Context, PropertyType};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Key of each node coverage is recorded for, by address.
+type Keys = HashMap<usize, Vec<String>>;
+
+/// Records which parts of the schemas were exercised by the documents validated with it,
+/// so that schema test suites can find branches and properties none of their documents reach.
+///
+/// Coverage is only recorded when passed to validation through [ValidationOptions::coverage()](crate::ValidationOptions::coverage()),
+/// and covers which branches of `oneOf` and `anyOf` matched, and which optional properties
+/// of objects were present. Both are recorded by the uri of their schema and the path to them
+/// within it, and only for the schemas within the context validated against.
+///
+/// # Examples
+///
+/// ```rust
+/// # use yaml_rust::YamlLoader;
+/// # use std::convert::TryFrom;
+/// # use yaml_validator::{Context, Coverage, Validate, ValidationOptions};
+/// #
+/// let schemas = YamlLoader::load_from_str(r#"
+///     uri: port
+///     schema:
+///         oneOf:
+///             - type: integer
+///             - type: string
+/// "#).unwrap();
+///
+/// let context = Context::try_from(&schemas[..]).unwrap();
+/// let document = YamlLoader::load_from_str("8080").unwrap().remove(0);
+///
+/// let coverage = Coverage::default();
+/// let opts = ValidationOptions::default().coverage(&coverage);
+///
+/// context.get_schema("port").unwrap()
+///     .validate_with(&context, &document, &opts).unwrap();
+///
+/// let report: Vec<String> = coverage.report(&context).iter().map(ToString::to_string).collect();
+/// assert_eq!(report, vec![
+///     "port: oneOf branch 0 covered",
+///     "port: oneOf branch 1 not covered",
+/// ]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Coverage {
+    // The id of the context coverage was last recorded in, and the key of each of its
+    // oneOf, anyOf and object nodes by address, since nodes never move once parsed.
+    index: RefCell<Option<(usize, Keys)>>,
+    // Branches which matched, by the uri and path of the oneOf or anyOf they belong to,
+    // followed by its keyword.
+    branches: RefCell<BTreeMap<Vec<String>, BTreeSet<usize>>>,
+    // Optional properties which were present, by the uri and path of their object.
+    properties: RefCell<BTreeMap<Vec<String>, BTreeSet<String>>>,
+}
+
+// Only what was recorded is compared, so coverage of the same schemas loaded into
+// different contexts is equal.
+impl PartialEq for Coverage {
+    fn eq(&self, other: &Self) -> bool {
+        self.branches == other.branches && self.properties == other.properties
+    }
+}
+
+impl Eq for Coverage {}
+
+/// A single branch or optional property, and whether any validated document exercised it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CoverageItem {
+    /// Names leading from the uri of the schema to the node containing the rule
+    pub schema_path: Vec<String>,
+    /// The branch or property, such as `oneOf branch 1` or `optional property 'phone'`
+    pub rule: String,
+    pub covered: bool,
+}
+
+impl std::fmt::Display for CoverageItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} {}",
+            self.schema_path.join(" -> "),
+            self.rule,
+            if self.covered {
+                "covered"
+            } else {
+                "not covered"
+            }
+        )
+    }
+}
+
+/// Calls `f` with every node of the schema, along with the names leading to it.
+/// The parts of nullable and composite nodes are passed along with the same names.
+fn walk<'a, 'schema>(
+    node: &'a PropertyType<'schema>,
+    path: &mut Vec<String>,
+    f: &mut impl FnMut(&'a PropertyType<'schema>, &[String]),
+) {
+    f(node, path);
+
+    match node {
+        PropertyType::Nullable(inner) => walk(inner, path, f),
+        PropertyType::Composite { base, modifiers } => {
+            for node in base.as_deref().into_iter().chain(modifiers) {
+                walk(node, path, f);
+            }
+        }
+        _ => {}
+    }
+
+    for (child, item) in children(node) {
+        path.push(child.to_string());
+        walk(item, path, f);
+        path.pop();
+    }
+}
+
+/// Calls `f` with every node of every schema in the context, along with its uri and path.
+fn walk_context<'schema>(
+    ctx: &'schema Context<'schema>,
+    f: &mut impl FnMut(&'schema PropertyType<'schema>, &[String]),
+) {
+    for (uri, index) in &ctx.uris {
+        walk(&ctx.schemas[*index].schema, &mut vec![uri.to_string()], f);
+    }
+}
+
+/// The key under which the branches of a oneOf or anyOf are recorded.
+fn branches_key(path: &[String], keyword: &str) -> Vec<String> {
+    let mut key = path.to_vec();
+    key.push(keyword.to_string());
+    key
+}
+
+impl Coverage {
+    /// The uri and path of the node within the context, by which its coverage is recorded.
+    /// Nodes which are not part of any schema within the context are not recorded.
+    fn key<T>(&self, ctx: &Context, node: &T) -> Option<Vec<String>> {
+        let mut index = self.index.borrow_mut();
+
+        if index.as_ref().is_none_or(|(id, _)| *id != ctx.id()) {
+            let mut keys = HashMap::new();
+            walk_context(ctx, &mut |node, path| {
+                let (address, key) = match node {
+                    PropertyType::Object(object) => (object as *const _ as usize, path.to_vec()),
+                    PropertyType::OneOf(one_of) => {
+                        (one_of as *const _ as usize, branches_key(path, "oneOf"))
+                    }
+                    PropertyType::AnyOf(any_of) => {
+                        (any_of as *const _ as usize, branches_key(path, "anyOf"))
+                    }
+                    _ => return,
+                };
+                keys.insert(address, key);
+            });
+            *index = Some((ctx.id(), keys));
+        }
+
+        index
+            .as_ref()
+            .and_then(|(_, keys)| keys.get(&(node as *const T as usize)))
+            .cloned()
+    }
+
+    pub(crate) fn record_branch<T>(&self, ctx: &Context, node: &T, branch: usize) {
+        if let Some(key) = self.key(ctx, node) {
+            self.branches
+                .borrow_mut()
+                .entry(key)
+                .or_default()
+                .insert(branch);
+        }
+    }
+
+    pub(crate) fn record_property<T>(&self, ctx: &Context, node: &T, name: &str) {
+        if let Some(key) = self.key(ctx, node) {
+            self.properties
+                .borrow_mut()
+                .entry(key)
+                .or_default()
+                .insert(name.to_string());
+        }
+    }
+
+    /// Every branch and optional property of the schemas in the context, in order of uri,
+    /// along with whether it was covered. Referenced schemas are reported under their own uri.
+    pub fn report(&self, ctx: &Context) -> Vec<CoverageItem> {
+        let branches = self.branches.borrow();
+        let properties = self.properties.borrow();
+        let mut items = Vec::new();
+
+        let branch_items = |keyword: &str, count: usize, path: &[String]| {
+            let covered = branches.get(&branches_key(path, keyword));
+
+            (0..count)
+                .map(|i| CoverageItem {
+                    schema_path: path.to_vec(),
+                    rule: format!("{} branch {}", keyword, i),
+                    covered: covered.is_some_and(|covered| covered.contains(&i)),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        walk_context(ctx, &mut |node, path| match node {
+            PropertyType::Object(object) => {
+                let present = properties.get(path);
+
+                for name in object.items.keys() {
+                    if !object.required.iter().flatten().any(|field| field == name) {
+                        items.push(CoverageItem {
+                            schema_path: path.to_vec(),
+                            rule: format!("optional property '{}'", name),
+                            covered: present.is_some_and(|present| present.contains(*name)),
+                        });
+                    }
+                }
+            }
+            PropertyType::OneOf(one_of) => {
+                items.extend(branch_items("oneOf", one_of.items.len(), path))
+            }
+            PropertyType::AnyOf(any_of) => {
+                items.extend(branch_items("anyOf", any_of.items.len(), path))
+            }
+            _ => {}
+        });
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Validate, ValidationOptions};
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn record_coverage() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    nickname:
+      type: string
+    phone:
+      oneOf:
+        - type: integer
+        - type: string
+    tags:
+      type: array
+      items:
+        anyOf:
+          - type: string
+          - type: integer
+  required: [name]
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+        let schema = context.get_schema("person").unwrap();
+
+        let coverage = Coverage::default();
+        let opts = ValidationOptions::default().coverage(&coverage);
+
+        let documents = YamlLoader::load_from_str(
+            "name: Bob\nphone: 12345\n---\nname: Alice\ntags: [admin]\n---\nname: Eve\nphone: [1]",
+        )
+        .unwrap();
+
+        for document in &documents {
+            let _ = schema.validate_with(&context, document, &opts);
+        }
+
+        let report: Vec<String> = coverage
+            .report(&context)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            report,
+            vec![
+                "person: optional property 'nickname' not covered",
+                "person: optional property 'phone' covered",
+                "person: optional property 'tags' covered",
+                "person -> phone: oneOf branch 0 covered",
+                "person -> phone: oneOf branch 1 not covered",
+                "person -> tags -> items: anyOf branch 0 covered",
+                "person -> tags -> items: anyOf branch 1 not covered",
+            ]
+        );
+    }
+
+    #[test]
+    fn coverage_by_schema_path() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: row
+schema:
+  type: array
+  prefixItems:
+    - oneOf:
+        - type: integer
+        - type: string
+  items:
+    anyOf:
+      - type: string
+      - type: boolean
+"#,
+        )
+        .unwrap();
+
+        let document = YamlLoader::load_from_str("[1, true]").unwrap().remove(0);
+
+        let record = || {
+            let context = Context::try_from(&yaml[..]).unwrap();
+            let coverage = Coverage::default();
+            let opts = ValidationOptions::default().coverage(&coverage);
+
+            context
+                .get_schema("row")
+                .unwrap()
+                .validate_with(&context, &document, &opts)
+                .unwrap();
+
+            let report: Vec<String> = coverage
+                .report(&context)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            (coverage, report)
+        };
+
+        let (first, report) = record();
+        assert_eq!(
+            report,
+            vec![
+                "row -> prefixItems[0]: oneOf branch 0 covered",
+                "row -> prefixItems[0]: oneOf branch 1 not covered",
+                "row -> items: anyOf branch 0 not covered",
+                "row -> items: anyOf branch 1 covered",
+            ]
+        );
+
+        // Coverage of the same schemas loaded into another context is the same.
+        let (second, _) = record();
+        assert_eq!(first, second);
+        assert_ne!(first, Coverage::default());
+    }
+}
@@ -0,0 +1,204 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+/// The number of decimal digits which always fit in an i128.
+const MAX_DIGITS: i64 = 38;
+
+/// An exact decimal number, represented as `mantissa * 10^-scale`.
+///
+/// Used for checking `multipleOf` and the bounds of reals, where the binary representation
+/// of f64 makes values such as 0.3 fail to be a multiple of 0.1, and rounds away the difference
+/// between values such as 0.3 and 0.30000000000000001.
+///
+/// Only parsing, comparison and divisibility are needed, none of which lose precision within
+/// an i128, so this is used rather than taking on rust_decimal as a dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Parse the textual representation of a real as written in the yaml source,
+    /// returning None for values which cannot be represented, such as `.inf`.
+    pub(crate) fn parse(source: &str) -> Option<Decimal> {
+        let (number, exponent) = match source.find(['e', 'E']) {
+            Some(index) => (&source[..index], source[index + 1..].parse::<i32>().ok()?),
+            None => (source, 0),
+        };
+
+        let (negative, number) = match number.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+
+        if integer.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let mut mantissa: i128 = 0;
+        for digit in integer.chars().chain(fraction.chars()) {
+            mantissa = mantissa
+                .checked_mul(10)?
+                .checked_add(i128::from(digit.to_digit(10)?))?;
+        }
+
+        // Zero is zero regardless of its exponent, which may be arbitrarily large.
+        if mantissa == 0 {
+            return Some(Decimal {
+                mantissa: 0,
+                scale: 0,
+            });
+        }
+
+        let mut scale = fraction.len() as i64 - i64::from(exponent);
+
+        // Powers of ten beyond the digits of an i128 could never be rescaled for comparison.
+        if scale.abs() > MAX_DIGITS {
+            return None;
+        }
+
+        // A negative scale means the number is a whole multiple of a power of ten.
+        if scale < 0 {
+            mantissa = mantissa.checked_mul(10i128.checked_pow(scale.unsigned_abs() as u32)?)?;
+            scale = 0;
+        }
+
+        Some(Decimal {
+            mantissa: if negative { -mantissa } else { mantissa },
+            scale: u32::try_from(scale).ok()?,
+        })
+    }
+
+    fn rescale(&self, scale: u32) -> Option<i128> {
+        self.mantissa
+            .checked_mul(10i128.checked_pow(scale - self.scale)?)
+    }
+
+    /// Whether this number is an exact multiple of `other`, or None if the
+    /// numbers are too large to be compared.
+    pub(crate) fn is_multiple_of(&self, other: &Decimal) -> Option<bool> {
+        let scale = self.scale.max(other.scale);
+        let divisor = other.rescale(scale)?;
+
+        if divisor == 0 {
+            return None;
+        }
+
+        Some(self.rescale(scale)? % divisor == 0)
+    }
+
+    /// Compares this number to `other`, or None if the numbers are too large to be compared.
+    pub(crate) fn compare(&self, other: &Decimal) -> Option<Ordering> {
+        let scale = self.scale.max(other.scale);
+
+        Some(self.rescale(scale)?.cmp(&other.rescale(scale)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decimal;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn parse_decimals() {
+        assert_eq!(
+            Decimal::parse("0.3"),
+            Some(Decimal {
+                mantissa: 3,
+                scale: 1
+            })
+        );
+        assert_eq!(
+            Decimal::parse("-12.50"),
+            Some(Decimal {
+                mantissa: -1250,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            Decimal::parse("1.5e2"),
+            Some(Decimal {
+                mantissa: 150,
+                scale: 0
+            })
+        );
+        assert_eq!(
+            Decimal::parse("25E-3"),
+            Some(Decimal {
+                mantissa: 25,
+                scale: 3
+            })
+        );
+        assert_eq!(Decimal::parse(".inf"), None);
+        assert_eq!(Decimal::parse("."), None);
+    }
+
+    #[test]
+    fn parse_large_exponents() {
+        assert_eq!(
+            Decimal::parse("0e999999999"),
+            Some(Decimal {
+                mantissa: 0,
+                scale: 0
+            })
+        );
+        assert_eq!(
+            Decimal::parse("0.0e-999999999"),
+            Some(Decimal {
+                mantissa: 0,
+                scale: 0
+            })
+        );
+        assert_eq!(Decimal::parse("1e999999999"), None);
+        assert_eq!(Decimal::parse("1e-999999999"), None);
+        assert_eq!(
+            Decimal::parse("1e38"),
+            Some(Decimal {
+                mantissa: 10i128.pow(38),
+                scale: 0
+            })
+        );
+    }
+
+    #[test]
+    fn multiples() {
+        let tenth = Decimal::parse("0.1").unwrap();
+
+        assert_eq!(
+            Decimal::parse("0.3").unwrap().is_multiple_of(&tenth),
+            Some(true)
+        );
+        assert_eq!(
+            Decimal::parse("7").unwrap().is_multiple_of(&tenth),
+            Some(true)
+        );
+        assert_eq!(
+            Decimal::parse("0.35").unwrap().is_multiple_of(&tenth),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn comparisons() {
+        let limit = Decimal::parse("0.3").unwrap();
+
+        assert_eq!(
+            Decimal::parse("0.30000000000000001")
+                .unwrap()
+                .compare(&limit),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            Decimal::parse("3e-1").unwrap().compare(&limit),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Decimal::parse("-1").unwrap().compare(&limit),
+            Some(Ordering::Less)
+        );
+    }
+}
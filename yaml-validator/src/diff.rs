@@ -0,0 +1,786 @@
+use crate::utils::{Limit, UnitValue};
+use crate::visit::{children, Child};
+use crate::{PropertyType, Schema};
+use std::fmt::Display;
+use std::ops::Sub;
+
+/// A single difference between two versions of a schema, as found by [Schema::diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    /// Names leading from the uri of the schema to the node which changed
+    pub schema_path: Vec<String>,
+    pub kind: SchemaChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaChangeKind {
+    PropertyAdded {
+        name: String,
+    },
+    PropertyRemoved {
+        name: String,
+    },
+    /// A field which was optional, or not listed at all, is now required
+    RequiredAdded {
+        field: String,
+    },
+    RequiredRemoved {
+        field: String,
+    },
+    /// The node describes a different kind of value, such as a string rather than an integer.
+    /// Nothing within the node is compared once its type has changed.
+    TypeChanged {
+        old: String,
+        new: String,
+    },
+    ConstraintAdded {
+        keyword: &'static str,
+        value: String,
+    },
+    ConstraintRemoved {
+        keyword: &'static str,
+        value: String,
+    },
+    ConstraintChanged {
+        keyword: &'static str,
+        old: String,
+        new: String,
+    },
+}
+
+impl std::fmt::Display for SchemaChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaChangeKind::PropertyAdded { name } => write!(f, "property '{}' added", name),
+            SchemaChangeKind::PropertyRemoved { name } => write!(f, "property '{}' removed", name),
+            SchemaChangeKind::RequiredAdded { field } => {
+                write!(f, "field '{}' is now required", field)
+            }
+            SchemaChangeKind::RequiredRemoved { field } => {
+                write!(f, "field '{}' is no longer required", field)
+            }
+            SchemaChangeKind::TypeChanged { old, new } => {
+                write!(f, "type changed from {} to {}", old, new)
+            }
+            SchemaChangeKind::ConstraintAdded { keyword, value } => {
+                write!(f, "{} {} added", keyword, value)
+            }
+            SchemaChangeKind::ConstraintRemoved { keyword, value } => {
+                write!(f, "{} {} removed", keyword, value)
+            }
+            SchemaChangeKind::ConstraintChanged { keyword, old, new } => {
+                write!(f, "{} changed from {} to {}", keyword, old, new)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.schema_path.join(" -> "), self.kind)
+    }
+}
+
+/// Removes any number of `nullable` wrappers, returning the node within and whether there were any.
+pub(crate) fn strip_nullable<'n, 'schema>(
+    mut node: &'n PropertyType<'schema>,
+) -> (&'n PropertyType<'schema>, bool) {
+    let mut nullable = false;
+
+    while let PropertyType::Nullable(inner) = node {
+        node = inner;
+        nullable = true;
+    }
+
+    (node, nullable)
+}
+
+/// Name of the kind of value described by a node, which must be the same in two versions of
+/// a schema for their constraints to be compared.
+pub(crate) fn type_name(node: &PropertyType) -> String {
+    match node {
+        PropertyType::Object(_) => "object".into(),
+        PropertyType::Array(_) => "array".into(),
+        PropertyType::Hash(_) => "hash".into(),
+        PropertyType::String(_) => "string".into(),
+        PropertyType::Integer(_) => "integer".into(),
+        PropertyType::Real(_) => "real".into(),
+        PropertyType::Bool(_) => "boolean".into(),
+        PropertyType::Reference(_) => "reference".into(),
+        PropertyType::Not(_) => "not".into(),
+        PropertyType::OneOf(_) => "oneOf".into(),
+        PropertyType::AllOf(_) => "allOf".into(),
+        PropertyType::AnyOf(_) => "anyOf".into(),
+        PropertyType::Const(_) => "const".into(),
+        PropertyType::Tag(_) => "tag".into(),
+        PropertyType::Nullable(inner) => type_name(inner),
+        PropertyType::Custom(custom) => format!("custom type '{}'", custom.typename),
+        PropertyType::Composite { base, modifiers } => base
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(modifiers)
+            .map(type_name)
+            .collect::<Vec<String>>()
+            .join(" and "),
+    }
+}
+
+fn limits<T>(
+    constraints: &mut Vec<(&'static str, String)>,
+    minimum: &Option<Limit<T>>,
+    maximum: &Option<Limit<T>>,
+) where
+    T: UnitValue + Display,
+    <T as Sub>::Output: UnitValue,
+{
+    if let Some(minimum) = minimum {
+        let keyword = if minimum.is_inclusive() {
+            "minimum"
+        } else {
+            "exclusiveMinimum"
+        };
+        constraints.push((keyword, minimum.value().to_string()));
+    }
+
+    if let Some(maximum) = maximum {
+        let keyword = if maximum.is_inclusive() {
+            "maximum"
+        } else {
+            "exclusiveMaximum"
+        };
+        constraints.push((keyword, maximum.value().to_string()));
+    }
+}
+
+/// Lists values as `[a, b]`.
+pub(crate) fn list<T: Display>(values: impl IntoIterator<Item = T>) -> String {
+    let values: Vec<String> = values.into_iter().map(|value| value.to_string()).collect();
+    format!("[{}]", values.join(", "))
+}
+
+/// Lists pairs as `{a: b, c: d}`.
+fn map<K: Display, V: Display>(pairs: impl IntoIterator<Item = (K, V)>) -> String {
+    let pairs: Vec<String> = pairs
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect();
+    format!("{{{}}}", pairs.join(", "))
+}
+
+fn length(
+    constraints: &mut Vec<(&'static str, String)>,
+    keyword: &'static str,
+    value: Option<usize>,
+) {
+    if let Some(value) = value {
+        constraints.push((keyword, value.to_string()));
+    }
+}
+
+fn flag(constraints: &mut Vec<(&'static str, String)>, keyword: &'static str, value: bool) {
+    if value {
+        constraints.push((keyword, "true".into()));
+    }
+}
+
+/// Every constraint of a node by keyword, other than its nested schemas.
+pub(crate) fn constraints(node: &PropertyType) -> Vec<(&'static str, String)> {
+    let mut constraints = Vec::new();
+
+    match node {
+        PropertyType::String(string) => {
+            length(&mut constraints, "minLength", string.min_length);
+            length(&mut constraints, "maxLength", string.max_length);
+            length(&mut constraints, "minByteLength", string.min_byte_length);
+            length(&mut constraints, "maxByteLength", string.max_byte_length);
+            flag(&mut constraints, "singleLine", string.single_line);
+
+            #[cfg(feature = "unicode-normalization")]
+            flag(&mut constraints, "requireNfc", string.require_nfc);
+
+            #[cfg(feature = "regex")]
+            {
+                if let Some(pattern) = &string.pattern {
+                    constraints.push(("pattern", format!("'{}'", pattern)));
+                }
+
+                if let Some(group) = &string.length_of {
+                    constraints.push(("lengthOf", group.to_string()));
+                }
+
+                let quoted = |patterns: &[regex::Regex]| {
+                    list(patterns.iter().map(|pattern| format!("'{}'", pattern)))
+                };
+
+                if !string.all_patterns.is_empty() {
+                    constraints.push(("allPatterns", quoted(&string.all_patterns)));
+                }
+
+                if !string.any_pattern.is_empty() {
+                    constraints.push(("anyPattern", quoted(&string.any_pattern)));
+                }
+            }
+
+            if let Some(enumeration) = &string.enumeration {
+                constraints.push(("enum", list(enumeration)));
+            }
+
+            if let Some(enum_map) = &string.enum_map {
+                constraints.push((
+                    "enumMap",
+                    map(enum_map.iter().map(|(input, canonical)| (input, canonical))),
+                ));
+            }
+
+            if let Some(format) = string.format {
+                constraints.push(("format", format.name().to_string()));
+            }
+
+            if let Some(format) = &string.custom_format {
+                constraints.push(("format", format.clone()));
+            }
+
+            #[cfg(feature = "date")]
+            {
+                if let Some(minimum) = &string.minimum {
+                    constraints.push(("minimum", minimum.to_string()));
+                }
+
+                if let Some(maximum) = &string.maximum {
+                    constraints.push(("maximum", maximum.to_string()));
+                }
+            }
+        }
+        PropertyType::Integer(integer) => {
+            limits(&mut constraints, &integer.minimum, &integer.maximum);
+
+            if !integer.multiple_of.is_empty() {
+                constraints.push(("multipleOf", list(&integer.multiple_of)));
+            }
+
+            if let Some(enumeration) = &integer.enumeration {
+                constraints.push(("enum", list(enumeration)));
+            }
+
+            flag(&mut constraints, "coerce", integer.coerce);
+
+            if let Some(format) = integer.format {
+                constraints.push(("format", format.name().to_string()));
+            }
+        }
+        PropertyType::Real(real) => {
+            limits(&mut constraints, &real.minimum, &real.maximum);
+
+            if !real.multiple_of.is_empty() {
+                constraints.push(("multipleOf", list(&real.multiple_of)));
+            }
+
+            if let Some(enumeration) = &real.enumeration {
+                constraints.push(("enum", list(enumeration)));
+            }
+        }
+        PropertyType::Array(array) => {
+            length(&mut constraints, "minItems", array.min_items);
+            length(&mut constraints, "maxItems", array.max_items);
+            flag(&mut constraints, "uniqueItems", array.unique_items);
+            length(&mut constraints, "minUniqueItems", array.min_unique_items);
+            length(&mut constraints, "maxUniqueItems", array.max_unique_items);
+            length(&mut constraints, "minContains", array.min_contains);
+            length(&mut constraints, "maxContains", array.max_contains);
+
+            if let Some(sorted) = array.sorted {
+                constraints.push(("sorted", sorted.name().to_string()));
+            }
+        }
+        PropertyType::Object(object) => {
+            flag(
+                &mut constraints,
+                "requireNonEmpty",
+                object.require_non_empty,
+            );
+
+            if !object.require_non_blank.is_empty() {
+                constraints.push(("requireNonBlank", list(&object.require_non_blank)));
+            }
+
+            if !object.aliases.is_empty() {
+                constraints.push((
+                    "aliases",
+                    map(object
+                        .aliases
+                        .iter()
+                        .map(|(name, aliases)| (name, list(aliases)))),
+                ));
+            }
+
+            if !object.equal_to.is_empty() {
+                constraints.push(("equalTo", map(&object.equal_to)));
+            }
+
+            if !object.property_dependencies.is_empty() {
+                constraints.push((
+                    "propertyDependencies",
+                    map(object.property_dependencies.iter().map(|(name, cases)| {
+                        (
+                            name,
+                            map(cases
+                                .iter()
+                                .map(|(value, fields)| (format!("{:?}", value), list(fields)))),
+                        )
+                    })),
+                ));
+            }
+
+            if !object.conditional_required.is_empty() {
+                constraints.push((
+                    "conditionalRequired",
+                    list(object.conditional_required.iter().map(|conditional| {
+                        format!(
+                            "{} when {} equals {:?}",
+                            list(&conditional.require),
+                            conditional.field,
+                            conditional.equals
+                        )
+                    })),
+                ));
+            }
+
+            if !object.conditionals.is_empty() {
+                constraints.push((
+                    "conditional",
+                    map(object.conditionals.iter().map(|(name, conditional)| {
+                        (
+                            name,
+                            format!("if {} equals {:?}", conditional.field, conditional.equals),
+                        )
+                    })),
+                ));
+            }
+
+            if let Some(unevaluated_properties) = object.unevaluated_properties {
+                constraints.push(("unevaluatedProperties", unevaluated_properties.to_string()));
+            }
+
+            #[cfg(feature = "regex")]
+            {
+                if let Some(pattern) = &object.additional_property_name_pattern {
+                    constraints.push(("additionalPropertyNamePattern", format!("'{}'", pattern)));
+                }
+
+                if !object.pattern_required.is_empty() {
+                    constraints.push((
+                        "patternRequired",
+                        list(
+                            object
+                                .pattern_required
+                                .iter()
+                                .map(|pattern| format!("'{}'", pattern)),
+                        ),
+                    ));
+                }
+            }
+        }
+        PropertyType::Reference(reference) => {
+            constraints.push(("$ref", format!("'{}'", reference)))
+        }
+        PropertyType::Tag(tag) => constraints.push(("tag", format!("'{}'", tag.tag))),
+        PropertyType::Const(constant) => {
+            constraints.push(("const", format!("{:?}", constant.value)))
+        }
+        _ => {}
+    }
+
+    constraints
+}
+
+struct Differ {
+    path: Vec<String>,
+    changes: Vec<SchemaChange>,
+}
+
+impl Differ {
+    fn push(&mut self, kind: SchemaChangeKind) {
+        self.changes.push(SchemaChange {
+            schema_path: self.path.clone(),
+            kind,
+        });
+    }
+
+    fn nested(&mut self, name: String, old: &PropertyType, new: &PropertyType) {
+        self.path.push(name);
+        self.diff(old, new);
+        self.path.pop();
+    }
+
+    /// Reports a change in the number of schemas listed under the keyword, such as the branches of a oneOf.
+    fn count(&mut self, keyword: &'static str, old: usize, new: usize) {
+        if old != new {
+            self.push(SchemaChangeKind::ConstraintChanged {
+                keyword,
+                old: format!("{} schemas", old),
+                new: format!("{} schemas", new),
+            });
+        }
+    }
+
+    /// Reports a nested schema present in only one of the versions. Branches are
+    /// covered by [count](Differ::count) instead.
+    fn unpaired(&mut self, child: Child, node: &PropertyType, added: bool) {
+        let keyword = match child {
+            Child::Property(name) if added => {
+                return self.push(SchemaChangeKind::PropertyAdded { name: name.into() })
+            }
+            Child::Property(name) => {
+                return self.push(SchemaChangeKind::PropertyRemoved { name: name.into() })
+            }
+            Child::Items => "items",
+            Child::Contains => "contains",
+            _ => return,
+        };
+
+        let value = type_name(node);
+        self.push(if added {
+            SchemaChangeKind::ConstraintAdded { keyword, value }
+        } else {
+            SchemaChangeKind::ConstraintRemoved { keyword, value }
+        });
+    }
+
+    fn constraints(&mut self, old: &PropertyType, new: &PropertyType) {
+        let (old, new) = (constraints(old), constraints(new));
+
+        for (keyword, value) in &old {
+            match new.iter().find(|(other, _)| other == keyword) {
+                Some((_, other)) if other != value => {
+                    self.push(SchemaChangeKind::ConstraintChanged {
+                        keyword,
+                        old: value.clone(),
+                        new: other.clone(),
+                    })
+                }
+                Some(_) => {}
+                None => self.push(SchemaChangeKind::ConstraintRemoved {
+                    keyword,
+                    value: value.clone(),
+                }),
+            }
+        }
+
+        for (keyword, value) in new {
+            if !old.iter().any(|(other, _)| *other == keyword) {
+                self.push(SchemaChangeKind::ConstraintAdded { keyword, value });
+            }
+        }
+    }
+
+    fn diff(&mut self, old: &PropertyType, new: &PropertyType) {
+        let (old, old_nullable) = strip_nullable(old);
+        let (new, new_nullable) = strip_nullable(new);
+
+        let (old_type, new_type) = (type_name(old), type_name(new));
+        if old_type != new_type {
+            self.push(SchemaChangeKind::TypeChanged {
+                old: old_type,
+                new: new_type,
+            });
+            return;
+        }
+
+        match (old_nullable, new_nullable) {
+            (false, true) => self.push(SchemaChangeKind::ConstraintAdded {
+                keyword: "nullable",
+                value: "true".into(),
+            }),
+            (true, false) => self.push(SchemaChangeKind::ConstraintRemoved {
+                keyword: "nullable",
+                value: "true".into(),
+            }),
+            _ => {}
+        }
+
+        self.constraints(old, new);
+
+        let (old_children, new_children) = (children(old), children(new));
+        let find = |children: &[(Child, _)], child| {
+            children
+                .iter()
+                .find(|(other, _)| *other == child)
+                .map(|(_, node)| *node)
+        };
+
+        for (child, node) in &old_children {
+            if find(&new_children, *child).is_none() {
+                self.unpaired(*child, node, false);
+            }
+        }
+
+        for (child, node) in &new_children {
+            if find(&old_children, *child).is_none() {
+                self.unpaired(*child, node, true);
+            }
+        }
+
+        match (old, new) {
+            (PropertyType::Object(old), PropertyType::Object(new)) => self.required(
+                old.required.as_deref().unwrap_or_default(),
+                new.required.as_deref().unwrap_or_default(),
+            ),
+            (PropertyType::Array(old), PropertyType::Array(new)) => self.count(
+                "prefixItems",
+                old.prefix_items.len(),
+                new.prefix_items.len(),
+            ),
+            (PropertyType::Hash(old), PropertyType::Hash(new)) => {
+                self.required(&old.required_keys, &new.required_keys)
+            }
+            (PropertyType::OneOf(old), PropertyType::OneOf(new)) => {
+                self.count("oneOf", old.items.len(), new.items.len())
+            }
+            (PropertyType::AllOf(old), PropertyType::AllOf(new)) => {
+                self.count("allOf", old.items.len(), new.items.len())
+            }
+            (PropertyType::AnyOf(old), PropertyType::AnyOf(new)) => {
+                self.count("anyOf", old.items.len(), new.items.len())
+            }
+            (
+                PropertyType::Composite {
+                    base: old_base,
+                    modifiers: old_modifiers,
+                },
+                PropertyType::Composite {
+                    base: new_base,
+                    modifiers: new_modifiers,
+                },
+            ) => {
+                // Types are equal, so both have the same base and modifiers, in the same order.
+                let old_nodes = old_base.iter().map(AsRef::as_ref).chain(old_modifiers);
+                let new_nodes = new_base.iter().map(AsRef::as_ref).chain(new_modifiers);
+
+                for (old, new) in old_nodes.zip(new_nodes) {
+                    self.diff(old, new);
+                }
+            }
+            _ => {}
+        }
+
+        for (child, old) in &old_children {
+            if let Some(new) = find(&new_children, *child) {
+                self.nested(child.to_string(), old, new);
+            }
+        }
+    }
+
+    /// Compares the fields which must be present, either in an object or a hash.
+    fn required(&mut self, old: &[&str], new: &[&str]) {
+        for field in old.iter().filter(|field| !new.contains(field)) {
+            self.push(SchemaChangeKind::RequiredRemoved {
+                field: field.to_string(),
+            });
+        }
+
+        for field in new.iter().filter(|field| !old.contains(field)) {
+            self.push(SchemaChangeKind::RequiredAdded {
+                field: field.to_string(),
+            });
+        }
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Compare this schema against a later version of it, listing the properties, required
+    /// fields, types and constraints which were added, removed or changed between them.
+    ///
+    /// Nodes are compared by their position within the schemas, and references are
+    /// compared by uri rather than followed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Schema;
+    /// #
+    /// let versions = YamlLoader::load_from_str(r#"
+    /// ---
+    /// uri: person
+    /// schema:
+    ///     type: object
+    ///     items:
+    ///         name:
+    ///             type: string
+    /// ---
+    /// uri: person
+    /// schema:
+    ///     type: object
+    ///     items:
+    ///         name:
+    ///             type: string
+    ///             maxLength: 40
+    /// "#).unwrap();
+    ///
+    /// let old = Schema::try_from(&versions[0]).unwrap();
+    /// let new = Schema::try_from(&versions[1]).unwrap();
+    ///
+    /// assert_eq!(old.diff(&new)[0].to_string(), "person -> name: maxLength 40 added");
+    /// ```
+    pub fn diff(&self, other: &Schema) -> Vec<SchemaChange> {
+        let mut differ = Differ {
+            path: vec![self.uri.to_string()],
+            changes: Vec::new(),
+        };
+
+        differ.diff(&self.schema, &other.schema);
+        differ.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn diff_added_required_field() {
+        let versions = YamlLoader::load_from_str(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+      minimum: 0
+    nickname:
+      type: string
+  required: [name]
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+      minimum: 18
+    email:
+      type: string
+      nullable: true
+  required: [name, email]
+"#,
+        )
+        .unwrap();
+
+        let old = Schema::try_from(&versions[0]).unwrap();
+        let new = Schema::try_from(&versions[1]).unwrap();
+
+        let changes: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                "person: property 'nickname' removed",
+                "person: property 'email' added",
+                "person: field 'email' is now required",
+                "person -> age: minimum changed from 0 to 18",
+            ]
+        );
+
+        assert_eq!(
+            old.diff(&new)[2].kind,
+            SchemaChangeKind::RequiredAdded {
+                field: "email".into()
+            }
+        );
+
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn diff_changed_types() {
+        let versions = YamlLoader::load_from_str(
+            r#"
+---
+uri: config
+schema:
+  type: array
+  items:
+    type: integer
+---
+uri: config
+schema:
+  type: array
+  minItems: 1
+  items:
+    type: string
+    nullable: true
+"#,
+        )
+        .unwrap();
+
+        let old = Schema::try_from(&versions[0]).unwrap();
+        let new = Schema::try_from(&versions[1]).unwrap();
+
+        let changes: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                "config: minItems 1 added",
+                "config -> items: type changed from integer to string",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_nested_schemas() {
+        let versions = YamlLoader::load_from_str(
+            r#"
+---
+uri: row
+schema:
+  type: array
+  prefixItems:
+    - type: string
+  items:
+    not:
+      type: integer
+---
+uri: row
+schema:
+  type: array
+  prefixItems:
+    - type: string
+      minLength: 1
+    - type: integer
+  contains:
+    type: string
+  items:
+    not:
+      type: integer
+      minimum: 0
+"#,
+        )
+        .unwrap();
+
+        let old = Schema::try_from(&versions[0]).unwrap();
+        let new = Schema::try_from(&versions[1]).unwrap();
+
+        let changes: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                "row: contains string added",
+                "row: prefixItems changed from 1 schemas to 2 schemas",
+                "row -> prefixItems[0]: minLength 1 added",
+                "row -> items -> not: minimum 0 added",
+            ]
+        );
+    }
+}
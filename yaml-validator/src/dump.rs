@@ -0,0 +1,197 @@
+use crate::utils::{Limit, UnitValue};
+use crate::{Context, PropertyType};
+use std::fmt::Display;
+use std::ops::Sub;
+
+fn limits<T>(constraints: &mut Vec<String>, minimum: &Option<Limit<T>>, maximum: &Option<Limit<T>>)
+where
+    T: UnitValue + Display,
+    <T as Sub>::Output: UnitValue,
+{
+    if let Some(minimum) = minimum {
+        let keyword = if minimum.is_inclusive() {
+            "minimum"
+        } else {
+            "exclusiveMinimum"
+        };
+        constraints.push(format!("{} {}", keyword, minimum.value()));
+    }
+
+    if let Some(maximum) = maximum {
+        let keyword = if maximum.is_inclusive() {
+            "maximum"
+        } else {
+            "exclusiveMaximum"
+        };
+        constraints.push(format!("{} {}", keyword, maximum.value()));
+    }
+}
+
+fn bounds(constraints: &mut Vec<String>, keyword: &str, min: Option<usize>, max: Option<usize>) {
+    if let Some(min) = min {
+        constraints.push(format!("min{} {}", keyword, min));
+    }
+
+    if let Some(max) = max {
+        constraints.push(format!("max{} {}", keyword, max));
+    }
+}
+
+fn with_constraints(name: String, constraints: Vec<String>) -> String {
+    if constraints.is_empty() {
+        name
+    } else {
+        format!("{}, {}", name, constraints.join(", "))
+    }
+}
+
+/// A one-line description of a schema node, naming its type and most important constraints.
+fn summary(node: &PropertyType) -> String {
+    let mut constraints = Vec::new();
+
+    match node {
+        PropertyType::Object(object) => {
+            let names: Vec<&str> = object.items.keys().copied().collect();
+            constraints.push(format!("properties [{}]", names.join(", ")));
+
+            if let Some(required) = &object.required {
+                constraints.push(format!("required [{}]", required.join(", ")));
+            }
+
+            with_constraints("object".into(), constraints)
+        }
+        PropertyType::Array(array) => {
+            bounds(&mut constraints, "Items", array.min_items, array.max_items);
+
+            let name = match &array.items {
+                Some(items) => format!("array of ({})", summary(items)),
+                None => "array".into(),
+            };
+
+            with_constraints(name, constraints)
+        }
+        PropertyType::Hash(hash) => match &hash.items {
+            Some(items) => format!("hash of ({})", summary(items)),
+            None => "hash".into(),
+        },
+        PropertyType::String(string) => {
+            bounds(
+                &mut constraints,
+                "Length",
+                string.min_length,
+                string.max_length,
+            );
+
+            #[cfg(feature = "regex")]
+            if let Some(pattern) = &string.pattern {
+                constraints.push(format!("pattern '{}'", pattern));
+            }
+
+            if let Some(enumeration) = &string.enumeration {
+                constraints.push(format!("enum of {} values", enumeration.len()));
+            }
+
+            with_constraints("string".into(), constraints)
+        }
+        PropertyType::Integer(integer) => {
+            limits(&mut constraints, &integer.minimum, &integer.maximum);
+            with_constraints("integer".into(), constraints)
+        }
+        PropertyType::Real(real) => {
+            limits(&mut constraints, &real.minimum, &real.maximum);
+            with_constraints("real".into(), constraints)
+        }
+        PropertyType::Bool(_) => "boolean".into(),
+        PropertyType::Reference(reference) => format!("reference to '{}'", reference),
+        PropertyType::Not(not) => format!("not ({})", summary(&not.item)),
+        PropertyType::OneOf(one_of) => format!("oneOf {} schemas", one_of.items.len()),
+        PropertyType::AllOf(all_of) => format!("allOf {} schemas", all_of.items.len()),
+        PropertyType::AnyOf(any_of) => format!("anyOf {} schemas", any_of.items.len()),
+        PropertyType::Const(_) => "const".into(),
+        PropertyType::Tag(tag) => format!("tag '{}'", tag.tag),
+        PropertyType::Nullable(inner) => format!("nullable {}", summary(inner)),
+        PropertyType::Custom(custom) => format!("custom type '{}'", custom.typename),
+        PropertyType::Composite { base, modifiers } => {
+            let modifiers: Vec<String> = modifiers.iter().map(summary).collect();
+
+            match base {
+                Some(base) => format!("{} with {}", summary(base), modifiers.join(" and ")),
+                None => modifiers.join(" and "),
+            }
+        }
+    }
+}
+
+impl<'schema> Context<'schema> {
+    /// Describe every schema in the context on a line of its own, listing its uri alongside
+    /// the type and most important constraints of its root, in order of uri.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: port
+    ///     schema:
+    ///         type: integer
+    ///         minimum: 1
+    ///         maximum: 65535
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    ///
+    /// assert_eq!(context.debug_dump(), "port: integer, minimum 1, maximum 65535\n");
+    /// ```
+    pub fn debug_dump(&self) -> String {
+        self.uris
+            .iter()
+            .map(|(uri, index)| format!("{}: {}\n", uri, summary(&self.schemas[*index].schema)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Context;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn dump_context() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: phonebook
+schema:
+  type: array
+  minItems: 1
+  items:
+    $ref: person
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+      maxLength: 50
+    phone:
+      nullable: true
+      type: string
+  required: [name]
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+
+        assert_eq!(
+            context.debug_dump(),
+            "person: object, properties [name, phone], required [name]
+phonebook: array of (reference to 'person'), minItems 1
+"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+/// Stable identifiers for the messages of validation errors, which are only turned
+/// into text once the error is displayed.
+///
+/// Messages may contain placeholders such as `{value}`, which are filled in with the
+/// details of the error. The message of a [ValidationError](crate::ValidationErrorKind::ValidationError),
+/// [ParameterizedValidation](crate::ValidationErrorKind::ParameterizedValidation) or
+/// [DetailedValidation](crate::ValidationErrorKind::DetailedValidation) error is itself
+/// placed within the [RequirementNotMet](MessageId::RequirementNotMet) message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MessageId {
+    WrongType,
+    BadValue,
+    RequirementNotMet,
+    FieldMissing,
+    ExtraField,
+    UnknownType,
+    MultipleErrors,
+    UnknownSchema,
+    ContextRequired,
+    NoDocuments,
+    LowerLimit,
+    ExclusiveLowerLimit,
+    UpperLimit,
+    ExclusiveUpperLimit,
+    MultipleOf,
+    MinLength,
+    MaxLength,
+    MinByteLength,
+    MaxByteLength,
+    LineBreak,
+    NotNfc,
+    PatternMismatch,
+    MinItems,
+    MaxItems,
+    DuplicateItem,
+    ContainsNone,
+    AdditionalPropertyName,
+    ConstMismatch,
+    InversionMatched,
+    MultipleOneOf,
+    MultipleOneOfLabelled,
+    NotInEnum,
+    MinUniqueItems,
+    MaxUniqueItems,
+    IncomparableItems,
+    NotAscending,
+    NotDescending,
+    ContainsExactly,
+    ContainsBetween,
+    ContainsAtLeast,
+    UncheckedItems,
+    ItemsMismatch,
+    PatternRequired,
+    AliasConflict,
+    NotEqualTo,
+    PointerNotFound,
+    ReferenceLoop,
+    MustBe,
+    NotAnInteger,
+    IntegerOverflow,
+    NegativeTimestamp,
+    TimestampTooLarge,
+    AllPatterns,
+    AnyPattern,
+    InvalidFormat,
+    UnregisteredFormat,
+    CustomFormat,
+    DateBeforeMinimum,
+    DateAfterMaximum,
+    TagsUnavailable,
+    TagMismatch,
+    Untagged,
+}
+
+impl MessageId {
+    /// The message in the default english catalog.
+    pub fn english(self) -> &'static str {
+        match self {
+            MessageId::WrongType => "wrong type, expected {expected} got {actual}",
+            MessageId::BadValue => "value could not be parsed or is missing, expected {expected}",
+            MessageId::RequirementNotMet => "special requirements for field not met: {message}",
+            MessageId::FieldMissing => "field '{field}' missing",
+            MessageId::ExtraField => "field '{field}' is not specified in the schema",
+            MessageId::UnknownType => "unknown type specified: {unknown_type}",
+            MessageId::MultipleErrors => "multiple errors were encountered: {errors}",
+            MessageId::UnknownSchema => "schema '{uri}' references was not found",
+            MessageId::ContextRequired => {
+                "schema '{uri}' is referenced, which requires validating using a Context"
+            }
+            MessageId::NoDocuments => "no documents to validate",
+            MessageId::LowerLimit => "value {value} is below minimum {limit} (inclusive)",
+            MessageId::ExclusiveLowerLimit => {
+                "value {value} is not above minimum {limit} (exclusive)"
+            }
+            MessageId::UpperLimit => "value {value} is above maximum {limit} (inclusive)",
+            MessageId::ExclusiveUpperLimit => {
+                "value {value} is not below maximum {limit} (exclusive)"
+            }
+            MessageId::MultipleOf => "value must be a multiple of the multipleOf field",
+            MessageId::MinLength => "string length is less than minLength",
+            MessageId::MaxLength => "string length is greater than maxLength",
+            MessageId::MinByteLength => "string is fewer than minByteLength bytes long",
+            MessageId::MaxByteLength => "string is more than maxByteLength bytes long",
+            MessageId::LineBreak => "string must be a single line, but contains a line break",
+            MessageId::NotNfc => "string is not in unicode normalization form C",
+            MessageId::PatternMismatch => "supplied value does not match regex pattern for field",
+            MessageId::MinItems => "array contains fewer than minItems items",
+            MessageId::MaxItems => "array contains more than maxItems items",
+            MessageId::DuplicateItem => "array contains duplicate key",
+            MessageId::ContainsNone => {
+                "at least one item in the array must match the 'contains' schema"
+            }
+            MessageId::AdditionalPropertyName => {
+                "property name does not match additionalPropertyNamePattern"
+            }
+            MessageId::ConstMismatch => "value does not equal the constant given in 'const'",
+            MessageId::InversionMatched => {
+                "validation inversion failed because inner result matched"
+            }
+            MessageId::MultipleOneOf => {
+                "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch"
+            }
+            MessageId::MultipleOneOfLabelled => {
+                "branch '{label}' validated successfully, but oneOf must only contain a single valid branch"
+            }
+            MessageId::NotInEnum => "value {value} is not one of [{allowed}]",
+            MessageId::MinUniqueItems => {
+                "array has {distinct} distinct items, fewer than minUniqueItems of {limit}"
+            }
+            MessageId::MaxUniqueItems => {
+                "array has {distinct} distinct items, more than maxUniqueItems of {limit}"
+            }
+            MessageId::IncomparableItems => "cannot compare {left} with {right} to check sort order",
+            MessageId::NotAscending => "array is not sorted in ascending order",
+            MessageId::NotDescending => "array is not sorted in descending order",
+            MessageId::ContainsExactly => {
+                "expected exactly {min} items matching the schema in 'contains', found {found} \
+                 (matching indices: {matching}, other indices: {other})"
+            }
+            MessageId::ContainsBetween => {
+                "expected between {min} and {max} items matching the schema in 'contains', \
+                 found {found} (matching indices: {matching}, other indices: {other})"
+            }
+            MessageId::ContainsAtLeast => {
+                "expected at least {min} items matching the schema in 'contains', found {found} \
+                 (matching indices: {matching}, other indices: {other})"
+            }
+            MessageId::UncheckedItems => {
+                "stopped after {invalid} invalid items, leaving {unchecked} more items unchecked"
+            }
+            MessageId::ItemsMismatch => {
+                "items at indices {indices} do not match the schema in 'items'"
+            }
+            MessageId::PatternRequired => {
+                "no property name matches the patternRequired pattern '{pattern}'"
+            }
+            MessageId::AliasConflict => "property is an alias of '{name}', which is also present",
+            MessageId::NotEqualTo => "value of '{key}' must equal the value of '{other}'",
+            MessageId::PointerNotFound => "'#{pointer}' does not point to a node within schema '{uri}'",
+            MessageId::ReferenceLoop => {
+                "reference to '{uri}' loops back to itself without descending into the value"
+            }
+            MessageId::MustBe => "value must be {expected}",
+            MessageId::NotAnInteger => "value {value} is not an integer",
+            MessageId::IntegerOverflow => "value {value} does not fit in an integer",
+            MessageId::NegativeTimestamp => "unix timestamp {value} cannot be negative",
+            MessageId::TimestampTooLarge => {
+                "unix timestamp {value} is past the year 9999, and may not be given in seconds"
+            }
+            MessageId::AllPatterns => "value does not match the allPatterns pattern '{pattern}'",
+            MessageId::AnyPattern => {
+                "value does not match any of the anyPattern patterns [{patterns}]"
+            }
+            MessageId::InvalidFormat => "'{value}' is not a valid {format}",
+            MessageId::UnregisteredFormat => "format '{format}' is not registered with the context",
+            MessageId::CustomFormat => "'{value}' is not a valid {format}: {reason}",
+            MessageId::DateBeforeMinimum => "date {value} is before the minimum of {limit}",
+            MessageId::DateAfterMaximum => "date {value} is after the maximum of {limit}",
+            MessageId::TagsUnavailable => {
+                "tag '{tag}' can only be checked on documents loaded as TaggedDocuments"
+            }
+            MessageId::TagMismatch => "expected tag '{expected}', found '{actual}'",
+            MessageId::Untagged => "expected tag '{expected}', but the value is untagged",
+        }
+    }
+}
+
+impl std::fmt::Display for MessageId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.english())
+    }
+}
+
+/// Replaces each `{name}` placeholder in a message with the argument of that name, leaving
+/// unknown placeholders as they are. Arguments are not themselves searched for placeholders.
+pub(crate) fn fill(message: &str, args: &[(&str, String)]) -> String {
+    let mut filled = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let arg = rest.find('}').and_then(|end| {
+            args.iter()
+                .find(|(name, _)| *name == &rest[1..end])
+                .map(|(_, value)| (end, value))
+        });
+
+        match arg {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+/// The message of the given catalog filled in with the arguments, falling back to english.
+pub(crate) fn localize(
+    catalog: &dyn MessageCatalog,
+    id: MessageId,
+    args: &[(&str, String)],
+) -> String {
+    fill(catalog.message(id).unwrap_or_else(|| id.english()), args)
+}
+
+/// A set of translations for the validation messages, used with
+/// [ValidationError::localized](crate::ValidationError::localized).
+pub trait MessageCatalog {
+    /// The translation of a message, or None to fall back to english. Translations
+    /// must keep the placeholders of the english message, such as `{value}`.
+    fn message(&self, id: MessageId) -> Option<&str>;
+}
+
+/// The default catalog, in which every message is in english.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct English;
+
+impl MessageCatalog for English {
+    fn message(&self, id: MessageId) -> Option<&str> {
+        Some(id.english())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!(
+            fill(
+                "value {value} is above maximum {limit}",
+                &[("value", "{limit}".into()), ("limit", "10".into())]
+            ),
+            "value {limit} is above maximum 10"
+        );
+
+        assert_eq!(fill("{unknown} {", &[]), "{unknown} {");
+    }
+}
@@ -1,6 +1,8 @@
+pub(crate) mod messages;
 pub(crate) mod schema;
 pub(crate) mod validation;
 
+pub use messages::MessageId;
 pub use schema::{SchemaError, SchemaErrorKind};
 pub use validation::{ValidationError, ValidationErrorKind};
 
@@ -13,6 +15,8 @@ pub enum GenericError<'a> {
         expected: &'static str,
         actual: &'a str,
     },
+    #[error("value could not be parsed or is missing, expected {expected}")]
+    BadValue { expected: &'static str },
     #[error("field '{field}' missing")]
     FieldMissing { field: &'a str },
     #[error("field '{field}' is not specified in the schema")]
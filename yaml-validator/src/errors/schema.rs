@@ -12,16 +12,22 @@ pub enum SchemaErrorKind<'a> {
         expected: &'static str,
         actual: &'a str,
     },
+    #[error("value could not be parsed or is missing, expected {expected}")]
+    BadValue { expected: &'static str },
     #[error("malformed field: {error}")]
     MalformedField { error: String },
     #[error("field '{field}' missing")]
     FieldMissing { field: &'a str },
     #[error("field '{field}' is not specified in the schema")]
     ExtraField { field: &'a str },
+    #[error("field '{field}' is not specified in the schema, did you mean '{suggestion}'?")]
+    MisspelledField { field: &'a str, suggestion: &'a str },
     #[error("unknown type specified: {unknown_type}")]
     UnknownType { unknown_type: &'a str },
     #[error("multiple errors were encountered: {errors:?}")]
     Multiple { errors: Vec<SchemaError<'a>> },
+    #[error("schema '{uri}' is defined more than once")]
+    DuplicateSchema { uri: &'a str },
 }
 
 /// A wrapper type around SchemaErrorKind containing path information about where the error occurred.
@@ -108,6 +114,7 @@ impl<'a> From<GenericError<'a>> for SchemaErrorKind<'a> {
             GenericError::WrongType { expected, actual } => {
                 SchemaErrorKind::WrongType { expected, actual }
             }
+            GenericError::BadValue { expected } => SchemaErrorKind::BadValue { expected },
             GenericError::FieldMissing { field } => SchemaErrorKind::FieldMissing { field },
             GenericError::ExtraField { field } => SchemaErrorKind::ExtraField { field },
             GenericError::Multiple { errors } => SchemaErrorKind::Multiple {
@@ -1,28 +1,53 @@
-use thiserror::Error;
-
 use crate::breadcrumb::{Breadcrumb, BreadcrumbSegment, BreadcrumbSegmentVec};
 
-use super::GenericError;
+use super::messages::{localize, English, MessageCatalog};
+use super::{GenericError, MessageId};
 
-#[derive(Error, Debug, PartialEq, Eq)]
+/// The kinds of validation error, each displayed using the [MessageId] of the same name
+/// or, for errors with a message of their own, [RequirementNotMet](MessageId::RequirementNotMet).
+///
+/// [ValidationError](ValidationErrorKind::ValidationError) holds the [MessageId] of its message
+/// rather than the text itself, so that it can be translated. Custom validators which produce
+/// their own text should use [DetailedValidation](ValidationErrorKind::DetailedValidation).
+#[derive(Debug, PartialEq, Eq)]
 pub enum ValidationErrorKind<'a> {
-    #[error("wrong type, expected {expected} got {actual}")]
     WrongType {
         expected: &'static str,
         actual: &'a str,
     },
-    #[error("special requirements for field not met: {error}")]
-    ValidationError { error: &'a str },
-    #[error("field '{field}' missing")]
-    FieldMissing { field: &'a str },
-    #[error("field '{field}' is not specified in the schema")]
-    ExtraField { field: &'a str },
-    #[error("unknown type specified: {unknown_type}")]
-    UnknownType { unknown_type: &'a str },
-    #[error("multiple errors were encountered: {errors:?}")]
-    Multiple { errors: Vec<ValidationError<'a>> },
-    #[error("schema '{uri}' references was not found")]
-    UnknownSchema { uri: &'a str },
+    BadValue {
+        expected: &'static str,
+    },
+    ValidationError {
+        error: MessageId,
+    },
+    /// A fixed message with placeholders, filled in with the named arguments.
+    ParameterizedValidation {
+        error: MessageId,
+        args: Vec<(&'static str, String)>,
+    },
+    DetailedValidation {
+        message: String,
+    },
+    FieldMissing {
+        field: &'a str,
+    },
+    ExtraField {
+        field: &'a str,
+    },
+    UnknownType {
+        unknown_type: &'a str,
+    },
+    Multiple {
+        errors: Vec<ValidationError<'a>>,
+    },
+    UnknownSchema {
+        uri: &'a str,
+    },
+    ContextRequired {
+        uri: &'a str,
+    },
+    NoDocuments,
 }
 
 impl<'a> ValidationErrorKind<'a> {
@@ -46,6 +71,81 @@ impl<'a> ValidationErrorKind<'a> {
     }
 }
 
+impl<'a> ValidationErrorKind<'a> {
+    /// The message describing the error, taken from the catalog.
+    fn message(&self, catalog: &dyn MessageCatalog) -> String {
+        let requirement = |message| {
+            localize(
+                catalog,
+                MessageId::RequirementNotMet,
+                &[("message", message)],
+            )
+        };
+
+        match self {
+            ValidationErrorKind::WrongType { expected, actual } => localize(
+                catalog,
+                MessageId::WrongType,
+                &[
+                    ("expected", expected.to_string()),
+                    ("actual", actual.to_string()),
+                ],
+            ),
+            ValidationErrorKind::BadValue { expected } => localize(
+                catalog,
+                MessageId::BadValue,
+                &[("expected", expected.to_string())],
+            ),
+            ValidationErrorKind::ValidationError { error } => {
+                requirement(localize(catalog, *error, &[]))
+            }
+            ValidationErrorKind::ParameterizedValidation { error, args } => {
+                requirement(localize(catalog, *error, args))
+            }
+            ValidationErrorKind::DetailedValidation { message } => requirement(message.clone()),
+            ValidationErrorKind::FieldMissing { field } => localize(
+                catalog,
+                MessageId::FieldMissing,
+                &[("field", field.to_string())],
+            ),
+            ValidationErrorKind::ExtraField { field } => localize(
+                catalog,
+                MessageId::ExtraField,
+                &[("field", field.to_string())],
+            ),
+            ValidationErrorKind::UnknownType { unknown_type } => localize(
+                catalog,
+                MessageId::UnknownType,
+                &[("unknown_type", unknown_type.to_string())],
+            ),
+            ValidationErrorKind::Multiple { errors } => localize(
+                catalog,
+                MessageId::MultipleErrors,
+                &[("errors", format!("{:?}", errors))],
+            ),
+            ValidationErrorKind::UnknownSchema { uri } => localize(
+                catalog,
+                MessageId::UnknownSchema,
+                &[("uri", uri.to_string())],
+            ),
+            ValidationErrorKind::ContextRequired { uri } => localize(
+                catalog,
+                MessageId::ContextRequired,
+                &[("uri", uri.to_string())],
+            ),
+            ValidationErrorKind::NoDocuments => localize(catalog, MessageId::NoDocuments, &[]),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for ValidationErrorKind<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str(&self.message(&English))
+    }
+}
+
+impl<'a> std::error::Error for ValidationErrorKind<'a> {}
+
 impl<'a> From<ValidationErrorKind<'a>> for ValidationError<'a> {
     fn from(kind: ValidationErrorKind<'a>) -> ValidationError<'a> {
         ValidationError {
@@ -67,6 +167,7 @@ impl<'a> From<GenericError<'a>> for ValidationErrorKind<'a> {
             GenericError::WrongType { expected, actual } => {
                 ValidationErrorKind::WrongType { expected, actual }
             }
+            GenericError::BadValue { expected } => ValidationErrorKind::BadValue { expected },
             GenericError::FieldMissing { field } => ValidationErrorKind::FieldMissing { field },
             GenericError::ExtraField { field } => ValidationErrorKind::ExtraField { field },
             GenericError::Multiple { errors } => ValidationErrorKind::Multiple {
@@ -93,19 +194,75 @@ pub struct ValidationError<'a> {
 }
 
 impl<'a> ValidationError<'a> {
-    fn flatten(&self, fmt: &mut std::fmt::Formatter<'_>, root: String) -> std::fmt::Result {
+    fn flatten(
+        &self,
+        fmt: &mut std::fmt::Formatter<'_>,
+        root: String,
+        catalog: &dyn MessageCatalog,
+    ) -> std::fmt::Result {
         match &self.kind {
             ValidationErrorKind::Multiple { errors } => {
                 for err in errors {
-                    err.flatten(fmt, format!("{}{}", root, self.state))?;
+                    err.flatten(fmt, format!("{}{}", root, self.state), catalog)?;
                 }
             }
-            err => writeln!(fmt, "{}{}: {}", root, self.state, err)?,
+            err => writeln!(fmt, "{}{}: {}", root, self.state, err.message(catalog))?,
         }
 
         Ok(())
     }
 
+    /// Display the error using the messages of the given catalog, rather than in english.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_validator::{MessageCatalog, MessageId, OwnedSchema};
+    /// # use yaml_validator::yaml_rust::YamlLoader;
+    /// #
+    /// struct Danish;
+    ///
+    /// impl MessageCatalog for Danish {
+    ///     fn message(&self, id: MessageId) -> Option<&str> {
+    ///         match id {
+    ///             MessageId::RequirementNotMet => Some("særlige krav til feltet er ikke opfyldt: {message}"),
+    ///             MessageId::MinLength => Some("strengen er kortere end minLength"),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let schema = OwnedSchema::from_str("type: string\nminLength: 10").unwrap();
+    /// let document = YamlLoader::load_from_str("hello").unwrap().remove(0);
+    ///
+    /// assert_eq!(
+    ///     schema.validate(&document).unwrap_err().localized(&Danish).to_string(),
+    ///     "#: særlige krav til feltet er ikke opfyldt: strengen er kortere end minLength\n"
+    /// );
+    /// ```
+    pub fn localized<'e>(&'e self, catalog: &'e dyn MessageCatalog) -> Localized<'e, 'a> {
+        Localized {
+            error: self,
+            catalog,
+        }
+    }
+
+    /// Number of individual errors, counting each error nested within a
+    /// [Multiple](ValidationErrorKind::Multiple) error separately.
+    pub fn leaf_count(&self) -> usize {
+        match &self.kind {
+            ValidationErrorKind::Multiple { errors } => {
+                errors.iter().map(ValidationError::leaf_count).sum()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Convert the error into one which no longer borrows from the schema or the document.
+    pub fn into_owned(self) -> OwnedValidationError {
+        self.into()
+    }
+
     pub fn add_path_name(path: &'a str) -> impl Fn(ValidationError<'a>) -> ValidationError<'a> {
         move |mut err: ValidationError<'a>| -> ValidationError<'a> {
             err.state.push(BreadcrumbSegment::Name(path));
@@ -122,7 +279,332 @@ impl<'a> ValidationError<'a> {
 }
 
 impl<'a> std::fmt::Display for ValidationError<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.flatten(fmt, "#".to_string(), &English)
+    }
+}
+
+/// A [ValidationError] displayed using a [MessageCatalog], as returned by [ValidationError::localized].
+pub struct Localized<'e, 'a> {
+    error: &'e ValidationError<'a>,
+    catalog: &'e dyn MessageCatalog,
+}
+
+impl<'e, 'a> std::fmt::Display for Localized<'e, 'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.flatten(fmt, "#".to_string(), self.catalog)
+    }
+}
+
+/// A [ValidationErrorKind] which owns all of its contents, as part of an [OwnedValidationError].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnedValidationErrorKind {
+    WrongType {
+        expected: &'static str,
+        actual: String,
+    },
+    BadValue {
+        expected: &'static str,
+    },
+    ValidationError {
+        error: MessageId,
+    },
+    ParameterizedValidation {
+        error: MessageId,
+        args: Vec<(&'static str, String)>,
+    },
+    DetailedValidation {
+        message: String,
+    },
+    FieldMissing {
+        field: String,
+    },
+    ExtraField {
+        field: String,
+    },
+    UnknownType {
+        unknown_type: String,
+    },
+    Multiple {
+        errors: Vec<OwnedValidationError>,
+    },
+    UnknownSchema {
+        uri: String,
+    },
+    ContextRequired {
+        uri: String,
+    },
+    NoDocuments,
+}
+
+impl OwnedValidationErrorKind {
+    /// The message describing the error, taken from the catalog.
+    fn message(&self, catalog: &dyn MessageCatalog) -> String {
+        let requirement = |message| {
+            localize(
+                catalog,
+                MessageId::RequirementNotMet,
+                &[("message", message)],
+            )
+        };
+
+        match self {
+            OwnedValidationErrorKind::WrongType { expected, actual } => localize(
+                catalog,
+                MessageId::WrongType,
+                &[
+                    ("expected", expected.to_string()),
+                    ("actual", actual.clone()),
+                ],
+            ),
+            OwnedValidationErrorKind::BadValue { expected } => localize(
+                catalog,
+                MessageId::BadValue,
+                &[("expected", expected.to_string())],
+            ),
+            OwnedValidationErrorKind::ValidationError { error } => {
+                requirement(localize(catalog, *error, &[]))
+            }
+            OwnedValidationErrorKind::ParameterizedValidation { error, args } => {
+                requirement(localize(catalog, *error, args))
+            }
+            OwnedValidationErrorKind::DetailedValidation { message } => {
+                requirement(message.clone())
+            }
+            OwnedValidationErrorKind::FieldMissing { field } => localize(
+                catalog,
+                MessageId::FieldMissing,
+                &[("field", field.clone())],
+            ),
+            OwnedValidationErrorKind::ExtraField { field } => {
+                localize(catalog, MessageId::ExtraField, &[("field", field.clone())])
+            }
+            OwnedValidationErrorKind::UnknownType { unknown_type } => localize(
+                catalog,
+                MessageId::UnknownType,
+                &[("unknown_type", unknown_type.clone())],
+            ),
+            OwnedValidationErrorKind::Multiple { errors } => localize(
+                catalog,
+                MessageId::MultipleErrors,
+                &[("errors", format!("{:?}", errors))],
+            ),
+            OwnedValidationErrorKind::UnknownSchema { uri } => {
+                localize(catalog, MessageId::UnknownSchema, &[("uri", uri.clone())])
+            }
+            OwnedValidationErrorKind::ContextRequired { uri } => {
+                localize(catalog, MessageId::ContextRequired, &[("uri", uri.clone())])
+            }
+            OwnedValidationErrorKind::NoDocuments => localize(catalog, MessageId::NoDocuments, &[]),
+        }
+    }
+}
+
+impl std::fmt::Display for OwnedValidationErrorKind {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str(&self.message(&English))
+    }
+}
+
+impl std::error::Error for OwnedValidationErrorKind {}
+
+/// A [BreadcrumbSegment] which owns its name, as part of an [OwnedValidationError].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnedSegment {
+    Name(String),
+    Index(usize),
+}
+
+/// A [ValidationError] which owns all of its contents, so that it can be stored or sent
+/// to another thread once the schema and the document it came from are gone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedValidationError {
+    pub kind: OwnedValidationErrorKind,
+    /// Segments from the innermost to the outermost level of the document.
+    pub path: Vec<OwnedSegment>,
+}
+
+impl OwnedValidationError {
+    fn flatten(&self, fmt: &mut std::fmt::Formatter<'_>, mut root: String) -> std::fmt::Result {
+        for segment in self.path.iter().rev() {
+            match segment {
+                OwnedSegment::Name(name) => root = format!("{}.{}", root, name),
+                OwnedSegment::Index(index) => root = format!("{}[{}]", root, index),
+            }
+        }
+
+        match &self.kind {
+            OwnedValidationErrorKind::Multiple { errors } => {
+                for err in errors {
+                    err.flatten(fmt, root.clone())?;
+                }
+            }
+            err => writeln!(fmt, "{}: {}", root, err)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for OwnedValidationError {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.flatten(fmt, "#".to_string())
     }
 }
+
+impl std::error::Error for OwnedValidationError {}
+
+impl<'a> From<ValidationError<'a>> for OwnedValidationError {
+    fn from(err: ValidationError<'a>) -> Self {
+        let kind = match err.kind {
+            ValidationErrorKind::WrongType { expected, actual } => {
+                OwnedValidationErrorKind::WrongType {
+                    expected,
+                    actual: actual.to_string(),
+                }
+            }
+            ValidationErrorKind::BadValue { expected } => {
+                OwnedValidationErrorKind::BadValue { expected }
+            }
+            ValidationErrorKind::ValidationError { error } => {
+                OwnedValidationErrorKind::ValidationError { error }
+            }
+            ValidationErrorKind::ParameterizedValidation { error, args } => {
+                OwnedValidationErrorKind::ParameterizedValidation { error, args }
+            }
+            ValidationErrorKind::DetailedValidation { message } => {
+                OwnedValidationErrorKind::DetailedValidation { message }
+            }
+            ValidationErrorKind::FieldMissing { field } => OwnedValidationErrorKind::FieldMissing {
+                field: field.to_string(),
+            },
+            ValidationErrorKind::ExtraField { field } => OwnedValidationErrorKind::ExtraField {
+                field: field.to_string(),
+            },
+            ValidationErrorKind::UnknownType { unknown_type } => {
+                OwnedValidationErrorKind::UnknownType {
+                    unknown_type: unknown_type.to_string(),
+                }
+            }
+            ValidationErrorKind::Multiple { errors } => OwnedValidationErrorKind::Multiple {
+                errors: errors.into_iter().map(OwnedValidationError::from).collect(),
+            },
+            ValidationErrorKind::UnknownSchema { uri } => OwnedValidationErrorKind::UnknownSchema {
+                uri: uri.to_string(),
+            },
+            ValidationErrorKind::ContextRequired { uri } => {
+                OwnedValidationErrorKind::ContextRequired {
+                    uri: uri.to_string(),
+                }
+            }
+            ValidationErrorKind::NoDocuments => OwnedValidationErrorKind::NoDocuments,
+        };
+
+        let path = err
+            .state
+            .segments()
+            .iter()
+            .map(|segment| match segment {
+                BreadcrumbSegment::Name(name) => OwnedSegment::Name(name.to_string()),
+                BreadcrumbSegment::Index(index) => OwnedSegment::Index(*index),
+            })
+            .collect();
+
+        OwnedValidationError { kind, path }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detailed_validation_path() {
+        let err: ValidationError = ValidationErrorKind::Multiple {
+            errors: vec![
+                ValidationErrorKind::DetailedValidation {
+                    message: format!("value {} is out of range", 42),
+                }
+                .with_path_name("age"),
+                ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::LowerLimit,
+                    args: vec![("value", "120".into()), ("limit", "150".into())],
+                }
+                .with_path_name("height"),
+            ],
+        }
+        .with_path_index(3);
+
+        let err = ValidationError::add_path_name("people")(err);
+
+        assert_eq!(
+            format!("{}", err),
+            "#.people[3].age: special requirements for field not met: value 42 is out of range
+#.people[3].height: special requirements for field not met: value 120 is below minimum 150 (inclusive)
+"
+        );
+    }
+
+    struct Stub;
+
+    impl MessageCatalog for Stub {
+        fn message(&self, id: MessageId) -> Option<&str> {
+            match id {
+                MessageId::MinLength => Some("[min-length]"),
+                MessageId::UpperLimit => Some("[{value} > {limit}]"),
+                MessageId::FieldMissing => Some("[missing {field}]"),
+                MessageId::WrongType => Some("[{actual} is not {expected}]"),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_localized_messages() {
+        let err: ValidationError = ValidationErrorKind::Multiple {
+            errors: vec![
+                ValidationErrorKind::ValidationError {
+                    error: MessageId::MinLength,
+                }
+                .with_path_name("name"),
+                ValidationErrorKind::ValidationError {
+                    error: MessageId::MaxItems,
+                }
+                .with_path_name("tags"),
+                ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::UpperLimit,
+                    args: vec![("value", "130".into()), ("limit", "120".into())],
+                }
+                .with_path_name("age"),
+                ValidationErrorKind::FieldMissing { field: "email" }.into(),
+                ValidationErrorKind::WrongType {
+                    expected: "integer",
+                    actual: "string",
+                }
+                .with_path_name("phone"),
+            ],
+        }
+        .into();
+
+        assert_eq!(
+            err.localized(&Stub).to_string(),
+            "#.name: special requirements for field not met: [min-length]
+#.tags: special requirements for field not met: array contains more than maxItems items
+#.age: special requirements for field not met: [130 > 120]
+#: [missing email]
+#.phone: [string is not integer]
+"
+        );
+
+        // The default display is unaffected by the catalog.
+        assert_eq!(
+            err.to_string(),
+            "#.name: special requirements for field not met: string length is less than minLength
+#.tags: special requirements for field not met: array contains more than maxItems items
+#.age: special requirements for field not met: value 130 is above maximum 120 (inclusive)
+#: field 'email' missing
+#.phone: wrong type, expected integer got string
+"
+        );
+    }
+}
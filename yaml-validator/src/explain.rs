@@ -0,0 +1,304 @@
+use crate::breadcrumb::BreadcrumbSegment;
+use crate::errors::{ValidationError, ValidationErrorKind};
+use crate::{Context, PropertyType, Schema};
+
+/// A single validation error, along with the location in the schema of the rule which caused it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Explanation<'schema> {
+    /// Location of the offending value within the document, such as `#[2].age`
+    pub document_path: String,
+    /// Description of the error itself
+    pub message: String,
+    /// Names leading from the uri of the schema to the node containing the rule
+    pub schema_path: Vec<&'schema str>,
+    /// The keyword within the schema node responsible for the error, if known
+    pub keyword: Option<&'static str>,
+}
+
+impl<'schema> std::fmt::Display for Explanation<'schema> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} (schema: {}",
+            self.document_path,
+            self.message,
+            self.schema_path.join(" -> ")
+        )?;
+
+        if let Some(keyword) = self.keyword {
+            write!(f, ": {}", keyword)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+type Leaf<'e, 'a> = (
+    String,
+    Vec<&'e BreadcrumbSegment<'a>>,
+    &'e ValidationErrorKind<'a>,
+);
+
+/// Flattens nested errors, keeping track of the full document path leading to each of them.
+fn leaves<'e, 'a>(
+    error: &'e ValidationError<'a>,
+    root: String,
+    mut segments: Vec<&'e BreadcrumbSegment<'a>>,
+    output: &mut Vec<Leaf<'e, 'a>>,
+) {
+    segments.extend(error.state.segments().iter().rev());
+    let root = format!("{}{}", root, error.state);
+
+    match &error.kind {
+        ValidationErrorKind::Multiple { errors } => {
+            for err in errors {
+                leaves(err, root.clone(), segments.clone(), output);
+            }
+        }
+        kind => output.push((root, segments, kind)),
+    }
+}
+
+/// Follows the document path through the schema for as long as the schema describes it.
+///
+/// References followed since the last step along the document path are remembered by the
+/// address of the node they resolved to, so that references which loop back to themselves
+/// stop at the last node reached, rather than being followed forever.
+fn locate<'n, 'schema: 'n>(
+    ctx: &'schema Context<'schema>,
+    mut node: &'n PropertyType<'schema>,
+    document_path: &[&BreadcrumbSegment],
+    schema_path: &mut Vec<&'schema str>,
+) -> &'n PropertyType<'schema> {
+    let mut segments = document_path.iter().peekable();
+    let mut following: Vec<usize> = Vec::new();
+
+    loop {
+        node = match (node, segments.peek()) {
+            (PropertyType::Nullable(inner), _) => inner,
+            (
+                PropertyType::Composite {
+                    base: Some(base), ..
+                },
+                Some(_),
+            ) => base,
+            (PropertyType::Reference(reference), _) => match reference.resolve_node(ctx) {
+                Ok(referenced)
+                    if !following.contains(&(referenced as *const PropertyType as usize)) =>
+                {
+                    following.push(referenced as *const PropertyType as usize);
+                    schema_path.push(reference.uri);
+                    referenced
+                }
+                _ => return node,
+            },
+            (PropertyType::Object(object), Some(BreadcrumbSegment::Name(name))) => {
+                match object.items.get_key_value(*name) {
+                    Some((name, item)) => {
+                        schema_path.push(name);
+                        segments.next();
+                        following.clear();
+                        item
+                    }
+                    None => return node,
+                }
+            }
+            (PropertyType::Array(array), Some(BreadcrumbSegment::Index(i)))
+                if *i < array.prefix_items.len() =>
+            {
+                return node
+            }
+            (PropertyType::Array(array), Some(BreadcrumbSegment::Index(_))) => match &array.items {
+                Some(items) => {
+                    schema_path.push("items");
+                    segments.next();
+                    following.clear();
+                    items
+                }
+                None => return node,
+            },
+            (PropertyType::Hash(hash), Some(BreadcrumbSegment::Name(_))) => match &hash.items {
+                Some(items) => {
+                    schema_path.push("items");
+                    segments.next();
+                    following.clear();
+                    items
+                }
+                None => return node,
+            },
+            _ => return node,
+        };
+    }
+}
+
+fn keyword(node: &PropertyType, kind: &ValidationErrorKind) -> Option<&'static str> {
+    match (node, kind) {
+        (PropertyType::Not(_), _) => Some("not"),
+        (PropertyType::OneOf(_), _) => Some("oneOf"),
+        (PropertyType::AllOf(_), _) => Some("allOf"),
+        (PropertyType::AnyOf(_), _) => Some("anyOf"),
+        (PropertyType::Const(_), _) => Some("const"),
+        (PropertyType::Tag(_), _) => Some("tag"),
+        (_, ValidationErrorKind::WrongType { .. }) => Some("type"),
+        (_, ValidationErrorKind::BadValue { .. }) => Some("type"),
+        (_, ValidationErrorKind::UnknownType { .. }) => Some("type"),
+        (_, ValidationErrorKind::FieldMissing { .. }) => Some("required"),
+        (_, ValidationErrorKind::ExtraField { .. }) => Some("items"),
+        (_, ValidationErrorKind::UnknownSchema { .. }) => Some("$ref"),
+        (_, ValidationErrorKind::ContextRequired { .. }) => Some("$ref"),
+        _ => None,
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Relate each of the errors produced by validating against this schema back to the
+    /// schema rule responsible for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Validate, Context};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             age:
+    ///                 type: integer
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let document = YamlLoader::load_from_str("age: old").unwrap().remove(0);
+    ///
+    /// let schema = context.get_schema("person").unwrap();
+    /// let error = schema.validate(&context, &document).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     schema.explain(&context, &error)[0].to_string(),
+    ///     "#.age: wrong type, expected integer got string (schema: person -> age: type)"
+    /// );
+    /// ```
+    pub fn explain(
+        &self,
+        ctx: &'schema Context<'schema>,
+        error: &ValidationError,
+    ) -> Vec<Explanation<'schema>> {
+        let mut errors = Vec::new();
+        leaves(error, "#".to_string(), Vec::new(), &mut errors);
+
+        errors
+            .into_iter()
+            .map(|(document_path, segments, kind)| {
+                let mut schema_path = vec![self.uri];
+                let node = locate(ctx, &self.schema, &segments, &mut schema_path);
+
+                Explanation {
+                    document_path,
+                    message: kind.to_string(),
+                    schema_path,
+                    keyword: keyword(node, kind),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::load_simple;
+    use crate::{Context, Validate};
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn explain_nested_errors() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+---
+uri: phonebook
+schema:
+  type: object
+  items:
+    entries:
+      type: array
+      items:
+        $ref: person
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = ctx.get_schema("phonebook").unwrap();
+
+        let document = load_simple(
+            r#"
+            entries:
+              - name: John
+                age: 52
+              - name: 200
+                age: Jimmy
+            "#,
+        );
+
+        let error = schema.validate(&ctx, &document).unwrap_err();
+
+        let explanations: Vec<String> = schema
+            .explain(&ctx, &error)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            explanations,
+            vec![
+                "#.entries[1].name: wrong type, expected string got integer (schema: phonebook -> entries -> items -> person -> name: type)",
+                "#.entries[1].age: wrong type, expected integer got string (schema: phonebook -> entries -> items -> person -> age: type)",
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_self_reference() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+---
+uri: a
+schema:
+  $ref: b
+---
+uri: b
+schema:
+  $ref: a
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = ctx.get_schema("a").unwrap();
+        let document = load_simple("10");
+        let error = schema.validate(&ctx, &document).unwrap_err();
+
+        let explanations: Vec<String> = schema
+            .explain(&ctx, &error)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            explanations,
+            vec!["#: special requirements for field not met: reference to 'b' loops back to itself without descending into the value (schema: a -> b -> a)"]
+        );
+    }
+}
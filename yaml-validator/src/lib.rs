@@ -1,37 +1,232 @@
 #![forbid(unsafe_code)]
+// SchemaError and ValidationError carry their breadcrumbs inline, which makes
+// them larger than clippy would like for an Err variant.
+#![allow(clippy::result_large_err)]
 
-use std::collections::BTreeMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
 pub use yaml_rust;
 use yaml_rust::Yaml;
 
 mod breadcrumb;
+mod classify;
+mod compat;
+mod coverage;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod diff;
+mod dump;
 mod errors;
+mod explain;
+mod lint;
+mod load;
+mod meta;
 mod modifiers;
+mod normalize;
+mod owned;
+#[cfg(feature = "serde_yaml")]
+mod serde_value;
+mod tags;
 mod types;
 mod utils;
+mod visit;
 use modifiers::*;
+use types::reference::{anchor_local_references, find_node};
 use types::*;
 
+pub use classify::ClassifyError;
+pub use compat::{is_backward_compatible, Incompatibility, IncompatibilityKind};
+pub use coverage::{Coverage, CoverageItem};
+pub use diff::{SchemaChange, SchemaChangeKind};
+pub use errors::messages::{English, MessageCatalog, MessageId};
 pub use errors::schema::{SchemaError, SchemaErrorKind};
-use errors::ValidationError;
+pub use errors::validation::{
+    Localized, OwnedSegment, OwnedValidationError, OwnedValidationErrorKind, ValidationError,
+    ValidationErrorKind,
+};
+pub use explain::Explanation;
+pub use lint::LintWarning;
+pub use load::{load_from_reader, LoadError};
+pub use owned::{OwnedSchema, OwnedSchemaError};
+#[cfg(feature = "serde_yaml")]
+pub use serde_value::from_serde_yaml;
+pub use tags::{DocumentTags, TaggedDocuments};
+pub use types::{SchemaInteger, SchemaReal};
+pub use utils::{Limit, UnitValue};
+pub use visit::{ScalarKind, SchemaVisitor};
 
 use crate::types::bool::SchemaBool;
-use utils::{CondenseErrors, OptionalLookup, YamlUtils};
+use utils::{
+    check_vocabulary, CondenseErrors, OptionalLookup, YamlUtils, ANNOTATION_KEYWORDS,
+    PROPERTY_KEYWORDS, TYPE_KEYWORDS, UNIVERSAL_KEYWORDS,
+};
+
+/// Options which change how a single document is validated, without changing the schemas
+/// or the [Context] they are part of. See [Validate::validate_with].
+///
+/// New options may be added in any release, so options are built up from the default:
+/// `ValidationOptions::default().coerce(true)`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationOptions<'a> {
+    /// Accept whole reals such as `10.0` for every integer, as if all integer
+    /// schemas had `coerce` set.
+    pub coerce: bool,
+    /// Tags of the document being validated, for use by the `tag` keyword. Without them,
+    /// any schema using `tag` fails.
+    pub tags: Option<&'a DocumentTags>,
+    /// Records the branches and optional properties exercised by the document.
+    pub coverage: Option<&'a Coverage>,
+    /// Check the items of arrays even when their length, uniqueness or order is already
+    /// wrong, reporting the errors within them alongside, rather than only the first.
+    pub report_all_array_errors: bool,
+    // Pairs of the node each reference currently being followed resolved to, and the value
+    // it is validating, by address, so that references which loop back to themselves fail.
+    pub(crate) following: RefCell<Vec<(usize, usize)>>,
+    // The tags above by the address of the node they belong to, found when validation starts.
+    pub(crate) tagged: RefCell<HashMap<usize, &'a str>>,
+}
+
+impl<'a> ValidationOptions<'a> {
+    /// Accept whole reals such as `10.0` for every integer schema.
+    pub fn coerce(mut self, coerce: bool) -> Self {
+        self.coerce = coerce;
+        self
+    }
+
+    /// Tags of the document being validated, for use by the `tag` keyword. Tags are found
+    /// by their path within the document, so the document must be validated from its root.
+    pub fn tags(mut self, tags: &'a DocumentTags) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Record the branches and optional properties exercised by the document.
+    pub fn coverage(mut self, coverage: &'a Coverage) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    /// Check the items of arrays even when their length, uniqueness or order is already wrong.
+    pub fn report_all_array_errors(mut self, report_all_array_errors: bool) -> Self {
+        self.report_all_array_errors = report_all_array_errors;
+        self
+    }
+}
 
 /// Validation trait implemented by all types, as well as the [Schema](crate::Schema) type
+///
+/// Implementors must provide at least one of [validate](Validate::validate) or
+/// [validate_with](Validate::validate_with), since each defaults to calling the other.
 pub trait Validate<'yaml, 'schema: 'yaml> {
+    /// Validate using the default [ValidationOptions].
     fn validate(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
-    ) -> Result<(), ValidationError<'yaml>>;
+    ) -> Result<(), ValidationError<'yaml>> {
+        self.validate_with(ctx, yaml, &ValidationOptions::default())
+    }
+
+    /// Validate using the given options, which apply to this call only.
+    /// Implementations which have no use for options may leave this out,
+    /// and it falls back to [validate](Validate::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Context, Validate, ValidationOptions};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: port
+    ///     schema:
+    ///         type: integer
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let schema = context.get_schema("port").unwrap();
+    /// let document = YamlLoader::load_from_str("8080.0").unwrap().remove(0);
+    ///
+    /// assert!(schema.validate(&context, &document).is_err());
+    ///
+    /// let opts = ValidationOptions::default().coerce(true);
+    /// schema.validate_with(&context, &document, &opts).unwrap();
+    /// ```
+    fn validate_with(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        _opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        self.validate(ctx, yaml)
+    }
+
+    /// Whether the document validates, for when only the outcome matters and not the errors.
+    /// Compound schemas stop checking at the first failure, rather than collecting every error.
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        self.validate_with(ctx, yaml, opts).is_ok()
+    }
 }
 
+/// Validator for a custom schema `type`, see [Context::register_type].
+type CustomValidator =
+    Box<dyn for<'a> Fn(&'a Yaml) -> Result<(), ValidationError<'a>> + Send + Sync>;
+
+/// Validator for a custom string `format`, see [Context::register_format].
+type FormatValidator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+static NEXT_CONTEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// Contains a number of schemas that may or may not be dependent on each other.
-#[derive(Debug, Default)]
 pub struct Context<'schema> {
-    schemas: BTreeMap<&'schema str, Schema<'schema>>,
+    // Schemas are only ever appended or replaced in place, so that references can
+    // remember the position of the schema they resolved to within a given context.
+    schemas: Vec<Schema<'schema>>,
+    uris: BTreeMap<&'schema str, usize>,
+    id: usize,
+    custom_types: BTreeMap<String, CustomValidator>,
+    custom_formats: BTreeMap<String, FormatValidator>,
+    // Whether objects accept properties not listed in their items, unless
+    // they set unevaluatedProperties themselves.
+    permissive_objects: bool,
+    // Whether keywords belonging to another built-in type are reported as such,
+    // rather than as fields unknown to the declared type.
+    strict_vocabulary: bool,
+}
+
+impl<'schema> Default for Context<'schema> {
+    fn default() -> Self {
+        Context {
+            schemas: Vec::new(),
+            uris: BTreeMap::new(),
+            id: NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed),
+            custom_types: BTreeMap::new(),
+            custom_formats: BTreeMap::new(),
+            permissive_objects: false,
+            strict_vocabulary: false,
+        }
+    }
+}
+
+impl<'schema> std::fmt::Debug for Context<'schema> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Context")
+            .field("schemas", &self.schemas)
+            .field("custom_types", &self.custom_types.keys())
+            .field("custom_formats", &self.custom_formats.keys())
+            .field("permissive_objects", &self.permissive_objects)
+            .field("strict_vocabulary", &self.strict_vocabulary)
+            .finish()
+    }
 }
 
 impl<'schema> Context<'schema> {
@@ -59,7 +254,515 @@ impl<'schema> Context<'schema> {
     ///     .validate(&context, &document).unwrap();
     /// ```
     pub fn get_schema(&self, uri: &str) -> Option<&Schema<'schema>> {
-        self.schemas.get(uri)
+        self.uris.get(uri).map(|index| &self.schemas[*index])
+    }
+
+    /// Like [get_schema](Context::get_schema), but the uri may be followed by a pointer to a node
+    /// within the schema, such as `person#/items/address`, in the same way as a `$ref`.
+    ///
+    /// The schema returned refers to the node, rather than holding a copy of it, and so
+    /// validates using this context only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Validate, Context};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             address:
+    ///                 type: object
+    ///                 items:
+    ///                     city:
+    ///                         type: string
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let document = YamlLoader::load_from_str("city: Copenhagen").unwrap().remove(0);
+    ///
+    /// context.get_schema_at("person#/items/address").unwrap()
+    ///     .validate(&context, &document).unwrap();
+    ///
+    /// assert!(context.get_schema_at("person#/items/phone").is_none());
+    /// ```
+    pub fn get_schema_at(&'schema self, uri: &'schema str) -> Option<Schema<'schema>> {
+        let reference = SchemaReference::new(uri);
+        reference.resolve_node(self).ok()?;
+
+        Some(Schema {
+            uri,
+            schema: PropertyType::Reference(reference),
+            default: None,
+            examples: Vec::new(),
+            counter_examples: Vec::new(),
+        })
+    }
+
+    /// Like [get_schema](Context::get_schema), but also returns the position of the schema,
+    /// which can be passed to [schema_at](Context::schema_at) to skip the lookup next time.
+    pub(crate) fn find_schema(&self, uri: &str) -> Option<(usize, &Schema<'schema>)> {
+        self.uris
+            .get(uri)
+            .map(|index| (*index, &self.schemas[*index]))
+    }
+
+    /// Get the schema at a position previously returned by [find_schema](Context::find_schema).
+    pub(crate) fn schema_at(&self, index: usize) -> &Schema<'schema> {
+        &self.schemas[index]
+    }
+
+    /// Uniquely identifies this context, for remembering lookups made within it.
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    fn store_schema(&mut self, schema: Schema<'schema>) {
+        match self.uris.get(schema.uri) {
+            Some(index) => self.schemas[*index] = schema,
+            None => {
+                self.uris.insert(schema.uri, self.schemas.len());
+                self.schemas.push(schema);
+            }
+        }
+    }
+
+    /// Combine the schemas of two contexts into one.
+    ///
+    /// Fails with a [DuplicateSchema](crate::SchemaErrorKind::DuplicateSchema) error for
+    /// every uri defined in both contexts, and a
+    /// [MalformedField](crate::SchemaErrorKind::MalformedField) error for every custom type
+    /// or format registered in both, rather than letting either one replace the other.
+    pub fn merge(mut self, other: Context<'schema>) -> Result<Self, SchemaError<'schema>> {
+        let mut errors: Vec<SchemaError<'schema>> = other
+            .uris
+            .keys()
+            .filter(|uri| self.uris.contains_key(*uri))
+            .map(|uri| SchemaErrorKind::DuplicateSchema { uri }.into())
+            .collect();
+
+        errors.extend(
+            other
+                .custom_types
+                .keys()
+                .filter(|typename| self.custom_types.contains_key(*typename))
+                .map(|typename| {
+                    SchemaErrorKind::MalformedField {
+                        error: format!("custom type '{}' is registered in both contexts", typename),
+                    }
+                    .into()
+                }),
+        );
+
+        errors.extend(
+            other
+                .custom_formats
+                .keys()
+                .filter(|name| self.custom_formats.contains_key(*name))
+                .map(|name| {
+                    SchemaErrorKind::MalformedField {
+                        error: format!("custom format '{}' is registered in both contexts", name),
+                    }
+                    .into()
+                }),
+        );
+
+        if errors.len() == 1 {
+            return Err(errors.pop().unwrap());
+        } else if !errors.is_empty() {
+            return Err(errors.into());
+        }
+
+        let added = self.schemas.len();
+        for schema in other.schemas {
+            self.store_schema(schema);
+        }
+        self.custom_types.extend(other.custom_types);
+        self.custom_formats.extend(other.custom_formats);
+        self.check_examples(added)?;
+        Ok(self)
+    }
+
+    /// Register a validator for a custom `type`, making it available to schemas
+    /// added to the context using [add_schemas](Context::add_schemas) afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::{Validate, Context, ValidationErrorKind};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: version
+    ///     schema:
+    ///         type: semver
+    /// "#).unwrap();
+    ///
+    /// let mut context = Context::default();
+    /// context.register_type("semver", |yaml| {
+    ///     let version = yaml.as_str().ok_or(ValidationErrorKind::WrongType {
+    ///         expected: "string",
+    ///         actual: "something else",
+    ///     })?;
+    ///
+    ///     if version.split('.').count() == 3 {
+    ///         Ok(())
+    ///     } else {
+    ///         Err(ValidationErrorKind::DetailedValidation {
+    ///             message: format!("{} is not a semantic version", version),
+    ///         }
+    ///         .into())
+    ///     }
+    /// });
+    /// context.add_schemas(&schemas).unwrap();
+    ///
+    /// let document = YamlLoader::load_from_str("1.2.3").unwrap().remove(0);
+    /// context.get_schema("version").unwrap()
+    ///     .validate(&context, &document).unwrap();
+    /// ```
+    pub fn register_type<F>(&mut self, typename: &str, validator: F)
+    where
+        F: for<'a> Fn(&'a Yaml) -> Result<(), ValidationError<'a>> + Send + Sync + 'static,
+    {
+        self.custom_types
+            .insert(typename.to_owned(), Box::new(validator));
+    }
+
+    /// Register a validator for a custom string `format`, making it available to schemas
+    /// added to the context using [add_schemas](Context::add_schemas) afterwards. Built in
+    /// formats cannot be replaced. The validator explains why a value is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::{Validate, Context};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: version
+    ///     schema:
+    ///         type: string
+    ///         format: semver
+    /// "#).unwrap();
+    ///
+    /// let mut context = Context::default();
+    /// context.register_format("semver", |value| {
+    ///     if value.split('.').count() == 3 {
+    ///         Ok(())
+    ///     } else {
+    ///         Err("expected three components".into())
+    ///     }
+    /// });
+    /// context.add_schemas(&schemas).unwrap();
+    ///
+    /// let document = YamlLoader::load_from_str("1.2.3").unwrap().remove(0);
+    /// context.get_schema("version").unwrap()
+    ///     .validate(&context, &document).unwrap();
+    /// ```
+    pub fn register_format<F>(&mut self, name: &str, validator: F)
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.custom_formats
+            .insert(name.to_owned(), Box::new(validator));
+    }
+
+    /// Choose whether objects accept properties which are not listed in their `items` by default,
+    /// so that documents gaining new fields keep validating against older schemas. Objects can
+    /// still opt into either behaviour using `unevaluatedProperties`. Objects are strict by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Context, Validate};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             name:
+    ///                 type: string
+    /// "#).unwrap();
+    ///
+    /// let document = YamlLoader::load_from_str("name: John\nage: 52").unwrap().remove(0);
+    ///
+    /// let strict = Context::try_from(&schemas[..]).unwrap();
+    /// assert!(strict.validate("person", &document).is_err());
+    ///
+    /// let mut permissive = Context::try_from(&schemas[..]).unwrap();
+    /// permissive.set_permissive_objects(true);
+    /// permissive.validate("person", &document).unwrap();
+    /// ```
+    pub fn set_permissive_objects(&mut self, permissive: bool) {
+        self.permissive_objects = permissive;
+    }
+
+    /// Choose whether keywords which only apply to a different built-in type than the one
+    /// declared, such as `minItems` on a string, are reported along with the types they do
+    /// apply to. Only affects schemas added to the context afterwards, and is off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: name
+    ///     schema:
+    ///         type: string
+    ///         minItems: 1
+    /// "#).unwrap();
+    ///
+    /// let mut context = Context::default();
+    /// context.set_strict_vocabulary(true);
+    ///
+    /// let err = context.add_schemas(&schemas).unwrap_err();
+    /// assert!(err.to_string().contains("minItems only applies to array schemas, not string"));
+    /// ```
+    pub fn set_strict_vocabulary(&mut self, strict: bool) {
+        self.strict_vocabulary = strict;
+    }
+
+    /// Parse the given schema documents and add them to the context.
+    ///
+    /// Unlike [TryFrom], the schemas may make use of any custom types registered
+    /// with the context. Fails with a [DuplicateSchema](crate::SchemaErrorKind::DuplicateSchema)
+    /// error if a uri is already defined in the context.
+    pub fn add_schemas(&mut self, documents: &'schema [Yaml]) -> Result<(), SchemaError<'schema>> {
+        let schemas = SchemaError::condense_errors(
+            &mut documents
+                .iter()
+                .map(|document| Schema::parse(document, self)),
+        )?;
+
+        self.add_parsed(schemas)
+    }
+
+    /// Build a context from a single bundle document, holding a `schemas` map from the uri
+    /// of each schema to its root node, rather than one document per schema.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let bundle = YamlLoader::load_from_str(r#"
+    ///     schemas:
+    ///         port:
+    ///             type: integer
+    ///             maximum: 65535
+    ///         host:
+    ///             type: string
+    /// "#).unwrap().remove(0);
+    ///
+    /// let context = Context::from_bundle(&bundle).unwrap();
+    /// let document = YamlLoader::load_from_str("8080").unwrap().remove(0);
+    ///
+    /// context.validate("port", &document).unwrap();
+    /// ```
+    pub fn from_bundle(bundle: &'schema Yaml) -> Result<Self, SchemaError<'schema>> {
+        let mut context = Context::default();
+        context.add_bundle(bundle)?;
+        Ok(context)
+    }
+
+    /// Parse the schemas of a bundle document, as described by [from_bundle](Context::from_bundle),
+    /// and add them to the context. Like [add_schemas](Context::add_schemas), the schemas may make
+    /// use of custom types, and it fails with a [DuplicateSchema](crate::SchemaErrorKind::DuplicateSchema)
+    /// error if a uri is already defined in the context.
+    ///
+    /// Repeated uris within the map itself cannot be detected, since the yaml parser keeps only
+    /// the last of them.
+    pub fn add_bundle(&mut self, bundle: &'schema Yaml) -> Result<(), SchemaError<'schema>> {
+        bundle.strict_contents(&["schemas"], &[])?;
+
+        let schemas = bundle
+            .lookup("schemas", "hash", Yaml::as_hash)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("schemas"))?;
+
+        let schemas = SchemaError::condense_errors(&mut schemas.iter().map(|(uri, node)| {
+            let uri = uri
+                .as_type("string", Yaml::as_str)
+                .map_err(SchemaError::from)
+                .map_err(SchemaError::add_path_name("schemas"))?;
+
+            Schema::parse_node(uri, node, self)
+        }))?;
+
+        self.add_parsed(schemas)
+    }
+
+    /// Add newly parsed schemas, as long as none of their uris are already defined.
+    fn add_parsed(&mut self, schemas: Vec<Schema<'schema>>) -> Result<(), SchemaError<'schema>> {
+        let mut errors: Vec<SchemaError<'schema>> = schemas
+            .iter()
+            .filter(|schema| self.uris.contains_key(schema.uri))
+            .map(|schema| SchemaErrorKind::DuplicateSchema { uri: schema.uri }.into())
+            .collect();
+
+        if errors.len() == 1 {
+            return Err(errors.pop().unwrap());
+        } else if !errors.is_empty() {
+            return Err(errors.into());
+        }
+
+        let added = self.schemas.len();
+        for schema in schemas {
+            self.store_schema(schema);
+        }
+        self.check_examples(added)
+    }
+
+    /// Add a single, already parsed schema to the context.
+    ///
+    /// The schema borrows from the yaml it was parsed from, so that yaml must outlive
+    /// the context. Fails with a [DuplicateSchema](crate::SchemaErrorKind::DuplicateSchema)
+    /// error if the uri is already defined in the context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Context, Schema, Validate};
+    /// #
+    /// let yaml = YamlLoader::load_from_str(r#"
+    ///     uri: just-a-number
+    ///     schema:
+    ///         type: integer
+    /// "#).unwrap();
+    ///
+    /// let mut context = Context::default();
+    /// context.insert_schema(Schema::try_from(&yaml[0]).unwrap()).unwrap();
+    ///
+    /// let document = YamlLoader::load_from_str("10").unwrap().remove(0);
+    /// context.get_schema("just-a-number").unwrap()
+    ///     .validate(&context, &document).unwrap();
+    /// ```
+    pub fn insert_schema(&mut self, schema: Schema<'schema>) -> Result<(), SchemaError<'schema>> {
+        if self.uris.contains_key(schema.uri) {
+            return Err(SchemaErrorKind::DuplicateSchema { uri: schema.uri }.into());
+        }
+
+        let added = self.schemas.len();
+        self.store_schema(schema);
+        self.check_examples(added)
+    }
+
+    /// Check the examples and counterexamples of every schema from the given position onwards,
+    /// which can only be done once any schemas they reference are part of the context.
+    /// If any of them fail, the schemas are removed from the context again.
+    fn check_examples(&mut self, from: usize) -> Result<(), SchemaError<'schema>> {
+        let mut errors: Vec<SchemaError<'schema>> = self.schemas[from..]
+            .iter()
+            .flat_map(|schema| schema.example_errors(self))
+            .collect();
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        self.schemas.truncate(from);
+        self.uris.retain(|_, index| *index < from);
+
+        // References in the schemas kept may have remembered lookups into the removed ones.
+        self.id = NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        if errors.len() == 1 {
+            Err(errors.pop().unwrap())
+        } else {
+            Err(errors.into())
+        }
+    }
+
+    /// Validate a document against the schema with the given uri in this context.
+    ///
+    /// Fails with an [UnknownSchema](ValidationErrorKind::UnknownSchema) error if there
+    /// is no schema with that uri.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: just-a-number
+    ///     schema:
+    ///         type: integer
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let document = YamlLoader::load_from_str("10").unwrap().remove(0);
+    ///
+    /// context.validate("just-a-number", &document).unwrap();
+    /// ```
+    pub fn validate<'yaml>(
+        &'schema self,
+        uri: &'yaml str,
+        yaml: &'yaml Yaml,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        self.get_schema(uri)
+            .ok_or(ValidationErrorKind::UnknownSchema { uri })?
+            .validate(self, yaml)
+    }
+
+    /// Validate a document against every schema in the context, returning the uri of the
+    /// schema it fits best along with the result of validating against it.
+    ///
+    /// The best fit is the schema producing the fewest errors, with ties going to the
+    /// uri which sorts first. Returns None if the context contains no schemas.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    /// ---
+    /// uri: number
+    /// schema:
+    ///     type: integer
+    /// ---
+    /// uri: text
+    /// schema:
+    ///     type: string
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let document = YamlLoader::load_from_str("hello").unwrap().remove(0);
+    ///
+    /// let (uri, result) = context.validate_best(&document).unwrap();
+    /// assert_eq!(uri, "text");
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn validate_best<'yaml>(
+        &'schema self,
+        yaml: &'yaml Yaml,
+    ) -> Option<(&'schema str, Result<(), ValidationError<'yaml>>)>
+    where
+        'schema: 'yaml,
+    {
+        self.uris
+            .iter()
+            .map(|(uri, index)| (*uri, self.schemas[*index].validate(self, yaml)))
+            .min_by_key(|(_, result)| result.as_ref().err().map_or(0, ValidationError::leaf_count))
     }
 }
 
@@ -69,12 +772,13 @@ impl<'schema> TryFrom<&'schema [Yaml]> for Context<'schema> {
     fn try_from(documents: &'schema [Yaml]) -> Result<Self, Self::Error> {
         let schemas = SchemaError::condense_errors(&mut documents.iter().map(Schema::try_from))?;
 
-        Ok(Context {
-            schemas: schemas
-                .into_iter()
-                .map(|schema| (schema.uri, schema))
-                .collect(),
-        })
+        let mut context = Context::default();
+        for schema in schemas {
+            context.store_schema(schema);
+        }
+
+        context.check_examples(0)?;
+        Ok(context)
     }
 }
 
@@ -92,34 +796,112 @@ enum PropertyType<'schema> {
     OneOf(SchemaOneOf<'schema>),
     AllOf(SchemaAllOf<'schema>),
     AnyOf(SchemaAnyOf<'schema>),
+    Const(SchemaConst<'schema>),
+    Tag(SchemaTag<'schema>),
+    Nullable(Box<PropertyType<'schema>>),
+    Custom(SchemaCustom<'schema>),
+    /// A node combining one or more modifiers, optionally alongside a base `type`.
+    Composite {
+        base: Option<Box<PropertyType<'schema>>>,
+        modifiers: Vec<PropertyType<'schema>>,
+    },
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for PropertyType<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        if yaml.as_hash().is_none() {
-            return Err(SchemaErrorKind::WrongType {
-                expected: "hash",
-                actual: yaml.type_to_str(),
-            }
-            .into());
+        PropertyType::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> PropertyType<'schema> {
+    /// Parse a schema node, consulting the context for any custom types.
+    fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        let hash = yaml.as_type("hash", Yaml::as_hash)?;
+
+        SchemaError::condense_errors(
+            &mut hash
+                .keys()
+                .filter_map(Yaml::as_str)
+                .filter(|key| PROPERTY_KEYWORDS.contains(key))
+                .map(|key| {
+                    Err::<(), _>(
+                        SchemaErrorKind::MalformedField {
+                            error: format!("{} only applies to the items of an object", key),
+                        }
+                        .with_path_name(key),
+                    )
+                }),
+        )?;
+
+        PropertyType::parse_property(yaml, ctx)
+    }
+
+    /// Parse the schema of an object's property, which may also hold the [PROPERTY_KEYWORDS]
+    /// read by the object itself.
+    fn parse_property(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.as_type("hash", Yaml::as_hash)?;
+
+        let nullable = yaml
+            .lookup("nullable", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("nullable"))
+            .into_optional()?
+            .unwrap_or(false);
+
+        let property = PropertyType::parse_keywords(yaml, ctx)?;
+
+        if nullable {
+            Ok(PropertyType::Nullable(Box::new(property)))
+        } else {
+            Ok(property)
         }
+    }
 
+    /// Picks the kind of property described by the keywords in a schema node.
+    fn parse_keywords(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
         if let Some(uri) = yaml
             .lookup("$ref", "string", Yaml::as_str)
             .into_optional()
             .map_err(SchemaError::from)?
         {
-            return Ok(PropertyType::Reference(SchemaReference { uri }));
+            let reference = PropertyType::Reference(SchemaReference::new(uri));
+
+            // Any other keywords alongside the reference further restrict the referenced schema.
+            if yaml
+                .as_type("hash", Yaml::as_hash)?
+                .keys()
+                .filter_map(Yaml::as_str)
+                .all(|key| ANNOTATION_KEYWORDS.contains(&key) || PROPERTY_KEYWORDS.contains(&key))
+            {
+                return Ok(reference);
+            }
+
+            return Ok(PropertyType::Composite {
+                base: Some(Box::new(reference)),
+                modifiers: vec![PropertyType::parse_constraints(yaml, ctx, true)?],
+            });
         }
 
+        PropertyType::parse_constraints(yaml, ctx, false)
+    }
+
+    /// Parses the modifiers and base type of a node, ignoring any `$ref`. With `infer_type`,
+    /// keywords of a built-in type are accepted without a `type`, see [infer_type](PropertyType::infer_type).
+    fn parse_constraints(
+        yaml: &'schema Yaml,
+        ctx: &Context,
+        infer_type: bool,
+    ) -> Result<Self, SchemaError<'schema>> {
+        let mut modifiers = Vec::new();
+
         if yaml
             .lookup("not", "hash", Option::from)
             .into_optional()
             .map_err(SchemaError::from)?
             .is_some()
         {
-            return Ok(PropertyType::Not(SchemaNot::try_from(yaml)?));
+            modifiers.push(PropertyType::Not(SchemaNot::parse(yaml, ctx)?));
         }
 
         if yaml
@@ -128,7 +910,7 @@ impl<'schema> TryFrom<&'schema Yaml> for PropertyType<'schema> {
             .map_err(SchemaError::from)?
             .is_some()
         {
-            return Ok(PropertyType::OneOf(SchemaOneOf::try_from(yaml)?));
+            modifiers.push(PropertyType::OneOf(SchemaOneOf::parse(yaml, ctx)?));
         }
 
         if yaml
@@ -137,7 +919,7 @@ impl<'schema> TryFrom<&'schema Yaml> for PropertyType<'schema> {
             .map_err(SchemaError::from)?
             .is_some()
         {
-            return Ok(PropertyType::AllOf(SchemaAllOf::try_from(yaml)?));
+            modifiers.push(PropertyType::AllOf(SchemaAllOf::parse(yaml, ctx)?));
         }
 
         if yaml
@@ -146,43 +928,236 @@ impl<'schema> TryFrom<&'schema Yaml> for PropertyType<'schema> {
             .map_err(SchemaError::from)?
             .is_some()
         {
-            return Ok(PropertyType::AnyOf(SchemaAnyOf::try_from(yaml)?));
+            modifiers.push(PropertyType::AnyOf(SchemaAnyOf::parse(yaml, ctx)?));
         }
 
-        let typename = yaml.lookup("type", "string", Yaml::as_str)?;
+        if yaml.lookup("const", "yaml", Option::from).is_ok() {
+            modifiers.push(PropertyType::Const(SchemaConst::parse(yaml)?));
+        }
 
-        match typename {
-            "object" => Ok(PropertyType::Object(SchemaObject::try_from(yaml)?)),
-            "string" => Ok(PropertyType::String(SchemaString::try_from(yaml)?)),
-            "integer" => Ok(PropertyType::Integer(SchemaInteger::try_from(yaml)?)),
-            "real" => Ok(PropertyType::Real(SchemaReal::try_from(yaml)?)),
-            "array" => Ok(PropertyType::Array(SchemaArray::try_from(yaml)?)),
-            "hash" => Ok(PropertyType::Hash(SchemaHash::try_from(yaml)?)),
-            "boolean" => Ok(PropertyType::Bool(SchemaBool::try_from(yaml)?)),
-            unknown_type => Err(SchemaErrorKind::UnknownType { unknown_type }.into()),
+        if yaml.lookup("tag", "yaml", Option::from).is_ok() {
+            modifiers.push(PropertyType::Tag(SchemaTag::parse(yaml)?));
         }
-    }
-}
 
-impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for PropertyType<'schema> {
-    fn validate(
-        &self,
-        ctx: &'schema Context<'schema>,
-        yaml: &'yaml Yaml,
+        if yaml.lookup("exclusive", "bool", Option::from).is_ok()
+            && !modifiers
+                .iter()
+                .any(|m| matches!(m, PropertyType::OneOf(_)))
+        {
+            return Err(SchemaErrorKind::MalformedField {
+                error: "exclusive can only be used alongside oneOf".into(),
+            }
+            .with_path_name("exclusive"));
+        }
+
+        let typed = yaml.lookup("type", "string", Option::from).is_ok();
+        let base = match (typed, infer_type) {
+            (false, true) => PropertyType::infer_type(yaml, ctx)?,
+            (false, false) if !modifiers.is_empty() => None,
+            _ => Some(PropertyType::parse_type(yaml, ctx)?),
+        };
+
+        match (base, modifiers.len()) {
+            (Some(base), 0) => Ok(base),
+            (Some(base), _) => Ok(PropertyType::Composite {
+                base: Some(Box::new(base)),
+                modifiers,
+            }),
+            // Reports the missing type.
+            (None, 0) => PropertyType::parse_type(yaml, ctx),
+            // Without a base type, the modifiers are the only thing allowed in the node.
+            (None, 1) => {
+                yaml.strict_schema_contents(&[], &[])?;
+                Ok(modifiers.pop().unwrap())
+            }
+            (None, _) => {
+                yaml.strict_schema_contents(&[], &[])?;
+                Ok(PropertyType::Composite {
+                    base: None,
+                    modifiers,
+                })
+            }
+        }
+    }
+
+    /// Parses the keywords of a node without a `type` as the built-in type they belong to,
+    /// such as `maxLength` alongside a reference to a string schema. Keywords shared by
+    /// several types, such as `minimum`, accept a value of any of them.
+    ///
+    /// Nodes with nothing but universal keywords have no base type.
+    fn infer_type(
+        yaml: &'schema Yaml,
+        ctx: &Context,
+    ) -> Result<Option<Self>, SchemaError<'schema>> {
+        let keywords: Vec<&str> = yaml
+            .as_type("hash", Yaml::as_hash)?
+            .keys()
+            .filter_map(Yaml::as_str)
+            .filter(|key| !UNIVERSAL_KEYWORDS.contains(key) && !PROPERTY_KEYWORDS.contains(key))
+            .collect();
+
+        if keywords.is_empty() {
+            return Ok(None);
+        }
+
+        let (mut candidates, errors): (Vec<_>, Vec<_>) = TYPE_KEYWORDS
+            .iter()
+            .filter(|(_, vocabulary)| keywords.iter().all(|key| vocabulary.contains(key)))
+            .map(|(typename, _)| PropertyType::parse_typed(yaml, typename, ctx))
+            .partition(Result::is_ok);
+
+        match (candidates.len(), errors.into_iter().next()) {
+            // No type accepts every keyword, so the type must be given.
+            (0, None) => PropertyType::parse_type(yaml, ctx).map(Some),
+            (0, Some(err)) => err.map(Some),
+            (1, _) => candidates.pop().unwrap().map(Some),
+            _ => Ok(Some(PropertyType::AnyOf(SchemaAnyOf {
+                items: candidates.into_iter().map(Result::unwrap).collect(),
+            }))),
+        }
+    }
+
+    /// Parses the base type of a node, as given by its `type` field.
+    fn parse_type(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        let typename = yaml.lookup("type", "string", Yaml::as_str)?;
+        PropertyType::parse_typed(yaml, typename, ctx)
+    }
+
+    fn parse_typed(
+        yaml: &'schema Yaml,
+        typename: &'schema str,
+        ctx: &Context,
+    ) -> Result<Self, SchemaError<'schema>> {
+        if ctx.strict_vocabulary && TYPE_KEYWORDS.iter().any(|(name, _)| *name == typename) {
+            check_vocabulary(yaml, typename)?;
+        }
+
+        match typename {
+            "object" => Ok(PropertyType::Object(SchemaObject::parse(yaml, ctx)?)),
+            "string" => Ok(PropertyType::String(SchemaString::parse(yaml, ctx)?)),
+            "integer" => Ok(PropertyType::Integer(SchemaInteger::try_from(yaml)?)),
+            "real" => Ok(PropertyType::Real(SchemaReal::try_from(yaml)?)),
+            "array" => Ok(PropertyType::Array(SchemaArray::parse(yaml, ctx)?)),
+            "hash" => Ok(PropertyType::Hash(SchemaHash::parse(yaml, ctx)?)),
+            "boolean" => Ok(PropertyType::Bool(SchemaBool::try_from(yaml)?)),
+            custom_type if ctx.custom_types.contains_key(custom_type) => {
+                Ok(PropertyType::Custom(SchemaCustom::try_from(yaml)?))
+            }
+            unknown_type => Err(SchemaErrorKind::UnknownType { unknown_type }.into()),
+        }
+    }
+}
+
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for PropertyType<'schema> {
+    fn validate_with(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         match self {
-            PropertyType::Integer(p) => p.validate(ctx, yaml),
-            PropertyType::Real(p) => p.validate(ctx, yaml),
-            PropertyType::String(p) => p.validate(ctx, yaml),
-            PropertyType::Object(p) => p.validate(ctx, yaml),
-            PropertyType::Array(p) => p.validate(ctx, yaml),
-            PropertyType::Hash(p) => p.validate(ctx, yaml),
-            PropertyType::Reference(p) => p.validate(ctx, yaml),
-            PropertyType::Not(p) => p.validate(ctx, yaml),
-            PropertyType::OneOf(p) => p.validate(ctx, yaml),
-            PropertyType::AllOf(p) => p.validate(ctx, yaml),
-            PropertyType::AnyOf(p) => p.validate(ctx, yaml),
-            PropertyType::Bool(p) => p.validate(ctx, yaml),
+            PropertyType::Integer(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Real(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::String(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Object(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Array(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Hash(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Reference(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Not(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::OneOf(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::AllOf(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::AnyOf(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Const(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Tag(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Bool(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Custom(p) => p.validate_with(ctx, yaml, opts),
+            PropertyType::Nullable(p) => {
+                if yaml.is_null() {
+                    Ok(())
+                } else {
+                    p.validate_with(ctx, yaml, opts)
+                }
+            }
+            PropertyType::Composite { base, modifiers } => {
+                // An object refusing unevaluated properties shares its properties with
+                // the object branches of its allOf, so the two are validated together.
+                let shared = match base.as_deref() {
+                    Some(PropertyType::Object(object))
+                        if object.unevaluated_properties == Some(false) =>
+                    {
+                        modifiers.iter().find_map(|modifier| match modifier {
+                            PropertyType::AllOf(all_of) => Some((object, all_of)),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                };
+
+                match shared {
+                    Some((object, all_of)) => {
+                        object.validate_with_all_of(ctx, yaml, opts, all_of)?
+                    }
+                    None => {
+                        if let Some(base) = base {
+                            base.validate_with(ctx, yaml, opts)?;
+                        }
+                    }
+                }
+
+                ValidationError::condense_errors(
+                    &mut modifiers
+                        .iter()
+                        .filter(|modifier| {
+                            shared.is_none() || !matches!(modifier, PropertyType::AllOf(_))
+                        })
+                        .map(|modifier| modifier.validate_with(ctx, yaml, opts))
+                        .filter(Result::is_err),
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        match self {
+            PropertyType::Integer(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Real(p) => p.matches(ctx, yaml, opts),
+            PropertyType::String(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Object(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Array(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Hash(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Reference(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Not(p) => p.matches(ctx, yaml, opts),
+            PropertyType::OneOf(p) => p.matches(ctx, yaml, opts),
+            PropertyType::AllOf(p) => p.matches(ctx, yaml, opts),
+            PropertyType::AnyOf(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Const(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Tag(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Bool(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Custom(p) => p.matches(ctx, yaml, opts),
+            PropertyType::Nullable(p) => yaml.is_null() || p.matches(ctx, yaml, opts),
+            PropertyType::Composite {
+                base: Some(base),
+                modifiers,
+            } if matches!(base.as_ref(), PropertyType::Object(object) if object.unevaluated_properties == Some(false))
+                && modifiers
+                    .iter()
+                    .any(|modifier| matches!(modifier, PropertyType::AllOf(_))) =>
+            {
+                // Properties shared with an allOf can only be checked all at once.
+                self.validate_with(ctx, yaml, opts).is_ok()
+            }
+            PropertyType::Composite { base, modifiers } => {
+                base.iter().all(|base| base.matches(ctx, yaml, opts))
+                    && modifiers
+                        .iter()
+                        .all(|modifier| modifier.matches(ctx, yaml, opts))
+            }
         }
     }
 }
@@ -192,38 +1167,431 @@ impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for PropertyType<'schema> {
 pub struct Schema<'schema> {
     uri: &'schema str,
     schema: PropertyType<'schema>,
+    // Value given by the `default` keyword of the root node, used when normalizing
+    // documents which refer to the schema.
+    default: Option<&'schema Yaml>,
+    // Documents which must, and must not, validate against the schema.
+    examples: Vec<&'schema Yaml>,
+    counter_examples: Vec<&'schema Yaml>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for Schema<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["uri", "schema"], &[])?;
+        Schema::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_contents(&["uri", "schema"], &["examples", "counterExamples"])?;
 
         let uri = yaml.lookup("uri", "string", Yaml::as_str)?;
-        let schema = PropertyType::try_from(yaml.lookup("schema", "yaml", Option::from)?)
-            .map_err(SchemaError::add_path_name(uri))?;
+        let schema = Schema::parse_node(uri, yaml.lookup("schema", "yaml", Option::from)?, ctx)?;
+
+        let lookup_examples =
+            |field: &'schema str| -> Result<Vec<&'schema Yaml>, SchemaError<'schema>> {
+                Ok(yaml
+                    .lookup(field, "array", Yaml::as_vec)
+                    .map_err(SchemaError::from)
+                    .map_err(SchemaError::add_path_name(field))
+                    .map_err(SchemaError::add_path_name(uri))
+                    .into_optional()?
+                    .map(|examples| examples.iter().collect())
+                    .unwrap_or_default())
+            };
+
+        Ok(Schema {
+            examples: lookup_examples("examples")?,
+            counter_examples: lookup_examples("counterExamples")?,
+            ..schema
+        })
+    }
+
+    /// Parse the root node of the schema with the given uri, without any examples.
+    fn parse_node(
+        uri: &'schema str,
+        yaml: &'schema Yaml,
+        ctx: &Context,
+    ) -> Result<Self, SchemaError<'schema>> {
+        let mut schema = PropertyType::parse(yaml, ctx).map_err(SchemaError::add_path_name(uri))?;
+
+        let mut pointers = Vec::new();
+        anchor_local_references(&mut schema, uri, &mut pointers);
+
+        SchemaError::condense_errors(&mut pointers.into_iter().map(|pointer| {
+            find_node(&schema, pointer).map(|_| ()).ok_or_else(|| {
+                SchemaErrorKind::MalformedField {
+                    error: format!(
+                        "local reference '#{}' does not point to a node within the schema",
+                        pointer
+                    ),
+                }
+                .with_path_name(uri)
+            })
+        }))?;
+
+        Ok(Schema {
+            uri,
+            schema,
+            default: yaml.lookup("default", "yaml", Option::from).ok(),
+            examples: Vec::new(),
+            counter_examples: Vec::new(),
+        })
+    }
+
+    /// Errors for every example which fails to validate, and every counterexample which does not.
+    fn example_errors(&self, ctx: &Context<'schema>) -> Vec<SchemaError<'schema>> {
+        fn validation_error<'a>(
+            schema: &'a Schema<'a>,
+            ctx: &'a Context<'a>,
+            yaml: &'a Yaml,
+        ) -> Option<String> {
+            schema
+                .validate(ctx, yaml)
+                .err()
+                .map(|err| err.to_string().trim_end().to_string())
+        }
+
+        let examples = self.examples.iter().enumerate().filter_map(|(i, example)| {
+            validation_error(self, ctx, example).map(|err| {
+                SchemaErrorKind::MalformedField {
+                    error: format!("example does not validate against the schema: {}", err),
+                }
+                .with_path_index(i)
+            })
+        });
+
+        let counter_examples = self
+            .counter_examples
+            .iter()
+            .enumerate()
+            .filter(|(_, example)| validation_error(self, ctx, example).is_none())
+            .map(|(i, _)| {
+                SchemaErrorKind::MalformedField {
+                    error: "counterexample unexpectedly validates against the schema".into(),
+                }
+                .with_path_index(i)
+            });
+
+        examples
+            .map(SchemaError::add_path_name("examples"))
+            .chain(counter_examples.map(SchemaError::add_path_name("counterExamples")))
+            .map(SchemaError::add_path_name(self.uri))
+            .collect()
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Validate all documents loaded from a single source, such as a file.
+    ///
+    /// An empty source is most likely a mistake rather than something which
+    /// should pass validation, so it fails with a [NoDocuments](ValidationErrorKind::NoDocuments) error.
+    /// When there is more than one document, errors are prefixed with the index of the document.
+    pub fn validate_documents<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        documents: &'yaml [Yaml],
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        match documents {
+            [] => Err(ValidationErrorKind::NoDocuments.into()),
+            [document] => self.validate(ctx, document),
+            documents => {
+                ValidationError::condense_errors(&mut documents.iter().enumerate().map(
+                    |(i, document)| {
+                        self.validate(ctx, document)
+                            .map_err(ValidationError::add_path_index(i))
+                    },
+                ))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Validate a single document, returning an error which does not borrow from the
+    /// document, so that it can be kept around after the document is dropped.
+    pub fn validate_value(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &Yaml,
+    ) -> Result<(), OwnedValidationError> {
+        self.validate(ctx, yaml)
+            .map_err(ValidationError::into_owned)
+    }
+
+    /// The node at the root of the schema, following references and looking past `nullable`,
+    /// or None if a reference cannot be resolved or loops back to itself.
+    fn root<'a>(&'a self, ctx: &'schema Context<'schema>) -> Option<&'a PropertyType<'schema>> {
+        let mut node = &self.schema;
+        let mut following: Vec<usize> = Vec::new();
+
+        loop {
+            node = match node {
+                PropertyType::Nullable(inner) => inner,
+                PropertyType::Reference(reference) => {
+                    let referenced = reference.resolve_node(ctx).ok()?;
+                    let address = referenced as *const PropertyType as usize;
+
+                    if following.contains(&address) {
+                        return None;
+                    }
+
+                    following.push(address);
+                    referenced
+                }
+                node => return Some(node),
+            }
+        }
+    }
+
+    /// The integer schema at the root of this schema, if it is one, for reading back its
+    /// constraints. Schemas of a single node within another are found with
+    /// [get_schema_at](Context::get_schema_at).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Context, Limit};
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             age:
+    ///                 type: integer
+    ///                 minimum: 0
+    ///                 exclusiveMaximum: 150
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let age = context.get_schema_at("person#/items/age").unwrap();
+    /// let integer = age.as_integer(&context).unwrap();
+    ///
+    /// assert_eq!(integer.minimum(), Some(&Limit::Inclusive(0)));
+    /// assert_eq!(integer.maximum(), Some(&Limit::Exclusive(150)));
+    /// assert!(context.get_schema("person").unwrap().as_integer(&context).is_none());
+    /// ```
+    pub fn as_integer<'a>(&'a self, ctx: &'schema Context<'schema>) -> Option<&'a SchemaInteger> {
+        match self.root(ctx)? {
+            PropertyType::Integer(integer) => Some(integer),
+            _ => None,
+        }
+    }
 
-        Ok(Schema { uri, schema })
+    /// The real schema at the root of this schema, if it is one, for reading back its
+    /// constraints. See [as_integer](Schema::as_integer).
+    pub fn as_real<'a>(&'a self, ctx: &'schema Context<'schema>) -> Option<&'a SchemaReal> {
+        match self.root(ctx)? {
+            PropertyType::Real(real) => Some(real),
+            _ => None,
+        }
     }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for Schema<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
-        self.schema.validate(ctx, yaml)
+        let tags = match opts.tags {
+            Some(tags) => tags,
+            None => return self.schema.validate_with(ctx, yaml, opts),
+        };
+
+        // Tags are kept by their path within the document, which is only known from its root,
+        // so the nodes they belong to are found up front, while the document cannot move.
+        opts.tagged.replace(tags.resolve(yaml));
+        let result = self.schema.validate_with(ctx, yaml, opts);
+        opts.tagged.take();
+
+        result
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::GenericError;
     use crate::utils::load_simple;
     use crate::Context;
     use yaml_rust::YamlLoader;
 
+    #[test]
+    fn get_schema_at_pointer() {
+        let yaml = YamlLoader::load_from_str(
+            r##"
+uri: order
+schema:
+  type: object
+  items:
+    shipping:
+      type: object
+      items:
+        street:
+          type: string
+        zip:
+          type: integer
+    billing:
+      $ref: "#/items/shipping"
+"##,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+
+        let address = context.get_schema_at("order#/items/shipping").unwrap();
+        address
+            .validate(&context, &load_simple("street: Main Street\nzip: 1000"))
+            .unwrap();
+
+        assert_eq!(
+            address
+                .validate(&context, &load_simple("street: Main Street\nzip: none"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_name("zip")
+        );
+
+        // References to other nodes are followed, and plain uris refer to the whole schema.
+        context
+            .get_schema_at("order#/items/billing")
+            .unwrap()
+            .validate(&context, &load_simple("zip: 2000"))
+            .unwrap();
+        context
+            .get_schema_at("order")
+            .unwrap()
+            .validate(&context, &load_simple("billing:\n  zip: 2000"))
+            .unwrap();
+
+        assert!(context.get_schema_at("order#/items/phone").is_none());
+        assert!(context.get_schema_at("invoice#/items/shipping").is_none());
+    }
+
+    #[test]
+    fn validate_with_options() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: dimensions
+schema:
+  type: object
+  items:
+    width:
+      type: integer
+    height:
+      type: integer
+      coerce: true
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+        let schema = context.get_schema("dimensions").unwrap();
+        let document = load_simple("width: 10.0\nheight: 20.0");
+
+        assert_eq!(
+            schema.validate(&context, &document).unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "real"
+            }
+            .with_path_name("width")
+        );
+
+        schema
+            .validate_with(
+                &context,
+                &document,
+                &ValidationOptions::default().coerce(true),
+            )
+            .unwrap();
+
+        // Options do not linger in the schema or context between calls.
+        schema
+            .validate_with(&context, &document, &ValidationOptions::default())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn validate_by_uri() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: number
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let document = load_simple("10");
+
+        ctx.validate("number", &document).unwrap();
+
+        assert_eq!(
+            ctx.validate("text", &document).unwrap_err(),
+            ValidationErrorKind::UnknownSchema { uri: "text" }.into()
+        );
+    }
+
+    #[test]
+    fn validate_best() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+---
+uri: server
+schema:
+  type: object
+  items:
+    hostname:
+      type: string
+    port:
+      type: integer
+  required:
+    - hostname
+    - port
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let document = load_simple("name: John\nage: old");
+
+        let (uri, result) = ctx.validate_best(&document).unwrap();
+
+        assert_eq!(uri, "person");
+        assert_eq!(
+            result.unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_name("age")
+        );
+
+        assert!(Context::default().validate_best(&document).is_none());
+    }
+
     #[test]
     fn from_yaml() {
         let yaml = YamlLoader::load_from_str(
@@ -245,4 +1613,874 @@ schema:
         dbg!(&schema);
         schema.validate(&context, &load_simple("20")).unwrap();
     }
+
+    #[test]
+    fn keyword_of_other_type() {
+        let yaml = load_simple("type: string\nminItems: 1\nmaxLength: 5");
+
+        assert_eq!(
+            PropertyType::try_from(&yaml).unwrap_err(),
+            GenericError::ExtraField { field: "minItems" }.into()
+        );
+
+        let mut ctx = Context::default();
+        ctx.set_strict_vocabulary(true);
+
+        assert_eq!(
+            PropertyType::parse(&yaml, &ctx).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "minItems only applies to array schemas, not string".into()
+            }
+            .with_path_name("minItems")
+        );
+
+        assert_eq!(
+            PropertyType::parse(&load_simple("type: boolean\nmultipleOf: 2"), &ctx).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "multipleOf only applies to integer or real schemas, not boolean".into()
+            }
+            .with_path_name("multipleOf")
+        );
+    }
+
+    #[test]
+    fn exclusive_without_one_of() {
+        assert_eq!(
+            PropertyType::try_from(&load_simple("type: integer\nexclusive: false")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "exclusive can only be used alongside oneOf".into()
+            }
+            .with_path_name("exclusive")
+        );
+    }
+
+    #[test]
+    fn unevaluated_properties() {
+        let yaml = load_simple(
+            r#"
+            type: object
+            unevaluatedProperties: false
+            items:
+              name:
+                type: string
+            allOf:
+              - type: object
+                items:
+                  age:
+                    type: integer
+        "#,
+        );
+        let schema = PropertyType::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("name: John\nage: 30"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("name: John\nage: old"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_name("age")
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("name: John\nphone: 1234"))
+                .unwrap_err(),
+            ValidationErrorKind::ExtraField { field: "phone" }.into()
+        );
+    }
+
+    #[test]
+    fn unevaluated_properties_through_references() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: base
+schema:
+  type: object
+  items:
+    id:
+      type: integer
+---
+uri: named
+schema:
+  type: object
+  nullable: true
+  items:
+    name:
+      type: string
+  not:
+    type: string
+---
+uri: person
+schema:
+  type: object
+  unevaluatedProperties: false
+  items:
+    age:
+      type: integer
+  allOf:
+    - $ref: base
+    - $ref: named
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+
+        context
+            .validate("person", &load_simple("id: 1\nname: John\nage: 30"))
+            .unwrap();
+
+        assert_eq!(
+            context
+                .validate("person", &load_simple("id: one\nname: John"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_name("id")
+        );
+
+        assert_eq!(
+            context
+                .validate("person", &load_simple("id: 1\nphone: 1234"))
+                .unwrap_err(),
+            ValidationErrorKind::ExtraField { field: "phone" }.into()
+        );
+    }
+
+    #[test]
+    fn permissive_objects() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+---
+uri: strict-person
+schema:
+  type: object
+  unevaluatedProperties: false
+  items:
+    name:
+      type: string
+"#,
+        )
+        .unwrap();
+
+        let document = load_simple("name: John\nage: 52");
+
+        let strict = Context::try_from(&yaml[..]).unwrap();
+        assert_eq!(
+            strict.validate("person", &document).unwrap_err(),
+            ValidationErrorKind::ExtraField { field: "age" }.into()
+        );
+
+        let mut permissive = Context::try_from(&yaml[..]).unwrap();
+        permissive.set_permissive_objects(true);
+        permissive.validate("person", &document).unwrap();
+
+        // Objects opting into strictness are unaffected by the default.
+        assert_eq!(
+            permissive.validate("strict-person", &document).unwrap_err(),
+            ValidationErrorKind::ExtraField { field: "age" }.into()
+        );
+    }
+
+    #[test]
+    fn reference_with_siblings() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: base-string
+schema:
+  type: string
+  minLength: 2
+---
+uri: code
+schema:
+  $ref: base-string
+  type: string
+  maxLength: 5
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+        let base = context.get_schema("base-string").unwrap();
+        let schema = context.get_schema("code").unwrap();
+
+        schema.validate(&context, &load_simple("abc")).unwrap();
+        base.validate(&context, &load_simple("abcdef")).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&context, &load_simple("abcdef"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxLength
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema.validate(&context, &load_simple("a")).unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MinLength
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn reference_with_untyped_siblings() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: base-string
+schema:
+  type: string
+  minLength: 2
+---
+uri: code
+schema:
+  $ref: base-string
+  maxLength: 5
+---
+uri: base-number
+schema:
+  type: integer
+---
+uri: positive
+schema:
+  $ref: base-number
+  minimum: 1
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+
+        context.validate("code", &load_simple("abc")).unwrap();
+
+        assert_eq!(
+            context
+                .validate("code", &load_simple("abcdef"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxLength
+            }
+            .into()
+        );
+
+        assert_eq!(
+            context.validate("code", &load_simple("a")).unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MinLength
+            }
+            .into()
+        );
+
+        // minimum applies to both integers and reals, either of which is accepted.
+        context.validate("positive", &load_simple("5")).unwrap();
+        context.validate("positive", &load_simple("0")).unwrap_err();
+        context
+            .validate("positive", &load_simple("5.5"))
+            .unwrap_err();
+
+        // Keywords which no single type accepts still need a type.
+        assert_eq!(
+            Schema::try_from(&load_simple(
+                "uri: mixed\nschema:\n  $ref: base-string\n  maxLength: 5\n  minItems: 1"
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::FieldMissing { field: "type" }.with_path_name("mixed")
+        );
+    }
+
+    #[test]
+    fn property_keywords_only_on_properties() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: name
+schema:
+  type: string
+---
+uri: person
+schema:
+  type: object
+  items:
+    fullName:
+      $ref: name
+      aliases: [name]
+    nickname:
+      $ref: name
+      equalTo: fullName
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+
+        context
+            .validate("person", &load_simple("name: John"))
+            .unwrap();
+
+        assert_eq!(
+            context
+                .validate("person", &load_simple("name: 30"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path_name("name")
+        );
+
+        assert_eq!(
+            context
+                .validate("person", &load_simple("name: John\nnickname: Bob"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotEqualTo,
+                args: vec![("key", "nickname".into()), ("other", "name".into())]
+            }
+            .with_path_name("nickname")
+        );
+
+        assert_eq!(
+            Schema::try_from(&load_simple(
+                "uri: tags\nschema:\n  type: hash\n  items:\n    type: string\n    aliases: [label]"
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "aliases only applies to the items of an object".into()
+            }
+            .with_path(breadcrumb!["aliases", "items", "tags"])
+        );
+
+        assert_eq!(
+            Schema::try_from(&load_simple(
+                "uri: pin\nschema:\n  type: string\n  equalTo: code"
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "equalTo only applies to the items of an object".into()
+            }
+            .with_path(breadcrumb!["equalTo", "pin"])
+        );
+    }
+
+    #[test]
+    fn insert_schema() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: test
+schema:
+  type: integer
+---
+uri: another
+schema:
+  $ref: test
+"#,
+        )
+        .unwrap();
+
+        let mut context = Context::try_from(&yaml[..1]).unwrap();
+        context
+            .insert_schema(Schema::try_from(&yaml[1]).unwrap())
+            .unwrap();
+
+        let schema = context.get_schema("another").unwrap();
+        schema.validate(&context, &load_simple("20")).unwrap();
+
+        assert_eq!(
+            context
+                .insert_schema(Schema::try_from(&yaml[0]).unwrap())
+                .unwrap_err(),
+            SchemaErrorKind::DuplicateSchema { uri: "test" }.into()
+        );
+
+        fn semver(_: &Yaml) -> Result<(), ValidationError<'_>> {
+            Ok(())
+        }
+
+        let mut first = Context::default();
+        first.register_type("semver", semver);
+        let mut second = Context::default();
+        second.register_type("semver", semver);
+
+        assert_eq!(
+            first.merge(second).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "custom type 'semver' is registered in both contexts".into()
+            }
+            .into()
+        );
+
+        let mut first = Context::default();
+        first.register_format("semver", |_| Ok(()));
+        let mut second = Context::default();
+        second.register_format("semver", |_| Ok(()));
+
+        assert_eq!(
+            first.merge(second).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "custom format 'semver' is registered in both contexts".into()
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn context_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Context>();
+        assert_send_sync::<Schema>();
+        assert_send_sync::<OwnedSchema>();
+    }
+
+    #[test]
+    fn validate_examples() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: port
+schema:
+  type: integer
+  minimum: 1
+examples:
+  - 8080
+counterExamples:
+  - 0
+  - http
+---
+uri: ports
+schema:
+  type: array
+  items:
+    $ref: port
+examples:
+  - [80, 443]
+"#,
+        )
+        .unwrap();
+
+        Context::try_from(&yaml[..]).unwrap();
+    }
+
+    #[test]
+    fn permissive_counter_example() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: port
+schema:
+  type: integer
+counterExamples:
+  - 0
+  - -1
+  - http
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Context::try_from(&yaml[..]).unwrap_err(),
+            SchemaErrorKind::Multiple {
+                errors: vec![
+                    SchemaErrorKind::MalformedField {
+                        error: "counterexample unexpectedly validates against the schema".into()
+                    }
+                    .with_path(breadcrumb![0usize, "counterExamples", "port"]),
+                    SchemaErrorKind::MalformedField {
+                        error: "counterexample unexpectedly validates against the schema".into()
+                    }
+                    .with_path(breadcrumb![1usize, "counterExamples", "port"]),
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn failing_example() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: port
+schema:
+  type: integer
+examples:
+  - http
+"#,
+        )
+        .unwrap();
+
+        let mut context = Context::default();
+
+        assert_eq!(
+            context.add_schemas(&yaml).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "example does not validate against the schema: #: wrong type, expected integer got string".into()
+            }
+            .with_path(breadcrumb![0usize, "examples", "port"])
+        );
+
+        assert!(context.get_schema("port").is_none());
+    }
+
+    #[test]
+    fn numeric_schema_limits() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+---
+uri: price
+schema:
+  type: real
+  minimum: 0.5
+  nullable: true
+---
+uri: cost
+schema:
+  $ref: price
+---
+uri: a
+schema:
+  $ref: b
+---
+uri: b
+schema:
+  $ref: a
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&yaml[..]).unwrap();
+
+        let cost = ctx.get_schema("cost").unwrap();
+        assert_eq!(
+            cost.as_real(&ctx).unwrap().minimum(),
+            Some(&Limit::Inclusive(0.5))
+        );
+        assert!(cost.as_integer(&ctx).is_none());
+
+        // References which loop back to themselves never reach a node.
+        assert!(ctx.get_schema("a").unwrap().as_real(&ctx).is_none());
+    }
+
+    #[test]
+    fn failing_example_forgets_references() {
+        let base = YamlLoader::load_from_str(
+            r#"---
+uri: phonebook
+schema:
+  type: array
+  items:
+    $ref: person
+"#,
+        )
+        .unwrap();
+
+        let extension = YamlLoader::load_from_str(
+            r#"---
+uri: team
+schema:
+  $ref: phonebook
+counterExamples:
+  - [10]
+---
+uri: person
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        let mut context = Context::try_from(&base[..]).unwrap();
+        assert!(context.add_schemas(&extension).is_err());
+
+        // The reference to person was followed while checking the counterexample of team.
+        assert_eq!(
+            context
+                .validate("phonebook", &load_simple("[10]"))
+                .unwrap_err(),
+            ValidationErrorKind::UnknownSchema { uri: "person" }.into()
+        );
+    }
+
+    #[test]
+    fn merge_contexts() {
+        let base = YamlLoader::load_from_str(
+            r#"---
+uri: test
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        let extension = YamlLoader::load_from_str(
+            r#"---
+uri: another
+schema:
+  $ref: test
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&base[..])
+            .unwrap()
+            .merge(Context::try_from(&extension[..]).unwrap())
+            .unwrap();
+
+        let schema = context.get_schema("another").unwrap();
+        schema.validate(&context, &load_simple("20")).unwrap();
+    }
+
+    #[test]
+    fn merge_contexts_with_collision() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: test
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Context::try_from(&yaml[..])
+                .unwrap()
+                .merge(Context::try_from(&yaml[..]).unwrap())
+                .unwrap_err(),
+            SchemaErrorKind::DuplicateSchema { uri: "test" }.into()
+        );
+    }
+
+    #[test]
+    fn context_from_bundle() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+schemas:
+  person:
+    type: object
+    items:
+      name:
+        type: string
+  phonebook:
+    type: array
+    items:
+      $ref: person
+---
+uri: person
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        let mut context = Context::from_bundle(&yaml[0]).unwrap();
+
+        context
+            .validate("person", &load_simple("name: Bob"))
+            .unwrap();
+        context
+            .validate("phonebook", &load_simple("- name: Bob\n- name: Alice"))
+            .unwrap();
+
+        assert_eq!(
+            context
+                .validate("phonebook", &load_simple("- name: 10"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path(breadcrumb!["name", 0usize])
+        );
+
+        assert_eq!(
+            context.add_schemas(&yaml[1..]).unwrap_err(),
+            SchemaErrorKind::DuplicateSchema { uri: "person" }.into()
+        );
+
+        assert_eq!(
+            context.add_bundle(&yaml[0]).unwrap_err(),
+            SchemaErrorKind::Multiple {
+                errors: vec![
+                    SchemaErrorKind::DuplicateSchema { uri: "person" }.into(),
+                    SchemaErrorKind::DuplicateSchema { uri: "phonebook" }.into(),
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn malformed_bundle() {
+        let yaml = load_simple("schemas:\n  port:\n    type: port");
+
+        assert_eq!(
+            Context::from_bundle(&yaml).unwrap_err(),
+            SchemaErrorKind::UnknownType {
+                unknown_type: "port"
+            }
+            .with_path_name("port")
+        );
+
+        let yaml = load_simple("schemas: [port]");
+
+        assert_eq!(
+            Context::from_bundle(&yaml).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "hash",
+                actual: "array"
+            }
+            .with_path_name("schemas")
+        );
+    }
+
+    #[test]
+    fn type_with_modifiers() {
+        let yaml = load_simple(
+            r#"
+            type: integer
+            allOf:
+              - type: integer
+                minimum: 10
+              - type: integer
+                maximum: 20
+            "#,
+        );
+
+        let schema = PropertyType::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema.validate(&ctx, &load_simple("15")).unwrap();
+
+        assert_eq!(
+            schema.validate(&ctx, &load_simple("hello")).unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema.validate(&ctx, &load_simple("25")).unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::UpperLimit,
+                args: vec![("value", "25".into()), ("limit", "20".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn multiple_modifiers() {
+        let yaml = load_simple(
+            r#"
+            oneOf:
+              - type: integer
+              - type: string
+            not:
+              type: string
+              minLength: 1
+            "#,
+        );
+
+        let schema = PropertyType::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema.validate(&ctx, &load_simple("15")).unwrap();
+        schema.validate(&ctx, &load_simple("''")).unwrap();
+        schema.validate(&ctx, &load_simple("hello")).unwrap_err();
+        schema.validate(&ctx, &load_simple("1.5")).unwrap_err();
+    }
+
+    #[test]
+    fn validate_documents() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: test
+schema:
+  type: integer
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+        let schema = context.get_schema("test").unwrap();
+
+        assert_eq!(
+            schema.validate_documents(&context, &[]).unwrap_err(),
+            ValidationErrorKind::NoDocuments.into()
+        );
+
+        let documents = YamlLoader::load_from_str("10\n---\nhello").unwrap();
+        assert_eq!(
+            schema.validate_documents(&context, &documents).unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_index(1)
+        );
+    }
+
+    #[test]
+    fn validate_value() {
+        let yaml = YamlLoader::load_from_str(
+            r#"---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&yaml[..]).unwrap();
+        let schema = context.get_schema("person").unwrap();
+
+        let mut errors = Vec::new();
+        for source in &["name: 10", "name: bob", "age: 20"] {
+            let document = load_simple(source);
+            if let Err(err) = schema.validate_value(&context, &document) {
+                errors.push(err);
+            }
+        }
+
+        assert_eq!(
+            errors,
+            vec![
+                OwnedValidationError {
+                    kind: OwnedValidationErrorKind::WrongType {
+                        expected: "string",
+                        actual: "integer".into()
+                    },
+                    path: vec![OwnedSegment::Name("name".into())],
+                },
+                OwnedValidationError {
+                    kind: OwnedValidationErrorKind::ExtraField {
+                        field: "age".into()
+                    },
+                    path: vec![],
+                },
+            ]
+        );
+
+        assert_eq!(
+            errors[0].to_string(),
+            "#.name: wrong type, expected string got integer\n"
+        );
+    }
 }
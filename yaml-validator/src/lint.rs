@@ -0,0 +1,267 @@
+use crate::visit::children;
+use crate::{Context, PropertyType, Schema};
+
+/// An advisory finding about a schema, such as a constraint which has no effect.
+///
+/// Unlike a [SchemaError](crate::SchemaError), a warning does not prevent the schema from being used.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintWarning {
+    /// Names leading from the uri of the schema to the node the warning concerns
+    pub schema_path: Vec<String>,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl std::fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.schema_path.join(" -> "), self.message)
+    }
+}
+
+fn warn(path: &[String], warnings: &mut Vec<LintWarning>, message: String) {
+    warnings.push(LintWarning {
+        schema_path: path.to_vec(),
+        message,
+    });
+}
+
+fn lint_child(
+    ctx: &Context,
+    node: &PropertyType,
+    path: &[String],
+    name: String,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let mut path = path.to_vec();
+    path.push(name);
+    lint(ctx, node, &path, warnings);
+}
+
+fn lint(ctx: &Context, node: &PropertyType, path: &[String], warnings: &mut Vec<LintWarning>) {
+    match node {
+        PropertyType::String(string) => {
+            if string.min_length == Some(0) {
+                warn(path, warnings, "minLength of 0 has no effect".into());
+            }
+
+            #[cfg(feature = "regex")]
+            if let (Some(pattern), Some(enumeration)) = (&string.pattern, &string.enumeration) {
+                for value in enumeration.iter().filter(|value| !pattern.is_match(value)) {
+                    warn(
+                        path,
+                        warnings,
+                        format!(
+                            "enum value '{}' does not match pattern '{}' and can never be valid",
+                            value, pattern
+                        ),
+                    );
+                }
+            }
+        }
+        PropertyType::Array(array) => {
+            if array.min_items == Some(0) {
+                warn(path, warnings, "minItems of 0 has no effect".into());
+            }
+        }
+        PropertyType::Object(object) => {
+            // Fields not listed in items may still be present when unevaluated ones are allowed.
+            let allow_unevaluated = object
+                .unevaluated_properties
+                .unwrap_or(ctx.permissive_objects);
+
+            for field in object.required.iter().flatten() {
+                if allow_unevaluated || object.items.contains_key(field) {
+                    continue;
+                }
+
+                #[cfg(feature = "regex")]
+                if let Some(pattern) = &object.additional_property_name_pattern {
+                    if pattern.is_match(field) {
+                        continue;
+                    }
+                }
+
+                warn(
+                    path,
+                    warnings,
+                    format!(
+                        "required field '{}' is not listed in items and can never be present",
+                        field
+                    ),
+                );
+            }
+        }
+        PropertyType::Nullable(inner) => lint(ctx, inner, path, warnings),
+        PropertyType::Composite { base, modifiers } => {
+            for node in base.iter().map(AsRef::as_ref).chain(modifiers) {
+                lint(ctx, node, path, warnings);
+            }
+        }
+        PropertyType::Hash(_)
+        | PropertyType::Not(_)
+        | PropertyType::OneOf(_)
+        | PropertyType::AllOf(_)
+        | PropertyType::AnyOf(_)
+        | PropertyType::Integer(_)
+        | PropertyType::Real(_)
+        | PropertyType::Bool(_)
+        | PropertyType::Reference(_)
+        | PropertyType::Const(_)
+        | PropertyType::Tag(_)
+        | PropertyType::Custom(_) => {}
+    }
+
+    for (child, item) in children(node) {
+        lint_child(ctx, item, path, child.to_string(), warnings);
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Look for constraints in the schema which are redundant, or make it impossible for
+    /// any document to be valid, without rejecting the schema outright. Objects are linted
+    /// according to whether the context lets them accept properties not listed in their `items`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             name:
+    ///                 type: string
+    ///                 minLength: 0
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     context.get_schema("person").unwrap().lint(&context)[0].to_string(),
+    ///     "person -> name: minLength of 0 has no effect"
+    /// );
+    /// ```
+    pub fn lint(&self, ctx: &Context) -> Vec<LintWarning> {
+        let mut warnings = Vec::new();
+        lint(ctx, &self.schema, &[self.uri.to_string()], &mut warnings);
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Context, Schema};
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn lint_redundant_constraints() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: phonebook
+schema:
+  type: object
+  items:
+    entries:
+      type: array
+      minItems: 0
+      items:
+        oneOf:
+          - type: string
+            minLength: 0
+          - type: integer
+"#,
+        )
+        .unwrap();
+
+        let schema = Schema::try_from(&yaml[0]).unwrap();
+
+        let warnings: Vec<String> = schema
+            .lint(&Context::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "phonebook -> entries: minItems of 0 has no effect",
+                "phonebook -> entries -> items -> oneOf[0]: minLength of 0 has no effect",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_unreachable_required() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+  required:
+    - name
+    - age
+"#,
+        )
+        .unwrap();
+
+        let schema = Schema::try_from(&yaml[0]).unwrap();
+
+        assert_eq!(
+            schema.lint(&Context::default())[0].to_string(),
+            "person: required field 'age' is not listed in items and can never be present"
+        );
+        assert_eq!(schema.lint(&Context::default()).len(), 1);
+
+        // Unlisted fields are allowed in permissive contexts, unless the object opts out.
+        let mut permissive = Context::default();
+        permissive.set_permissive_objects(true);
+        assert!(schema.lint(&permissive).is_empty());
+
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  unevaluatedProperties: true
+  items:
+    name:
+      type: string
+  required: [age]
+"#,
+        )
+        .unwrap();
+
+        let schema = Schema::try_from(&yaml[0]).unwrap();
+        assert!(schema.lint(&Context::default()).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn lint_enum_mismatching_pattern() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: color
+schema:
+  type: string
+  pattern: "^[a-z]+$"
+  enum: [red, Green]
+"#,
+        )
+        .unwrap();
+
+        let schema = Schema::try_from(&yaml[0]).unwrap();
+
+        assert_eq!(
+            schema.lint(&Context::default())[0].to_string(),
+            "color: enum value 'Green' does not match pattern '^[a-z]+$' and can never be valid"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::Read;
+use thiserror::Error;
+use yaml_rust::{ScanError, Yaml, YamlLoader};
+
+/// Errors encountered while loading documents with [load_from_reader].
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("failed to read source: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse yaml: {0}")]
+    Yaml(#[from] ScanError),
+}
+
+/// Load every document from a reader, such as a file or stdin, as [YamlLoader::load_from_str] does.
+///
+/// yaml_rust can only load documents from a complete string, so the source is read in full
+/// before parsing. A source which is not valid utf8 fails with an [Io](LoadError::Io) error
+/// of kind [InvalidData](std::io::ErrorKind::InvalidData).
+///
+/// # Examples
+///
+/// ```rust
+/// # use yaml_validator::load_from_reader;
+/// #
+/// let documents = load_from_reader("name: Bob\n---\nname: Alice".as_bytes()).unwrap();
+///
+/// assert_eq!(documents.len(), 2);
+/// assert_eq!(documents[1]["name"].as_str(), Some("Alice"));
+/// ```
+pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Vec<Yaml>, LoadError> {
+    let mut source = String::new();
+    reader.read_to_string(&mut source)?;
+
+    Ok(YamlLoader::load_from_str(&source)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn load_from_memory() {
+        let documents = load_from_reader(Cursor::new("- 1\n- 2\n---\nhello")).unwrap();
+
+        assert_eq!(
+            documents,
+            vec![
+                Yaml::Array(vec![Yaml::Integer(1), Yaml::Integer(2)]),
+                Yaml::String("hello".into())
+            ]
+        );
+
+        match load_from_reader(Cursor::new(b"key: \xff")).unwrap_err() {
+            LoadError::Io(err) => assert_eq!(err.kind(), ErrorKind::InvalidData),
+            err => panic!("expected an io error, got {:?}", err),
+        }
+
+        assert!(matches!(
+            load_from_reader(Cursor::new("key: [1, 2")).unwrap_err(),
+            LoadError::Yaml(_)
+        ));
+    }
+}
@@ -0,0 +1,326 @@
+use crate::errors::messages::{localize, English};
+use crate::errors::{SchemaError, SchemaErrorKind, ValidationError, ValidationErrorKind};
+use crate::utils::{CondenseErrors, YamlUtils};
+use crate::{Context, Schema};
+use std::sync::OnceLock;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// The structure of schema documents, expressed in the vocabulary of the schemas themselves.
+/// Only keywords which nest other schemas are described here, so that the remaining keywords
+/// of each type are listed in one place only, and checked by the types while parsing.
+///
+/// Since `items` means something different for objects than for the other types, nodes are
+/// validated by the custom `node` type, which picks between the two kinds of node by their `type`.
+const META_SCHEMA: &str = r#"
+uri: document
+schema:
+  type: object
+  items:
+    uri:
+      type: string
+      minLength: 1
+    schema:
+      type: node
+    examples:
+      type: array
+    counterExamples:
+      type: array
+  required: [uri, schema]
+---
+uri: object
+schema:
+  allOf:
+    - $ref: keywords
+    - type: object
+      unevaluatedProperties: true
+      items:
+        items:
+          type: properties
+        conditional:
+          type: hash
+          items:
+            type: object
+            unevaluatedProperties: true
+            items:
+              then:
+                type: node
+              else:
+                type: node
+---
+uri: other
+schema:
+  allOf:
+    - $ref: keywords
+    - type: object
+      unevaluatedProperties: true
+      items:
+        items:
+          type: node
+---
+uri: keywords
+schema:
+  type: object
+  unevaluatedProperties: true
+  items:
+    type:
+      type: string
+    not:
+      type: node
+    oneOf:
+      $ref: branches
+    allOf:
+      $ref: branches
+    anyOf:
+      $ref: branches
+    itemsAnyOf:
+      $ref: branches
+    prefixItems:
+      $ref: branches
+    contains:
+      type: node
+---
+uri: branches
+schema:
+  type: array
+  minItems: 1
+  items:
+    type: node
+"#;
+
+static DOCUMENTS: OnceLock<Vec<Yaml>> = OnceLock::new();
+static META: OnceLock<Context<'static>> = OnceLock::new();
+
+/// The meta-schema, parsed once and shared by every thread, since the errors of a
+/// validation cannot outlive the context they were validated against.
+fn meta() -> &'static Context<'static> {
+    META.get_or_init(|| {
+        let documents = DOCUMENTS.get_or_init(|| YamlLoader::load_from_str(META_SCHEMA).unwrap());
+
+        let mut meta = Context::default();
+        meta.register_type("node", validate_node);
+        meta.register_type("properties", validate_properties);
+        meta.add_schemas(documents).unwrap();
+        meta
+    })
+}
+
+fn validate_node(yaml: &Yaml) -> Result<(), ValidationError<'_>> {
+    let uri = match yaml["type"].as_str() {
+        Some("object") => "object",
+        _ => "other",
+    };
+
+    meta().validate(uri, yaml)
+}
+
+/// Validates the properties of an object, each of which is a node named by its key.
+fn validate_properties(yaml: &Yaml) -> Result<(), ValidationError<'_>> {
+    let properties = yaml.as_type("hash", Yaml::as_hash)?;
+
+    ValidationError::condense_errors(&mut properties.iter().map(|(name, property)| {
+        let name = name.as_type("string", Yaml::as_str)?;
+        validate_node(property).map_err(ValidationError::add_path_name(name))
+    }))?;
+
+    Ok(())
+}
+
+/// Describes a failed validation against the meta-schema as a problem with the schema.
+fn into_schema_error(err: ValidationError) -> SchemaError {
+    let kind = match err.kind {
+        ValidationErrorKind::WrongType { expected, actual } => {
+            SchemaErrorKind::WrongType { expected, actual }
+        }
+        ValidationErrorKind::BadValue { expected } => SchemaErrorKind::BadValue { expected },
+        ValidationErrorKind::FieldMissing { field } => SchemaErrorKind::FieldMissing { field },
+        ValidationErrorKind::ExtraField { field } => SchemaErrorKind::ExtraField { field },
+        ValidationErrorKind::Multiple { errors } => SchemaErrorKind::Multiple {
+            errors: errors.into_iter().map(into_schema_error).collect(),
+        },
+        ValidationErrorKind::ValidationError { error } => SchemaErrorKind::MalformedField {
+            error: error.english().into(),
+        },
+        ValidationErrorKind::ParameterizedValidation { error, args } => {
+            SchemaErrorKind::MalformedField {
+                error: localize(&English, error, &args),
+            }
+        }
+        ValidationErrorKind::DetailedValidation { message } => {
+            SchemaErrorKind::MalformedField { error: message }
+        }
+        kind => SchemaErrorKind::MalformedField {
+            error: kind.to_string(),
+        },
+    };
+
+    SchemaError {
+        kind,
+        state: err.state,
+    }
+}
+
+impl<'schema> Context<'schema> {
+    /// Check a single schema document without adding it to the context. The structure of the
+    /// document is checked against a meta-schema first, so that misplaced or mistyped keywords
+    /// are reported at their path within the document, before the schema itself is parsed using
+    /// any custom types and formats registered with the context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let document = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             name:
+    ///                 oneOf:
+    ///                     type: string
+    /// "#).unwrap().remove(0);
+    ///
+    /// assert_eq!(
+    ///     Context::default().validate_schema_document(&document).unwrap_err().to_string(),
+    ///     "#.schema.items.name.oneOf: wrong type, expected array got hash\n"
+    /// );
+    /// ```
+    pub fn validate_schema_document<'a>(&self, document: &'a Yaml) -> Result<(), SchemaError<'a>> {
+        meta()
+            .validate("document", document)
+            .map_err(into_schema_error)?;
+
+        Schema::parse(document, self).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::MessageId;
+
+    #[test]
+    fn validate_schema_documents() {
+        let documents = YamlLoader::load_from_str(
+            r#"
+uri: phonebook
+schema:
+  type: array
+  minItems: 1
+  items:
+    $ref: person
+---
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+      aliases: [fullName]
+    phone:
+      nullable: true
+      oneOf:
+        - type: string
+          title: formatted
+        - type: integer
+    tags:
+      type: hash
+      items:
+        type: string
+    extensions:
+      type: array
+      itemsAnyOf:
+        - type: string
+        - type: integer
+  required: [name]
+  conditional:
+    phone:
+      if:
+        field: name
+        equals: operator
+      then:
+        type: integer
+examples:
+  - name: bob
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::default();
+        for document in &documents {
+            ctx.validate_schema_document(document).unwrap();
+        }
+    }
+
+    #[test]
+    fn malformed_schema_documents() {
+        let document = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: 10
+    phone:
+      oneOf: []
+"#,
+        )
+        .unwrap()
+        .remove(0);
+
+        assert_eq!(
+            Context::default()
+                .validate_schema_document(&document)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "#.schema.items.name.type: wrong type, expected string got integer
+#.schema.items.phone.oneOf: malformed field: {}
+",
+                MessageId::MinItems.english()
+            )
+        );
+
+        // Only arrays take a list of schemas for their items, and under a keyword of its own.
+        let document = YamlLoader::load_from_str(
+            r#"
+uri: labels
+schema:
+  type: hash
+  items:
+    - type: string
+"#,
+        )
+        .unwrap()
+        .remove(0);
+
+        assert!(Context::default()
+            .validate_schema_document(&document)
+            .unwrap_err()
+            .to_string()
+            .starts_with("#.schema.items: wrong type, expected hash got array\n"));
+
+        let document = YamlLoader::load_from_str("schema:\n  type: integer")
+            .unwrap()
+            .remove(0);
+
+        assert_eq!(
+            Context::default()
+                .validate_schema_document(&document)
+                .unwrap_err(),
+            SchemaErrorKind::FieldMissing { field: "uri" }.into()
+        );
+    }
+
+    #[test]
+    fn meta_schema_shared_between_threads() {
+        let here = meta() as *const Context as usize;
+        let there = std::thread::spawn(|| meta() as *const Context as usize)
+            .join()
+            .unwrap();
+
+        assert_eq!(here, there);
+    }
+}
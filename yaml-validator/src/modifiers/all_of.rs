@@ -1,62 +1,130 @@
+use super::parse_branches;
+use crate::errors::SchemaError;
 use crate::errors::ValidationError;
-use crate::errors::{SchemaError, SchemaErrorKind};
 use crate::utils::{CondenseErrors, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use crate::{Context, PropertyType, SchemaObject, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug)]
 pub(crate) struct SchemaAllOf<'schema> {
-    items: Vec<PropertyType<'schema>>,
+    pub(crate) items: Vec<PropertyType<'schema>>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaAllOf<'schema> {
     type Error = SchemaError<'schema>;
-
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["allOf"], &[])?;
-        let items = SchemaError::condense_errors(
-            &mut yaml
-                .lookup("allOf", "array", Yaml::as_vec)?
-                .iter()
-                .map(|property| {
-                    PropertyType::try_from(property).map_err(SchemaError::add_path_name("items"))
-                }),
-        )?;
+        yaml.strict_schema_contents(&["allOf"], &[])?;
+        SchemaAllOf::parse(yaml, &Context::default())
+    }
+}
 
-        if items.is_empty() {
-            return Err(SchemaErrorKind::MalformedField {
-                error: "allOf modifier requires an array of schemas to validate against".to_owned(),
-            }
-            .with_path_name("allOf"));
-        }
+impl<'schema> SchemaAllOf<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        let items = parse_branches(yaml, "allOf", ctx)?;
 
         Ok(SchemaAllOf { items })
     }
+
+    /// Splits the branches into the object schemas they consist of, and all others.
+    ///
+    /// References are followed, and nullable, composite and nested allOf branches looked into,
+    /// so that objects defined elsewhere still share their properties with the others.
+    pub(crate) fn partition_objects<'a>(
+        &'a self,
+        ctx: &'schema Context<'schema>,
+    ) -> (
+        Vec<&'a SchemaObject<'schema>>,
+        Vec<&'a PropertyType<'schema>>,
+    )
+    where
+        'schema: 'a,
+    {
+        let mut objects = Vec::new();
+        let mut others = Vec::new();
+        let mut following = Vec::new();
+
+        for item in &self.items {
+            partition(ctx, item, &mut objects, &mut others, &mut following);
+        }
+
+        (objects, others)
+    }
+}
+
+/// Sorts the node into the objects, or the other schemas, it consists of.
+///
+/// `following` holds the nodes each reference currently being followed resolved to, by address,
+/// so that references which loop back to themselves are validated as they are instead.
+fn partition<'a, 'schema: 'a>(
+    ctx: &'schema Context<'schema>,
+    node: &'a PropertyType<'schema>,
+    objects: &mut Vec<&'a SchemaObject<'schema>>,
+    others: &mut Vec<&'a PropertyType<'schema>>,
+    following: &mut Vec<usize>,
+) {
+    match node {
+        PropertyType::Object(object) => objects.push(object),
+        // Only hashes are validated against the objects, so null never applies.
+        PropertyType::Nullable(inner) => partition(ctx, inner, objects, others, following),
+        PropertyType::AllOf(all_of) => {
+            for item in &all_of.items {
+                partition(ctx, item, objects, others, following);
+            }
+        }
+        PropertyType::Composite { base, modifiers } => {
+            for node in base.as_deref().into_iter().chain(modifiers) {
+                partition(ctx, node, objects, others, following);
+            }
+        }
+        PropertyType::Reference(reference) => match reference.resolve_node(ctx) {
+            Ok(resolved) if !following.contains(&(resolved as *const _ as usize)) => {
+                following.push(resolved as *const _ as usize);
+                partition(ctx, resolved, objects, others, following);
+                following.pop();
+            }
+            // Unknown or looping references report their own errors once validated.
+            _ => others.push(node),
+        },
+        other => others.push(other),
+    }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaAllOf<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         ValidationError::condense_errors(
             &mut self
                 .items
                 .iter()
-                .enumerate()
-                .map(|(_, schema)| schema.validate(ctx, yaml))
+                .map(|schema| schema.validate_with(ctx, yaml, opts))
                 .filter(Result::is_err),
         )?;
         Ok(())
     }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        self.items
+            .iter()
+            .all(|schema| schema.matches(ctx, yaml, opts))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{errors::ValidationErrorKind, utils::load_simple};
+    use crate::errors::SchemaErrorKind;
+    use crate::errors::{MessageId, ValidationErrorKind};
+    use crate::utils::load_simple;
 
     #[test]
     fn one_of_from_yaml() {
@@ -80,7 +148,11 @@ mod tests {
             SchemaErrorKind::Multiple {
                 errors: vec![
                     SchemaErrorKind::FieldMissing { field: "allOf" }.into(),
-                    SchemaErrorKind::ExtraField { field: "allOff" }.into(),
+                    SchemaErrorKind::MisspelledField {
+                        field: "allOff",
+                        suggestion: "allOf"
+                    }
+                    .into(),
                 ]
             }
             .into()
@@ -126,9 +198,20 @@ mod tests {
                 .validate(&Context::default(), &load_simple("hi"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "string length is less than minLength"
+                error: MessageId::MinLength
             }
             .into()
         );
     }
+
+    #[test]
+    fn empty_branches() {
+        assert_eq!(
+            SchemaAllOf::try_from(&load_simple("allOf: []")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "allOf modifier requires an array of schemas to validate against".into()
+            }
+            .with_path_name("allOf")
+        );
+    }
 }
@@ -1,64 +1,80 @@
+use super::parse_branches;
+use crate::errors::SchemaError;
 use crate::errors::ValidationError;
-use crate::errors::{SchemaError, SchemaErrorKind};
 use crate::utils::{CondenseErrors, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug)]
 pub(crate) struct SchemaAnyOf<'schema> {
-    items: Vec<PropertyType<'schema>>,
+    pub(crate) items: Vec<PropertyType<'schema>>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaAnyOf<'schema> {
     type Error = SchemaError<'schema>;
-
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["anyOf"], &[])?;
-        let items = SchemaError::condense_errors(
-            &mut yaml
-                .lookup("anyOf", "array", Yaml::as_vec)?
-                .iter()
-                .map(|property| {
-                    PropertyType::try_from(property).map_err(SchemaError::add_path_name("items"))
-                }),
-        )?;
-
-        if items.is_empty() {
-            return Err(SchemaErrorKind::MalformedField {
-                error: "anyOf modifier requires an array of schemas to validate against".to_owned(),
-            }
-            .with_path_name("anyOf"));
-        }
+        yaml.strict_schema_contents(&["anyOf"], &[])?;
+        SchemaAnyOf::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> SchemaAnyOf<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        let items = parse_branches(yaml, "anyOf", ctx)?;
 
         Ok(SchemaAnyOf { items })
     }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaAnyOf<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         let (valid, errs): (Vec<_>, Vec<_>) = self
             .items
             .iter()
             .enumerate()
-            .map(|(_, schema)| schema.validate(ctx, yaml))
+            .map(|(id, schema)| schema.validate_with(ctx, yaml, opts).map(|_| id))
             .partition(Result::is_ok);
 
+        if let Some(coverage) = opts.coverage {
+            for id in valid.iter().flatten() {
+                coverage.record_branch(ctx, self, *id);
+            }
+        }
+
         if valid.is_empty() {
             Err(ValidationError::condense_errors(&mut errs.into_iter()).unwrap_err())
         } else {
             Ok(())
         }
     }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        // Every branch has to be tried to record which of them matched.
+        if opts.coverage.is_some() {
+            return self.validate_with(ctx, yaml, opts).is_ok();
+        }
+
+        self.items
+            .iter()
+            .any(|schema| schema.matches(ctx, yaml, opts))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::SchemaErrorKind;
     use crate::utils::load_simple;
 
     #[test]
@@ -83,7 +99,11 @@ mod tests {
             SchemaErrorKind::Multiple {
                 errors: vec![
                     SchemaErrorKind::FieldMissing { field: "anyOf" }.into(),
-                    SchemaErrorKind::ExtraField { field: "anyOff" }.into(),
+                    SchemaErrorKind::MisspelledField {
+                        field: "anyOff",
+                        suggestion: "anyOf"
+                    }
+                    .into(),
                 ]
             }
             .into()
@@ -155,4 +175,15 @@ mod tests {
             .validate(&Context::default(), &load_simple("hello world"))
             .unwrap();
     }
+
+    #[test]
+    fn empty_branches() {
+        assert_eq!(
+            SchemaAnyOf::try_from(&load_simple("anyOf: []")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "anyOf modifier requires an array of schemas to validate against".into()
+            }
+            .with_path_name("anyOf")
+        );
+    }
 }
@@ -0,0 +1,162 @@
+use crate::errors::{MessageId, SchemaError, ValidationError, ValidationErrorKind};
+use crate::utils::YamlUtils;
+use crate::{Context, Validate, ValidationOptions};
+use std::convert::TryFrom;
+use yaml_rust::Yaml;
+
+#[derive(Debug)]
+pub(crate) struct SchemaConst<'schema> {
+    pub(crate) value: &'schema Yaml,
+}
+
+impl<'schema> TryFrom<&'schema Yaml> for SchemaConst<'schema> {
+    type Error = SchemaError<'schema>;
+    fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
+        yaml.strict_schema_contents(&["const"], &[])?;
+        SchemaConst::parse(yaml)
+    }
+}
+
+impl<'schema> SchemaConst<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml) -> Result<Self, SchemaError<'schema>> {
+        Ok(SchemaConst {
+            value: yaml.lookup("const", "yaml", Option::from)?,
+        })
+    }
+}
+
+/// Structural equality between two yaml nodes, ignoring the order of keys within hashes
+/// and comparing reals by value rather than by their textual representation.
+fn deep_equal(left: &Yaml, right: &Yaml) -> bool {
+    match (left, right) {
+        (Yaml::Real(left), Yaml::Real(right)) => {
+            match (left.parse::<f64>(), right.parse::<f64>()) {
+                (Ok(left), Ok(right)) => left == right,
+                _ => left == right,
+            }
+        }
+        (Yaml::Array(left), Yaml::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| deep_equal(left, right))
+        }
+        (Yaml::Hash(left), Yaml::Hash(right)) => left.len() == right.len()
+            && left.iter().all(
+                |(key, value)| matches!(right.get(key), Some(other) if deep_equal(value, other)),
+            ),
+        (left, right) => left == right,
+    }
+}
+
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaConst<'schema> {
+    fn validate_with(
+        &self,
+        _: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        _: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        if deep_equal(self.value, yaml) {
+            Ok(())
+        } else {
+            Err(ValidationErrorKind::ValidationError {
+                error: MessageId::ConstMismatch,
+            }
+            .with_path_name("const"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::load_simple;
+
+    #[test]
+    fn validate_scalar() {
+        let yaml = load_simple("const: 1.50");
+        let schema = SchemaConst::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("1.5"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("\"1.5\""))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::ConstMismatch
+            }
+            .with_path_name("const")
+        );
+    }
+
+    #[test]
+    fn validate_object() {
+        let yaml = load_simple(
+            r#"
+            const:
+              version: 2
+              tags: [stable, lts]
+              owner:
+                name: infra
+                team: platform
+            "#,
+        );
+        let schema = SchemaConst::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple(
+                    r#"
+                    owner:
+                      team: platform
+                      name: infra
+                    tags: [stable, lts]
+                    version: 2
+                    "#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple(
+                        r#"
+                        version: 2
+                        tags: [stable, lts]
+                        owner:
+                          name: infra
+                          team: security
+                        "#,
+                    ),
+                )
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::ConstMismatch
+            }
+            .with_path_name("const")
+        );
+
+        // Unlike keys, the order of items in an array is significant.
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple(
+                    r#"
+                    version: 2
+                    tags: [lts, stable]
+                    owner:
+                      name: infra
+                      team: platform
+                    "#,
+                ),
+            )
+            .unwrap_err();
+    }
+}
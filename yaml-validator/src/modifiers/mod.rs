@@ -1,9 +1,42 @@
 pub(crate) mod all_of;
 pub(crate) mod any_of;
+pub(crate) mod constant;
 pub(crate) mod not;
 pub(crate) mod one_of;
+pub(crate) mod tag;
 
 pub(crate) use all_of::SchemaAllOf;
 pub(crate) use any_of::SchemaAnyOf;
+pub(crate) use constant::SchemaConst;
 pub(crate) use not::SchemaNot;
 pub(crate) use one_of::SchemaOneOf;
+pub(crate) use tag::SchemaTag;
+
+use crate::errors::{SchemaError, SchemaErrorKind};
+use crate::utils::{CondenseErrors, YamlUtils};
+use crate::{Context, PropertyType};
+use yaml_rust::Yaml;
+
+/// Parses the schemas listed under a combinator keyword such as `oneOf`, rejecting
+/// an empty list before any of the schemas are parsed.
+fn parse_branches<'schema>(
+    yaml: &'schema Yaml,
+    keyword: &'static str,
+    ctx: &Context,
+) -> Result<Vec<PropertyType<'schema>>, SchemaError<'schema>> {
+    let branches = yaml.lookup(keyword, "array", Yaml::as_vec)?;
+
+    if branches.is_empty() {
+        return Err(SchemaErrorKind::MalformedField {
+            error: format!(
+                "{} modifier requires an array of schemas to validate against",
+                keyword
+            ),
+        }
+        .with_path_name(keyword));
+    }
+
+    SchemaError::condense_errors(&mut branches.iter().map(|property| {
+        PropertyType::parse(property, ctx).map_err(SchemaError::add_path_name("items"))
+    }))
+}
@@ -1,22 +1,27 @@
-use crate::errors::{SchemaError, ValidationError, ValidationErrorKind};
+use crate::errors::{MessageId, SchemaError, ValidationError, ValidationErrorKind};
 use crate::utils::YamlUtils;
-use crate::{Context, PropertyType, Validate};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug)]
 pub(crate) struct SchemaNot<'schema> {
-    item: Box<PropertyType<'schema>>,
+    pub(crate) item: Box<PropertyType<'schema>>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaNot<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["not"], &[])?;
+        yaml.strict_schema_contents(&["not"], &[])?;
+        SchemaNot::parse(yaml, &Context::default())
+    }
+}
 
+impl<'schema> SchemaNot<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
         // I'm using Option::from here because I don't actually want to transform
         // the resulting yaml object into a specific type, but need the yaml itself
-        // to be passed into PropertyType::try_from
+        // to be passed into PropertyType::parse
         yaml.lookup("not", "yaml", Option::from)
             .map_err(SchemaError::from)
             .map(|inner| {
@@ -25,7 +30,8 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaNot<'schema> {
                     .map_err(SchemaError::add_path_name("not"))?;
                 Ok(SchemaNot {
                     item: Box::new(
-                        PropertyType::try_from(inner).map_err(SchemaError::add_path_name("not"))?,
+                        PropertyType::parse(inner, ctx)
+                            .map_err(SchemaError::add_path_name("not"))?,
                     ),
                 })
             })?
@@ -33,19 +39,30 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaNot<'schema> {
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaNot<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
-        match self.item.validate(ctx, yaml) {
-            Err(_) => Ok(()),
-            Ok(_) => Err(ValidationErrorKind::ValidationError {
-                error: "validation inversion failed because inner result matched",
+        if self.item.matches(ctx, yaml, opts) {
+            Err(ValidationErrorKind::ValidationError {
+                error: MessageId::InversionMatched,
             }
-            .with_path_name("not")),
+            .with_path_name("not"))
+        } else {
+            Ok(())
         }
     }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        !self.item.matches(ctx, yaml, opts)
+    }
 }
 
 #[cfg(test)]
@@ -74,7 +91,11 @@ mod tests {
             SchemaErrorKind::Multiple {
                 errors: vec![
                     SchemaErrorKind::FieldMissing { field: "not" }.into(),
-                    SchemaErrorKind::ExtraField { field: "note" }.into(),
+                    SchemaErrorKind::MisspelledField {
+                        field: "note",
+                        suggestion: "not"
+                    }
+                    .into(),
                 ]
             }
             .into()
@@ -111,7 +132,7 @@ mod tests {
                 .validate(&Context::default(), &load_simple("20"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "validation inversion failed because inner result matched"
+                error: MessageId::InversionMatched
             }
             .with_path_name("not")
         );
@@ -147,4 +168,54 @@ mod tests {
             .validate(&Context::default(), &load_simple("20"))
             .unwrap();
     }
+
+    #[test]
+    fn matches_compound_schemas() {
+        let yaml = load_simple(
+            r#"
+            not:
+              type: array
+              minItems: 1
+              uniqueItems: true
+              items:
+                oneOf:
+                  - type: integer
+                    minimum: 10
+                  - type: integer
+                    maximum: 20
+                  - anyOf:
+                    - type: string
+                    - type: boolean
+              contains:
+                type: boolean
+            "#,
+        );
+        let schema = SchemaNot::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        for (source, inverted) in &[
+            ("[true]", false),
+            ("[hello, true, 5, 30]", false),
+            ("[15, true]", true),
+            ("[hello]", true),
+            ("[true, true]", true),
+            ("[]", true),
+            ("name: bob", true),
+        ] {
+            let document = load_simple(source);
+            let inner = schema.item.validate(&ctx, &document).is_ok();
+
+            assert_eq!(
+                schema.item.matches(&ctx, &document, &Default::default()),
+                inner
+            );
+            assert_eq!(schema.matches(&ctx, &document, &Default::default()), !inner);
+            assert_eq!(
+                schema.validate(&ctx, &document).is_ok(),
+                *inverted,
+                "{}",
+                source
+            );
+        }
+    }
 }
@@ -1,70 +1,109 @@
-use crate::errors::{SchemaError, SchemaErrorKind};
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{CondenseErrors, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use super::parse_branches;
+use crate::errors::SchemaError;
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
+use crate::utils::{OptionalLookup, YamlUtils};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug)]
 pub(crate) struct SchemaOneOf<'schema> {
-    items: Vec<PropertyType<'schema>>,
+    pub(crate) items: Vec<PropertyType<'schema>>,
+    // The title or $comment of each branch, naming it in errors instead of its index.
+    labels: Vec<Option<&'schema str>>,
+    // When false, more than one branch is allowed to match, like anyOf.
+    exclusive: bool,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaOneOf<'schema> {
     type Error = SchemaError<'schema>;
-
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["oneOf"], &[])?;
-        let items = SchemaError::condense_errors(
-            &mut yaml
-                .lookup("oneOf", "array", Yaml::as_vec)?
-                .iter()
-                .map(|property| {
-                    PropertyType::try_from(property).map_err(SchemaError::add_path_name("items"))
-                }),
-        )?;
-
-        if items.is_empty() {
-            return Err(SchemaErrorKind::MalformedField {
-                error: "oneOf modifier requires an array of schemas to validate against".to_owned(),
-            }
-            .with_path_name("oneOf"));
-        }
+        yaml.strict_schema_contents(&["oneOf"], &["exclusive"])?;
+        SchemaOneOf::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> SchemaOneOf<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        let items = parse_branches(yaml, "oneOf", ctx)?;
 
-        Ok(SchemaOneOf { items })
+        let labels = yaml
+            .lookup("oneOf", "array", Yaml::as_vec)?
+            .iter()
+            .map(|branch| {
+                branch["title"]
+                    .as_str()
+                    .or_else(|| branch["$comment"].as_str())
+            })
+            .collect();
+
+        let exclusive = yaml
+            .lookup("exclusive", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("exclusive"))
+            .into_optional()?
+            .unwrap_or(true);
+
+        Ok(SchemaOneOf {
+            items,
+            labels,
+            exclusive,
+        })
     }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaOneOf<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         let (valid, errs): (Vec<_>, Vec<_>) = self
             .items
             .iter()
             .enumerate()
-            .map(|(id, schema)| schema.validate(ctx, yaml).map(|valid| (valid, id)))
+            .map(|(id, schema)| {
+                schema
+                    .validate_with(ctx, yaml, opts)
+                    .map(|valid| (valid, id))
+                    .map_err(ValidationError::add_path_index(id))
+            })
             .partition(Result::is_ok);
 
+        if let Some(coverage) = opts.coverage {
+            for (_, id) in valid.iter().flatten() {
+                coverage.record_branch(ctx, self, *id);
+            }
+        }
+
         match valid.len() {
             0 => {
-                // If none of the options matched, return the errors from ALL the arms
-                Err(ValidationError::condense_errors(&mut errs.into_iter()).unwrap_err())
+                // If none of the options matched, return the errors from ALL the arms,
+                // using their index in the oneOf array
+                Err(ValidationErrorKind::Multiple {
+                    errors: errs.into_iter().map(Result::unwrap_err).collect(),
+                }
+                .with_path_name("oneOf"))
             }
             1 => Ok(()),
+            _ if !self.exclusive => Ok(()),
             _ => {
                 // Generate an 'error' for each of the arms that validated correctly, using their index. in the oneOf array
                 Err(ValidationErrorKind::Multiple {
                     errors: valid
                         .into_iter()
                         .map(Result::unwrap)
-                        .map(|(_, id)| {
-                            ValidationErrorKind::ValidationError {
-                                error: "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch",
+                        .map(|(_, id)| match self.labels[id] {
+                            Some(label) => ValidationErrorKind::ParameterizedValidation {
+                                error: MessageId::MultipleOneOfLabelled,
+                                args: vec![("label", label.to_string())],
                             }
-                            .with_path_index(id)
+                            .with_path_index(id),
+                            None => ValidationErrorKind::ValidationError {
+                                error: MessageId::MultipleOneOf,
+                            }
+                            .with_path_index(id),
                         })
                         .collect(),
                 }
@@ -72,11 +111,36 @@ impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaOneOf<'schema> {
             }
         }
     }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        // Every branch has to be tried to record which of them matched.
+        if opts.coverage.is_some() {
+            return self.validate_with(ctx, yaml, opts).is_ok();
+        }
+
+        let mut valid = self
+            .items
+            .iter()
+            .filter(|schema| schema.matches(ctx, yaml, opts));
+
+        // Exclusive oneOf only needs to know whether there is a second match.
+        match (valid.next(), self.exclusive) {
+            (None, _) => false,
+            (Some(_), false) => true,
+            (Some(_), true) => valid.next().is_none(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::SchemaErrorKind;
     use crate::utils::load_simple;
 
     #[test]
@@ -101,7 +165,11 @@ mod tests {
             SchemaErrorKind::Multiple {
                 errors: vec![
                     SchemaErrorKind::FieldMissing { field: "oneOf" }.into(),
-                    SchemaErrorKind::ExtraField { field: "oneOff" }.into(),
+                    SchemaErrorKind::MisspelledField {
+                        field: "oneOff",
+                        suggestion: "oneOf"
+                    }
+                    .into(),
                 ]
             }
             .into()
@@ -136,13 +204,92 @@ mod tests {
             .unwrap()
             .validate(&Context::default(), &load_simple("10"))
             .unwrap_err(),
-            ValidationErrorKind::Multiple { errors: vec![
-                ValidationErrorKind::ValidationError { error: "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch"}.with_path_index(0),
-                ValidationErrorKind::ValidationError { error: "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch"}.with_path_index(1),
-            ]}.with_path_name("oneOf")
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MultipleOneOf
+                    }
+                    .with_path_index(0),
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MultipleOneOf
+                    }
+                    .with_path_index(1),
+                ]
+            }
+            .with_path_name("oneOf")
         )
     }
 
+    #[test]
+    fn validate_multiple_valid_labels() {
+        let yaml = load_simple(
+            r#"
+            oneOf:
+              - type: integer
+                title: port number
+              - type: integer
+                $comment: process id
+              - type: integer
+              - type: string
+                title: service name
+            "#,
+        );
+
+        let schema = SchemaOneOf::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+        let document = load_simple("10");
+
+        assert_eq!(
+            schema.validate(&ctx, &document).unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::MultipleOneOfLabelled,
+                        args: vec![("label", "port number".into())]
+                    }
+                    .with_path_index(0),
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::MultipleOneOfLabelled,
+                        args: vec![("label", "process id".into())]
+                    }
+                    .with_path_index(1),
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MultipleOneOf
+                    }
+                    .with_path_index(2),
+                ]
+            }
+            .with_path_name("oneOf")
+        );
+    }
+
+    #[test]
+    fn validate_multiple_valid_non_exclusive() {
+        let yaml = load_simple(
+            r#"
+            oneOf:
+              - type: integer
+              - type: integer
+                minimum: 5
+            exclusive: false
+            "#,
+        );
+
+        let schema = SchemaOneOf::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("10"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("hello"))
+                .unwrap_err()
+                .leaf_count(),
+            2
+        );
+    }
+
     #[test]
     fn validate_multiple_possible() {
         SchemaOneOf::try_from(&load_simple(
@@ -186,10 +333,63 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("hello you!"))
                 .unwrap_err(),
-                ValidationErrorKind::Multiple { errors: vec![
-                ValidationErrorKind::ValidationError { error: "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch"}.with_path_index(0),
-                ValidationErrorKind::ValidationError { error: "multiple branches of oneOf validated successfully. oneOf must only contain a single valid branch"}.with_path_index(1),
-            ]}.with_path_name("oneOf")
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MultipleOneOf
+                    }
+                    .with_path_index(0),
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MultipleOneOf
+                    }
+                    .with_path_index(1),
+                ]
+            }
+            .with_path_name("oneOf")
+        );
+    }
+
+    #[test]
+    fn validate_no_valid_branches() {
+        let yaml = load_simple(
+            r#"
+                oneOf:
+                  - type: integer
+                  - type: string
+                "#,
+        );
+
+        assert_eq!(
+            SchemaOneOf::try_from(&yaml)
+                .unwrap()
+                .validate(&Context::default(), &load_simple("1.5"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::WrongType {
+                        expected: "integer",
+                        actual: "real"
+                    }
+                    .with_path_index(0),
+                    ValidationErrorKind::WrongType {
+                        expected: "string",
+                        actual: "real"
+                    }
+                    .with_path_index(1),
+                ]
+            }
+            .with_path_name("oneOf")
+        );
+    }
+
+    #[test]
+    fn empty_branches() {
+        assert_eq!(
+            SchemaOneOf::try_from(&load_simple("oneOf: []")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "oneOf modifier requires an array of schemas to validate against".into()
+            }
+            .with_path_name("oneOf")
         );
     }
 }
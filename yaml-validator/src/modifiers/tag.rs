@@ -0,0 +1,140 @@
+use crate::errors::{MessageId, SchemaError, ValidationError, ValidationErrorKind};
+use crate::utils::YamlUtils;
+use crate::{Context, Validate, ValidationOptions};
+use std::convert::TryFrom;
+use yaml_rust::Yaml;
+
+#[derive(Debug)]
+pub(crate) struct SchemaTag<'schema> {
+    pub(crate) tag: &'schema str,
+}
+
+impl<'schema> TryFrom<&'schema Yaml> for SchemaTag<'schema> {
+    type Error = SchemaError<'schema>;
+    fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
+        yaml.strict_schema_contents(&["tag"], &[])?;
+        SchemaTag::parse(yaml)
+    }
+}
+
+impl<'schema> SchemaTag<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml) -> Result<Self, SchemaError<'schema>> {
+        Ok(SchemaTag {
+            tag: yaml
+                .lookup("tag", "string", Yaml::as_str)
+                .map_err(SchemaError::from)
+                .map_err(SchemaError::add_path_name("tag"))?,
+        })
+    }
+}
+
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaTag<'schema> {
+    fn validate_with(
+        &self,
+        _: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        // Tags are dropped when loading documents normally, so checking them
+        // without the tags would reject every single document.
+        if opts.tags.is_none() {
+            return Err(ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::TagsUnavailable,
+                args: vec![("tag", self.tag.to_string())],
+            }
+            .into());
+        }
+
+        let expected = ("expected", self.tag.to_string());
+
+        let (error, args) = match opts
+            .tagged
+            .borrow()
+            .get(&(yaml as *const Yaml as usize))
+            .copied()
+        {
+            Some(tag) if tag == self.tag => return Ok(()),
+            Some(tag) => (
+                MessageId::TagMismatch,
+                vec![expected, ("actual", tag.to_string())],
+            ),
+            None => (MessageId::Untagged, vec![expected]),
+        };
+
+        Err(ValidationErrorKind::ParameterizedValidation { error, args }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::load_simple;
+    use crate::{Schema, TaggedDocuments};
+
+    #[test]
+    fn validate_tag() {
+        let yaml = load_simple("uri: secret\nschema:\n  tag: '!Secret'");
+        let schema = Schema::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        let documents =
+            TaggedDocuments::load_from_str("!Secret hunter2\n---\n!Public hunter2\n---\nhunter2")
+                .unwrap();
+        let documents: Vec<_> = documents.iter().collect();
+
+        let (document, tags) = documents[0];
+        schema
+            .validate_with(&ctx, document, &ValidationOptions::default().tags(tags))
+            .unwrap();
+
+        let (document, tags) = documents[1];
+        assert_eq!(
+            schema
+                .validate_with(&ctx, document, &ValidationOptions::default().tags(tags))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::TagMismatch,
+                args: vec![("expected", "!Secret".into()), ("actual", "!Public".into())]
+            }
+            .into()
+        );
+
+        let (document, tags) = documents[2];
+        assert_eq!(
+            schema
+                .validate_with(&ctx, document, &ValidationOptions::default().tags(tags))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::Untagged,
+                args: vec![("expected", "!Secret".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema.validate(&ctx, documents[0].0).unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::TagsUnavailable,
+                args: vec![("tag", "!Secret".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_tag_of_copied_document() {
+        let yaml = load_simple(
+            "uri: login\nschema:\n  type: object\n  items:\n    password:\n      tag: '!Secret'",
+        );
+        let schema = Schema::try_from(&yaml).unwrap();
+
+        let documents = TaggedDocuments::load_from_str("password: !Secret hunter2").unwrap();
+        let (document, tags) = documents.iter().next().unwrap();
+        let opts = ValidationOptions::default().tags(tags);
+
+        let copy = document.clone();
+        schema
+            .validate_with(&Context::default(), &copy, &opts)
+            .unwrap();
+    }
+}
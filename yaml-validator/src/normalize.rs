@@ -0,0 +1,398 @@
+use crate::errors::ValidationError;
+use crate::types::reference::find_node;
+use crate::{Context, PropertyType, Schema, SchemaInteger, Validate};
+use yaml_rust::Yaml;
+
+/// Finds the default of the node a property refers to, when the property has none of its own.
+///
+/// This is either the `default` of the root of the referenced schema, or of the property
+/// a pointer such as `person#/items/port` leads to. References to references are followed
+/// in turn, and `visited` holds those followed so far, so that loops end without a default.
+fn referenced_default<'schema>(
+    ctx: &'schema Context<'schema>,
+    node: &PropertyType<'schema>,
+    visited: &mut Vec<usize>,
+) -> Option<&'schema Yaml> {
+    match node {
+        PropertyType::Nullable(inner) => referenced_default(ctx, inner, visited),
+        PropertyType::Composite {
+            base: Some(base), ..
+        } => referenced_default(ctx, base, visited),
+        PropertyType::Reference(reference) => {
+            let address = reference as *const _ as usize;
+            if visited.contains(&address) {
+                return None;
+            }
+            visited.push(address);
+
+            let schema = reference.resolve(ctx).ok()?;
+
+            let (default, target) = match reference.pointer {
+                None => (schema.default, &schema.schema),
+                Some(pointer) => {
+                    let segments: Vec<&str> =
+                        pointer.split('/').filter(|s| !s.is_empty()).collect();
+
+                    // The default of a property is kept by the object it is part of.
+                    let default = match segments.as_slice() {
+                        [parent @ .., "items", name] => {
+                            match find_node(&schema.schema, &parent.join("/")) {
+                                Some(PropertyType::Object(object)) => {
+                                    object.defaults.get(name).copied()
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    (default, find_node(&schema.schema, pointer)?)
+                }
+            };
+
+            default.or_else(|| referenced_default(ctx, target, visited))
+        }
+        _ => None,
+    }
+}
+
+/// Converts scalars written as strings into the type expected by the schema, and
+/// fills in the defaults of missing object properties.
+///
+/// `following` holds the nodes each reference currently being followed resolved to, and the
+/// value it is normalizing, by address, so that references which loop back to themselves
+/// without descending into the value are only followed once.
+fn normalize<'schema>(
+    ctx: &'schema Context<'schema>,
+    node: &PropertyType<'schema>,
+    yaml: &mut Yaml,
+    following: &mut Vec<(usize, usize)>,
+) {
+    match (node, &mut *yaml) {
+        (PropertyType::Nullable(_), Yaml::Null) => {}
+        (PropertyType::Nullable(inner), _) => normalize(ctx, inner, yaml, following),
+        (
+            PropertyType::Composite {
+                base: Some(base), ..
+            },
+            _,
+        ) => normalize(ctx, base, yaml, following),
+        (PropertyType::Reference(reference), _) => {
+            if let Ok(node) = reference.resolve_node(ctx) {
+                let pair = (
+                    node as *const PropertyType as usize,
+                    yaml as *const Yaml as usize,
+                );
+
+                if !following.contains(&pair) {
+                    following.push(pair);
+                    normalize(ctx, node, yaml, following);
+                    following.pop();
+                }
+            }
+        }
+        (PropertyType::Integer(_), Yaml::String(value)) => {
+            if let Ok(integer) = value.parse::<i64>() {
+                *yaml = Yaml::Integer(integer);
+            }
+        }
+        (PropertyType::Integer(integer), Yaml::Real(_)) if integer.coerce => {
+            if let Some(value) = SchemaInteger::coerce_real(yaml).and_then(Result::ok) {
+                *yaml = Yaml::Integer(value);
+            }
+        }
+        (PropertyType::Real(_), Yaml::String(value)) if value.parse::<f64>().is_ok() => {
+            *yaml = Yaml::Real(value.clone());
+        }
+        (PropertyType::String(string), Yaml::String(value)) => {
+            if let Some(canonical) = string.canonical(value) {
+                *yaml = Yaml::String(canonical.to_owned());
+            }
+        }
+        (PropertyType::Bool(_), Yaml::String(value)) => match value.as_str() {
+            "true" => *yaml = Yaml::Boolean(true),
+            "false" => *yaml = Yaml::Boolean(false),
+            _ => {}
+        },
+        (PropertyType::Object(object), Yaml::Hash(hash)) => {
+            for (name, item) in &object.items {
+                // A value given under an alias takes the place of the default.
+                let key = object.property(hash, name).map_or(*name, |(key, _)| key);
+                let key = Yaml::String(key.to_string());
+
+                match hash.get_mut(&key) {
+                    Some(value) => normalize(ctx, item, value, following),
+                    None => {
+                        let default = object
+                            .defaults
+                            .get(name)
+                            .copied()
+                            .or_else(|| referenced_default(ctx, item, &mut Vec::new()));
+
+                        if let Some(default) = default {
+                            hash.insert(key, default.clone());
+                        }
+                    }
+                }
+            }
+        }
+        (PropertyType::Array(array), Yaml::Array(values)) => {
+            for (item, value) in array.prefix_items.iter().zip(values.iter_mut()) {
+                normalize(ctx, item, value, following);
+            }
+
+            if let Some(items) = &array.items {
+                for value in values.iter_mut().skip(array.prefix_items.len()) {
+                    normalize(ctx, items, value, following);
+                }
+            }
+        }
+        (PropertyType::Hash(hash), Yaml::Hash(values)) => {
+            if let Some(items) = &hash.items {
+                for (_, value) in values.iter_mut() {
+                    normalize(ctx, items, value, following);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Normalize a document in place and validate the result, making it suitable for
+    /// deserializing into types which expect the values described by the schema.
+    ///
+    /// Strings holding integers, reals or booleans are converted wherever the schema
+    /// expects one of those types, and missing object properties are set to the value
+    /// given by their `default` keyword, if any. Strings listed in an `enumMap` are
+    /// replaced by the canonical value they map to. Properties referring to another schema
+    /// without a default of their own take the default of the node they refer to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::{Yaml, YamlLoader};
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: server
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             port:
+    ///                 type: integer
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    /// let mut document = YamlLoader::load_from_str("port: '8080'").unwrap().remove(0);
+    ///
+    /// context.get_schema("server").unwrap()
+    ///     .normalize(&context, &mut document).unwrap();
+    ///
+    /// assert_eq!(document["port"], Yaml::Integer(8080));
+    /// ```
+    pub fn normalize<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml mut Yaml,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        normalize(ctx, &self.schema, yaml, &mut Vec::new());
+        self.validate(ctx, yaml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ValidationErrorKind;
+    use crate::utils::load_simple;
+    use crate::Context;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn normalize_document() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: server
+schema:
+  type: object
+  items:
+    port:
+      type: integer
+    secure:
+      type: boolean
+    protocol:
+      type: string
+      default: http
+    routes:
+      type: array
+      items:
+        type: object
+        items:
+          weight:
+            type: real
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = ctx.get_schema("server").unwrap();
+
+        let mut document = load_simple(
+            r#"
+            port: "8080"
+            secure: "false"
+            routes:
+              - weight: "0.5"
+            "#,
+        );
+
+        schema.normalize(&ctx, &mut document).unwrap();
+
+        assert_eq!(
+            document,
+            load_simple(
+                r#"
+                port: 8080
+                secure: false
+                routes:
+                  - weight: 0.5
+                protocol: http
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn normalize_invalid() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: server
+schema:
+  type: object
+  items:
+    port:
+      type: integer
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let mut document = load_simple("port: eighty");
+
+        assert_eq!(
+            ctx.get_schema("server")
+                .unwrap()
+                .normalize(&ctx, &mut document)
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path_name("port")
+        );
+
+        assert_eq!(document, load_simple("port: eighty"));
+    }
+
+    #[test]
+    fn normalize_referenced_defaults() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+---
+uri: port
+schema:
+  type: integer
+  default: 8080
+---
+uri: tls
+schema:
+  type: object
+  items:
+    enabled:
+      type: boolean
+      default: false
+---
+uri: loop
+schema:
+  $ref: loop
+---
+uri: server
+schema:
+  type: object
+  items:
+    port:
+      $ref: port
+    secure:
+      $ref: tls#/items/enabled
+    admin:
+      $ref: port
+      default: 9090
+    other:
+      $ref: loop
+    missing:
+      $ref: loop
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let mut document = load_simple("other: 1");
+
+        // The loop is reported by validation, once defaults have been filled in.
+        assert!(ctx
+            .get_schema("server")
+            .unwrap()
+            .normalize(&ctx, &mut document)
+            .is_err());
+
+        assert_eq!(
+            document,
+            load_simple(
+                r#"
+                other: 1
+                admin: 9090
+                port: 8080
+                secure: false
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn normalize_enum_map() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: settings
+schema:
+  type: object
+  items:
+    enabled:
+      type: string
+      enumMap:
+        "yes": "true"
+        y: "true"
+        "true": "true"
+        "no": "false"
+        n: "false"
+        "false": "false"
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = ctx.get_schema("settings").unwrap();
+
+        let mut document = load_simple("enabled: 'y'");
+        schema.normalize(&ctx, &mut document).unwrap();
+        assert_eq!(document, load_simple("enabled: 'true'"));
+
+        let mut document = load_simple("enabled: maybe");
+        assert!(schema.normalize(&ctx, &mut document).is_err());
+        assert_eq!(document, load_simple("enabled: maybe"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::errors::{ValidationError, ValidationErrorKind};
+use crate::{Context, PropertyType, Validate};
+use self_cell::self_cell;
+use std::convert::TryFrom;
+use thiserror::Error;
+use yaml_rust::{ScanError, Yaml, YamlLoader};
+
+/// Errors encountered while constructing an [OwnedSchema].
+#[derive(Error, Debug)]
+pub enum OwnedSchemaError {
+    #[error("failed to parse yaml: {0}")]
+    Yaml(#[from] ScanError),
+    #[error("source contained no schema")]
+    Empty,
+    #[error("invalid schema:\n{0}")]
+    Schema(String),
+}
+
+self_cell!(
+    /// The yaml of a schema, along with the schema parsed from it.
+    struct ParsedSchema {
+        owner: Yaml,
+
+        #[covariant]
+        dependent: PropertyType,
+    }
+
+    impl {Debug}
+);
+
+/// A standalone schema which can be validated against without a [Context].
+///
+/// The schema is parsed once upon construction, and kept alongside the yaml it borrows from.
+/// Schemas referencing other schemas using `$ref` must be validated through a [Context] instead.
+#[derive(Debug)]
+pub struct OwnedSchema {
+    schema: ParsedSchema,
+    context: Context<'static>,
+}
+
+impl OwnedSchema {
+    /// Create a schema from yaml source, containing the schema itself without a `uri`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use yaml_validator::OwnedSchema;
+    /// #
+    /// let schema = OwnedSchema::from_str("type: integer").unwrap();
+    /// let document = YamlLoader::load_from_str("10").unwrap().remove(0);
+    ///
+    /// schema.validate(&document).unwrap();
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(source: &str) -> Result<Self, OwnedSchemaError> {
+        let yaml = YamlLoader::load_from_str(source)?
+            .into_iter()
+            .next()
+            .ok_or(OwnedSchemaError::Empty)?;
+
+        let schema = ParsedSchema::try_new(yaml, |yaml| {
+            PropertyType::try_from(yaml).map_err(|err| OwnedSchemaError::Schema(format!("{}", err)))
+        })?;
+
+        Ok(OwnedSchema {
+            schema,
+            context: Context::default(),
+        })
+    }
+
+    /// Validate a single document against the schema.
+    pub fn validate<'a>(&'a self, yaml: &'a Yaml) -> Result<(), ValidationError<'a>> {
+        self.schema
+            .borrow_dependent()
+            .validate(&self.context, yaml)
+            .map_err(require_context)
+    }
+}
+
+/// Replaces failed lookups of referenced schemas with an error explaining why they failed.
+fn require_context(mut err: ValidationError) -> ValidationError {
+    err.kind = match err.kind {
+        ValidationErrorKind::UnknownSchema { uri } => ValidationErrorKind::ContextRequired { uri },
+        ValidationErrorKind::Multiple { errors } => ValidationErrorKind::Multiple {
+            errors: errors.into_iter().map(require_context).collect(),
+        },
+        kind => kind,
+    };
+
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::MessageId;
+    use crate::utils::load_simple;
+
+    #[test]
+    fn validate_integer() {
+        let schema = OwnedSchema::from_str("type: integer\nminimum: 10").unwrap();
+
+        schema.validate(&load_simple("20")).unwrap();
+
+        assert_eq!(
+            schema.validate(&load_simple("5")).unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::LowerLimit,
+                args: vec![("value", "5".into()), ("limit", "10".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn invalid_schema() {
+        assert_eq!(
+            format!("{}", OwnedSchema::from_str("type: rational").unwrap_err()),
+            "invalid schema:\n#: unknown type specified: rational\n"
+        );
+    }
+
+    #[test]
+    fn validate_reference() {
+        let schema = OwnedSchema::from_str(
+            r#"
+            type: array
+            items:
+              $ref: person
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.validate(&load_simple("- John")).unwrap_err(),
+            ValidationErrorKind::ContextRequired { uri: "person" }.into()
+        );
+    }
+}
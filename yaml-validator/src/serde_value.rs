@@ -0,0 +1,176 @@
+use crate::Context;
+use crate::OwnedValidationError;
+use serde_yaml::Value;
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+/// Convert a value loaded by serde_yaml into the yaml_rust representation used for validation.
+///
+/// Values map onto their yaml_rust counterparts as follows:
+///
+/// * `Null`, `Bool`, `String`, `Sequence` and `Mapping` become [Null](Yaml::Null),
+///   [Boolean](Yaml::Boolean), [String](Yaml::String), [Array](Yaml::Array) and [Hash](Yaml::Hash).
+/// * Numbers which fit in an `i64` become [Integer](Yaml::Integer), and every other number,
+///   including integers above `i64::MAX`, becomes a [Real](Yaml::Real), as yaml_rust would load them.
+///
+/// The conversion is lossy in two ways:
+///
+/// * Tagged values, such as `!Secret hunter2`, are replaced by the value without its tag, so
+///   schemas using the `tag` keyword cannot be checked. Load the source with
+///   [TaggedDocuments](crate::TaggedDocuments) instead.
+/// * Integers above `i64::MAX` lose precision once they are read back as a real.
+///
+/// # Examples
+///
+/// ```rust
+/// # use yaml_validator::{from_serde_yaml, yaml_rust::Yaml};
+/// #
+/// let value: serde_yaml::Value = serde_yaml::from_str("[1, 2.5, !Secret hunter2]").unwrap();
+///
+/// assert_eq!(
+///     from_serde_yaml(&value),
+///     Yaml::Array(vec![
+///         Yaml::Integer(1),
+///         Yaml::Real("2.5".into()),
+///         Yaml::String("hunter2".into()),
+///     ])
+/// );
+/// ```
+pub fn from_serde_yaml(value: &Value) -> Yaml {
+    match value {
+        Value::Null => Yaml::Null,
+        Value::Bool(value) => Yaml::Boolean(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Yaml::Integer(integer),
+            None => Yaml::Real(real(number.as_f64().unwrap_or(f64::NAN))),
+        },
+        Value::String(value) => Yaml::String(value.clone()),
+        Value::Sequence(items) => Yaml::Array(items.iter().map(from_serde_yaml).collect()),
+        Value::Mapping(mapping) => Yaml::Hash(
+            mapping
+                .iter()
+                .map(|(key, value)| (from_serde_yaml(key), from_serde_yaml(value)))
+                .collect::<Hash>(),
+        ),
+        Value::Tagged(tagged) => from_serde_yaml(&tagged.value),
+    }
+}
+
+/// Writes a real the way it would appear in a yaml document, so that yaml_rust reads it back.
+fn real(value: f64) -> String {
+    if value.is_nan() {
+        ".nan".into()
+    } else if value.is_infinite() {
+        if value > 0.0 { ".inf" } else { "-.inf" }.into()
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+impl<'schema> Context<'schema> {
+    /// Validate a value loaded by serde_yaml against the schema with the given uri, after
+    /// converting it with [from_serde_yaml]. The converted value only lives as long as the
+    /// call, so the error does not borrow from it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::Context;
+    /// #
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: port
+    ///     schema:
+    ///         type: integer
+    ///         maximum: 65535
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    ///
+    /// context.validate_serde_yaml("port", &serde_yaml::from_str("8080").unwrap()).unwrap();
+    /// assert!(context.validate_serde_yaml("port", &serde_yaml::from_str("70000").unwrap()).is_err());
+    /// ```
+    pub fn validate_serde_yaml(
+        &'schema self,
+        uri: &str,
+        value: &Value,
+    ) -> Result<(), OwnedValidationError> {
+        let yaml = from_serde_yaml(value);
+
+        self.validate(uri, &yaml)
+            .map_err(OwnedValidationError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn convert_values() {
+        let source = r#"
+name: John
+age: 30
+height: 1.85
+admin: false
+manager: ~
+tags: [a, !label b]
+"#;
+
+        let value: Value = serde_yaml::from_str(source).unwrap();
+        let expected = YamlLoader::load_from_str(&source.replace("!label ", ""))
+            .unwrap()
+            .remove(0);
+
+        assert_eq!(from_serde_yaml(&value), expected);
+
+        assert_eq!(real(f64::NEG_INFINITY), "-.inf");
+        assert_eq!(
+            from_serde_yaml(&serde_yaml::from_str("18446744073709551615").unwrap()).as_f64(),
+            Some(18446744073709551615.0)
+        );
+    }
+
+    #[test]
+    fn validate_serde_mapping() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+  required: [name]
+"#,
+        )
+        .unwrap();
+
+        let context = Context::try_from(&schemas[..]).unwrap();
+
+        let mut person = serde_yaml::Mapping::new();
+        person.insert("name".into(), "John".into());
+        person.insert("age".into(), 30.into());
+        let person = Value::Mapping(person);
+
+        context.validate_serde_yaml("person", &person).unwrap();
+
+        // The value round-trips through serde_yaml's own serialization too.
+        let reloaded: Value =
+            serde_yaml::from_str(&serde_yaml::to_string(&person).unwrap()).unwrap();
+        context.validate_serde_yaml("person", &reloaded).unwrap();
+
+        let invalid: Value = serde_yaml::from_str("age: thirty").unwrap();
+        let err = context.validate_serde_yaml("person", &invalid).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "#: field 'name' missing\n#.age: wrong type, expected integer got string\n"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::LoadError;
+use std::collections::HashMap;
+use std::io::Read;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, ScanError, TScalarStyle, TokenType};
+use yaml_rust::{Yaml, YamlLoader};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Index(usize),
+    Key(Yaml),
+}
+
+#[derive(Debug)]
+enum Container {
+    Sequence { next: usize },
+    // The key whose value comes next, or None while waiting for a key.
+    Mapping { key: Option<Option<Yaml>> },
+}
+
+/// Records the tag of every scalar in the stream, along with the steps leading to it
+/// from the root of its document. Scalars which cannot be reached by walking the loaded
+/// documents, such as those within keys, are left out.
+#[derive(Default)]
+struct TagReceiver {
+    document: usize,
+    stack: Vec<(Container, Option<Vec<Step>>)>,
+    tags: Vec<(usize, Vec<Step>, String)>,
+}
+
+impl TagReceiver {
+    /// Works out the path of the next node within the innermost container, given
+    /// the key it would represent if it happens to be a key itself.
+    fn place(&mut self, key: Option<Yaml>) -> Option<Vec<Step>> {
+        let (container, path) = match self.stack.last_mut() {
+            Some(top) => top,
+            None => return Some(Vec::new()),
+        };
+
+        let step = match container {
+            Container::Sequence { next } => {
+                *next += 1;
+                Some(Step::Index(*next - 1))
+            }
+            Container::Mapping { key: pending } => match pending.take() {
+                Some(Some(key)) => Some(Step::Key(key)),
+                Some(None) => None,
+                None => {
+                    *pending = Some(key);
+                    return None;
+                }
+            },
+        };
+
+        let mut path = path.clone()?;
+        path.push(step?);
+        Some(path)
+    }
+}
+
+/// The key a scalar represents, following the same rules as [YamlLoader].
+fn scalar_key(value: &str, style: TScalarStyle, tag: &Option<TokenType>) -> Option<Yaml> {
+    match tag {
+        _ if style != TScalarStyle::Plain => Some(Yaml::String(value.to_string())),
+        Some(TokenType::Tag(handle, _)) if handle == "!!" => None,
+        Some(_) => Some(Yaml::String(value.to_string())),
+        None => Some(Yaml::from_str(value)),
+    }
+}
+
+impl MarkedEventReceiver for TagReceiver {
+    fn on_event(&mut self, event: Event, _: Marker) {
+        match event {
+            Event::DocumentEnd => self.document += 1,
+            Event::SequenceStart(_) => {
+                let path = self.place(None);
+                self.stack.push((Container::Sequence { next: 0 }, path));
+            }
+            Event::MappingStart(_) => {
+                let path = self.place(None);
+                self.stack.push((Container::Mapping { key: None }, path));
+            }
+            Event::SequenceEnd | Event::MappingEnd => {
+                self.stack.pop();
+            }
+            Event::Scalar(value, style, _, tag) => {
+                let path = self.place(scalar_key(&value, style, &tag));
+
+                if let (Some(path), Some(TokenType::Tag(handle, suffix))) = (path, tag) {
+                    self.tags
+                        .push((self.document, path, format!("{}{}", handle, suffix)));
+                }
+            }
+            Event::Alias(_) => {
+                self.place(None);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Documents loaded along with the tags of their scalar values, such as `!Secret` or `!!str`,
+/// which [YamlLoader] otherwise discards. Tags are only checked by the `tag` keyword when
+/// validating one of the documents with its [DocumentTags], see [ValidationOptions::tags](crate::ValidationOptions::tags).
+///
+/// Only scalars carry tags, since yaml_rust does not report the tags of sequences or
+/// mappings. Values repeated through an alias, and scalars used as keys, are untagged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use yaml_rust::YamlLoader;
+/// # use std::convert::TryFrom;
+/// # use yaml_validator::{Context, TaggedDocuments, Validate, ValidationOptions};
+/// #
+/// let schemas = YamlLoader::load_from_str(r#"
+///     uri: password
+///     schema:
+///         type: string
+///         tag: "!Secret"
+/// "#).unwrap();
+///
+/// let context = Context::try_from(&schemas[..]).unwrap();
+/// let schema = context.get_schema("password").unwrap();
+///
+/// let documents = TaggedDocuments::load_from_str("!Secret hunter2").unwrap();
+/// let (document, tags) = documents.iter().next().unwrap();
+/// let opts = ValidationOptions::default().tags(tags);
+///
+/// schema.validate_with(&context, document, &opts).unwrap();
+/// assert!(schema.validate(&context, document).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedDocuments {
+    documents: Vec<Yaml>,
+    tags: Vec<DocumentTags>,
+}
+
+/// The tags of a single document, by the path of each tagged node from the root of the document.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocumentTags {
+    tags: Vec<(Vec<Step>, String)>,
+}
+
+impl TaggedDocuments {
+    /// Load every document in the source, as [YamlLoader::load_from_str] does.
+    pub fn load_from_str(source: &str) -> Result<Self, ScanError> {
+        let documents = YamlLoader::load_from_str(source)?;
+
+        let mut receiver = TagReceiver::default();
+        Parser::new(source.chars()).load(&mut receiver, true)?;
+
+        let mut tags = vec![DocumentTags::default(); documents.len()];
+        for (document, path, tag) in receiver.tags {
+            if let Some(document) = tags.get_mut(document) {
+                document.tags.push((path, tag));
+            }
+        }
+
+        Ok(TaggedDocuments { documents, tags })
+    }
+
+    /// Load every document from a reader, as [load_from_reader](crate::load_from_reader) does.
+    pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Self, LoadError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+
+        Ok(TaggedDocuments::load_from_str(&source)?)
+    }
+
+    /// The loaded documents, in order.
+    pub fn documents(&self) -> &[Yaml] {
+        &self.documents
+    }
+
+    /// Each of the loaded documents in order, along with its tags.
+    pub fn iter(&self) -> impl Iterator<Item = (&Yaml, &DocumentTags)> {
+        self.documents.iter().zip(&self.tags)
+    }
+}
+
+impl DocumentTags {
+    /// The tag of a node within the document, given the root of the document, if it had one.
+    pub fn tag<'a>(&'a self, root: &Yaml, node: &Yaml) -> Option<&'a str> {
+        self.resolve(root).remove(&(node as *const Yaml as usize))
+    }
+
+    /// Finds each tagged node within the document, by its address. The addresses are only
+    /// meaningful for as long as the document is borrowed, since it may be moved afterwards.
+    pub(crate) fn resolve<'a>(&'a self, root: &Yaml) -> HashMap<usize, &'a str> {
+        self.tags
+            .iter()
+            .filter_map(|(path, tag)| {
+                let node = path.iter().try_fold(root, |node, step| match step {
+                    Step::Index(i) => node.as_vec()?.get(*i),
+                    Step::Key(key) => node.as_hash()?.get(key),
+                })?;
+
+                Some((node as *const Yaml as usize, tag.as_str()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_tags() {
+        let documents = TaggedDocuments::load_from_str(
+            r#"
+password: !Secret hunter2
+names:
+  - !!str 200
+  - bob
+---
+!Secret root
+"#,
+        )
+        .unwrap();
+
+        let mut iter = documents.iter();
+        let (first, tags) = iter.next().unwrap();
+        assert_eq!(tags.tag(first, &first["password"]), Some("!Secret"));
+        assert_eq!(tags.tag(first, &first["names"][0]), Some("!!str"));
+        assert_eq!(tags.tag(first, &first["names"][1]), None);
+
+        // Tags follow the path of the node, rather than where it was loaded.
+        let moved = first.clone();
+        assert_eq!(tags.tag(&moved, &moved["password"]), Some("!Secret"));
+
+        let (second, tags) = iter.next().unwrap();
+        assert_eq!(tags.tag(second, second), Some("!Secret"));
+    }
+}
@@ -1,52 +1,91 @@
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
 use crate::errors::{SchemaError, SchemaErrorKind};
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{try_into_usize, CondenseErrors, OptionalLookup, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use crate::modifiers::SchemaAnyOf;
+use crate::utils::{lookup_usize, type_keywords, CondenseErrors, OptionalLookup, YamlUtils};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "ascending",
+            SortOrder::Descending => "descending",
+        }
+    }
+
+    fn violation(self) -> MessageId {
+        match self {
+            SortOrder::Ascending => MessageId::NotAscending,
+            SortOrder::Descending => MessageId::NotDescending,
+        }
+    }
+
+    fn allows(self, ordering: Ordering) -> bool {
+        match self {
+            SortOrder::Ascending => ordering != Ordering::Greater,
+            SortOrder::Descending => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// Compares two scalars of the same kind, treating integers and reals alike.
+fn compare_scalars(a: &Yaml, b: &Yaml) -> Option<Ordering> {
+    match (a, b) {
+        (Yaml::Integer(a), Yaml::Integer(b)) => Some(a.cmp(b)),
+        (Yaml::String(a), Yaml::String(b)) => Some(a.cmp(b)),
+        (Yaml::Boolean(a), Yaml::Boolean(b)) => Some(a.cmp(b)),
+        (Yaml::Integer(_), Yaml::Real(_))
+        | (Yaml::Real(_), Yaml::Integer(_))
+        | (Yaml::Real(_), Yaml::Real(_)) => {
+            let to_f64 = |yaml: &Yaml| yaml.as_f64().or_else(|| yaml.as_i64().map(|i| i as f64));
+            to_f64(a)?.partial_cmp(&to_f64(b)?)
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct SchemaArray<'schema> {
-    items: Option<Box<PropertyType<'schema>>>,
-    min_items: Option<usize>,
-    max_items: Option<usize>,
-    unique_items: bool,
-    contains: Option<Box<PropertyType<'schema>>>,
-    min_contains: Option<usize>,
-    max_contains: Option<usize>,
+    pub(crate) items: Option<Box<PropertyType<'schema>>>,
+    // Schemas for the leading elements of the array, with `items` applying to the rest.
+    pub(crate) prefix_items: Vec<PropertyType<'schema>>,
+    pub(crate) min_items: Option<usize>,
+    pub(crate) max_items: Option<usize>,
+    pub(crate) unique_items: bool,
+    // Bounds on the number of distinct items, which may otherwise be repeated.
+    pub(crate) min_unique_items: Option<usize>,
+    pub(crate) max_unique_items: Option<usize>,
+    pub(crate) contains: Option<Box<PropertyType<'schema>>>,
+    pub(crate) min_contains: Option<usize>,
+    pub(crate) max_contains: Option<usize>,
+    pub(crate) sorted: Option<SortOrder>,
+    // Elements checked against `items` stop being validated once this many have failed.
+    max_item_errors: Option<usize>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaArray<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(
-            &[],
-            &[
-                "type",
-                "items",
-                "maxItems",
-                "minItems",
-                "uniqueItems",
-                "contains",
-                "minContains",
-                "maxContains",
-            ],
-        )?;
-
-        let min_items = yaml
-            .lookup("minItems", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("minItems"))
-            .into_optional()?;
+        SchemaArray::parse(yaml, &Context::default())
+    }
+}
 
-        let max_items = yaml
-            .lookup("maxItems", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("maxItems"))
-            .into_optional()?;
+impl<'schema> SchemaArray<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_schema_contents(&[], &type_keywords("array"))?;
+
+        let min_items = lookup_usize(yaml, "minItems")?;
+
+        let max_items = lookup_usize(yaml, "maxItems")?;
 
         let unique_items = yaml
             .lookup("uniqueItems", "bool", Yaml::as_bool)
@@ -64,39 +103,128 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaArray<'schema> {
             }
         }
 
+        let min_unique_items = lookup_usize(yaml, "minUniqueItems")?;
+
+        let max_unique_items = lookup_usize(yaml, "maxUniqueItems")?;
+
+        if let (Some(min_unique_items), Some(max_unique_items)) =
+            (min_unique_items, max_unique_items)
+        {
+            if min_unique_items > max_unique_items {
+                return Err(SchemaErrorKind::MalformedField {
+                    error: "minUniqueItems cannot be greater than maxUniqueItems".into(),
+                }
+                .into());
+            }
+        }
+
+        let sorted = yaml
+            .lookup("sorted", "string", Yaml::as_str)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("sorted"))
+            .into_optional()?
+            .map(|order| match order {
+                "asc" => Ok(SortOrder::Ascending),
+                "desc" => Ok(SortOrder::Descending),
+                _ => Err(SchemaError::from(SchemaErrorKind::MalformedField {
+                    error: format!("sorted must be either 'asc' or 'desc', not '{}'", order),
+                })),
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("sorted"))?;
+
         let items = yaml
             .lookup("items", "yaml", Option::from)
             .map_err(SchemaError::from)
             .map_err(SchemaError::add_path_name("items"))
             .into_optional()?
-            .map(PropertyType::try_from)
+            .map(|inner| PropertyType::parse(inner, ctx))
             .transpose()
-            .map_err(SchemaError::add_path_name("items"))?
-            .map(Box::new);
+            .map_err(SchemaError::add_path_name("items"))?;
+
+        // A list of schemas, any of which each item may match. Tuples, where each position
+        // has a schema of its own, are described by prefixItems instead.
+        let items_any_of = yaml
+            .lookup("itemsAnyOf", "array", Yaml::as_vec)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|branches| {
+                if branches.is_empty() {
+                    return Err(SchemaErrorKind::MalformedField {
+                        error: "itemsAnyOf must contain at least one schema".into(),
+                    }
+                    .into());
+                }
+
+                let items = SchemaError::condense_errors(&mut branches.iter().enumerate().map(
+                    |(i, inner)| {
+                        PropertyType::parse(inner, ctx).map_err(SchemaError::add_path_index(i))
+                    },
+                ))?;
+
+                Ok(PropertyType::AnyOf(SchemaAnyOf { items }))
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("itemsAnyOf"))?;
+
+        yaml.check_exclusive_fields(&["items", "itemsAnyOf"])?;
+
+        let items_keyword = if items_any_of.is_some() {
+            "itemsAnyOf"
+        } else {
+            "items"
+        };
+        let items = items.or(items_any_of).map(Box::new);
+
+        let prefix_items = yaml
+            .lookup("prefixItems", "array", Yaml::as_vec)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|prefix| {
+                SchemaError::condense_errors(&mut prefix.iter().enumerate().map(|(i, inner)| {
+                    PropertyType::parse(inner, ctx).map_err(SchemaError::add_path_index(i))
+                }))
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("prefixItems"))?
+            .unwrap_or_default();
+
+        if let (Some(_), Some(max_items)) = (&items, max_items) {
+            if !prefix_items.is_empty() && max_items <= prefix_items.len() {
+                return Err(SchemaErrorKind::MalformedField {
+                    error: format!(
+                        "{} only applies to elements after the {} prefixItems, but maxItems of {} leaves no room for any",
+                        items_keyword,
+                        prefix_items.len(),
+                        max_items
+                    ),
+                }
+                .with_path_name(items_keyword));
+            }
+        }
+
+        let max_item_errors = lookup_usize(yaml, "maxItemErrors")?;
+
+        if max_item_errors == Some(0) {
+            return Err(SchemaErrorKind::MalformedField {
+                error: "maxItemErrors must be at least 1".into(),
+            }
+            .with_path_name("maxItemErrors"));
+        }
 
         let contains = yaml
             .lookup("contains", "yaml", Option::from)
             .map_err(SchemaError::from)
             .map_err(SchemaError::add_path_name("contains"))
             .into_optional()?
-            .map(PropertyType::try_from)
+            .map(|inner| PropertyType::parse(inner, ctx))
             .transpose()
             .map_err(SchemaError::add_path_name("contains"))?
             .map(Box::new);
 
-        let min_contains = yaml
-            .lookup("minContains", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("minContains"))
-            .into_optional()?;
+        let min_contains = lookup_usize(yaml, "minContains")?;
 
-        let max_contains = yaml
-            .lookup("maxContains", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("maxContains"))
-            .into_optional()?;
+        let max_contains = lookup_usize(yaml, "maxContains")?;
 
         // This does not seem like the nicest way to do this...
         match (&contains, &min_contains, &max_contains) {
@@ -123,28 +251,27 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaArray<'schema> {
 
         Ok(SchemaArray {
             items,
+            prefix_items,
             min_items,
             max_items,
             unique_items,
+            min_unique_items,
+            max_unique_items,
             contains,
             min_contains,
             max_contains,
+            sorted,
+            max_item_errors,
         })
     }
-}
-
-impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaArray<'schema> {
-    fn validate(
-        &self,
-        ctx: &'schema Context<'schema>,
-        yaml: &'yaml Yaml,
-    ) -> Result<(), ValidationError<'yaml>> {
-        let items = yaml.as_type("array", Yaml::as_vec)?;
 
+    /// Checks the length, uniqueness and order of the items, which do not depend
+    /// on whether the items validate against any schema.
+    fn validate_shape<'yaml>(&self, items: &'yaml [Yaml]) -> Result<(), ValidationError<'yaml>> {
         if let Some(min_items) = &self.min_items {
             if items.len() < *min_items {
                 return Err(ValidationErrorKind::ValidationError {
-                    error: "array contains fewer than minItems items",
+                    error: MessageId::MinItems,
                 }
                 .into());
             }
@@ -153,70 +280,201 @@ impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaArray<'schema> {
         if let Some(max_items) = &self.max_items {
             if items.len() > *max_items {
                 return Err(ValidationErrorKind::ValidationError {
-                    error: "array contains more than maxItems items",
+                    error: MessageId::MaxItems,
                 }
                 .into());
             }
         }
 
         if self.unique_items {
-            let mut set = HashSet::new();
+            // Inserting directly hashes each item once, rather than once to look it up
+            // and once more to insert it, which adds up for large nested items.
+            let mut set = HashSet::with_capacity(items.len());
             for (i, item) in items.iter().enumerate() {
-                if set.contains(item) {
+                if !set.insert(item) {
                     return Err(ValidationErrorKind::ValidationError {
-                        error: "array contains duplicate key",
+                        error: MessageId::DuplicateItem,
                     }
                     .with_path_index(i));
                 }
+            }
+        }
+
+        if self.min_unique_items.is_some() || self.max_unique_items.is_some() {
+            let distinct = items.iter().collect::<HashSet<_>>().len();
+
+            if let Some(min) = self.min_unique_items.filter(|min| distinct < *min) {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::MinUniqueItems,
+                    args: vec![
+                        ("distinct", distinct.to_string()),
+                        ("limit", min.to_string()),
+                    ],
+                }
+                .into());
+            }
 
-                set.insert(item);
+            if let Some(max) = self.max_unique_items.filter(|max| distinct > *max) {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::MaxUniqueItems,
+                    args: vec![
+                        ("distinct", distinct.to_string()),
+                        ("limit", max.to_string()),
+                    ],
+                }
+                .into());
             }
         }
 
-        if let Some(contains) = &self.contains {
-            let contained = items
-                .iter()
-                .enumerate()
-                .map(|(i, item)| {
-                    contains
-                        .validate(ctx, item)
-                        .map_err(ValidationError::add_path_index(i))
-                })
-                .filter(Result::is_ok)
-                .count();
+        if let Some(order) = self.sorted {
+            for (i, pair) in items.windows(2).enumerate() {
+                let ordering = compare_scalars(&pair[0], &pair[1]).ok_or_else(|| {
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::IncomparableItems,
+                        args: vec![
+                            ("left", pair[0].type_to_str().to_string()),
+                            ("right", pair[1].type_to_str().to_string()),
+                        ],
+                    }
+                    .with_path_index(i + 1)
+                })?;
 
-            if let Some(min) = self.min_contains {
-                if contained < min {
+                if !order.allows(ordering) {
                     return Err(ValidationErrorKind::ValidationError {
-                        error:
-                            "fewer than minContains items validated against schema in 'contains'",
+                        error: order.violation(),
                     }
-                    .into());
+                    .with_path_index(i + 1));
                 }
-            } else if contained < 1 {
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_contains<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        items: &'yaml [Yaml],
+        opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        if let Some(contains) = &self.contains {
+            let (matching, mismatched): (Vec<usize>, Vec<usize>) =
+                (0..items.len()).partition(|i| contains.matches(ctx, &items[*i], opts));
+
+            let contained = matching.len();
+
+            // Without minContains, at least one item must match.
+            let min = self.min_contains.unwrap_or(1);
+            if contained >= min && self.max_contains.is_none_or(|max| contained <= max) {
+                return Ok(());
+            }
+
+            if self.min_contains.is_none() && self.max_contains.is_none() {
                 return Err(ValidationErrorKind::ValidationError {
-                    error: "at least one item in the array must match the 'contains' schema",
+                    error: MessageId::ContainsNone,
                 }
                 .into());
             }
 
-            if let Some(max) = self.max_contains {
-                if contained > max {
-                    return Err(ValidationErrorKind::ValidationError {
-                        error: "more than minContains items validated against schema in 'contains'",
+            let (error, mut args) = match self.max_contains {
+                Some(max) if max == min => (MessageId::ContainsExactly, vec![]),
+                Some(max) => (MessageId::ContainsBetween, vec![("max", max.to_string())]),
+                None => (MessageId::ContainsAtLeast, vec![]),
+            };
+
+            // Stating the range in effect, and listing which items did and did not
+            // match, makes it easier to fix the document.
+            args.extend(vec![
+                ("min", min.to_string()),
+                ("found", contained.to_string()),
+                ("matching", format!("{:?}", matching)),
+                ("other", format!("{:?}", mismatched)),
+            ]);
+
+            return Err(ValidationErrorKind::ParameterizedValidation { error, args }.into());
+        }
+
+        Ok(())
+    }
+
+    fn validate_elements<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        items: &'yaml [Yaml],
+        opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        // Arrays shorter than the prefix are allowed, and only have their present elements checked.
+        let mut errors =
+            self.prefix_items
+                .iter()
+                .zip(items.iter())
+                .enumerate()
+                .map(|(i, (schema, item))| {
+                    schema
+                        .validate_with(ctx, item, opts)
+                        .map_err(ValidationError::add_path_index(i))
+                });
+
+        ValidationError::condense_errors(&mut errors)?;
+
+        if let Some(schema) = &self.items {
+            // Resolve a reference once up front, instead of looking it up in the
+            // context again for every single item in the array.
+            let schema: &dyn Validate<'yaml, 'schema> = match schema.as_ref() {
+                PropertyType::Reference(reference) if items.len() > self.prefix_items.len() => {
+                    reference.resolve_node(ctx)?
+                }
+                schema => schema,
+            };
+
+            let mut errors = Vec::new();
+            let mut failed = Vec::new();
+            let mut remaining = items.iter().enumerate().skip(self.prefix_items.len());
+
+            for (i, item) in &mut remaining {
+                if let Err(err) = schema.validate_with(ctx, item, opts) {
+                    errors.push(ValidationError::add_path_index(i)(err));
+                    failed.push(i);
+
+                    if Some(errors.len()) == self.max_item_errors {
+                        break;
                     }
-                    .into());
                 }
             }
-        };
 
-        if let Some(schema) = &self.items {
-            let mut errors = items.iter().enumerate().map(|(i, item)| {
-                schema
-                    .validate(ctx, item)
-                    .map_err(ValidationError::add_path_index(i))
-            });
+            let unchecked = remaining.count();
+            if unchecked > 0 {
+                errors.push(
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::UncheckedItems,
+                        args: vec![
+                            ("invalid", errors.len().to_string()),
+                            ("unchecked", unchecked.to_string()),
+                        ],
+                    }
+                    .into(),
+                );
+            }
+
+            // Errors within the elements are otherwise only told apart from those caused by
+            // 'contains' by their paths, so the elements failing 'items' are named as well.
+            if self.contains.is_some() && !failed.is_empty() {
+                errors.push(
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::ItemsMismatch,
+                        args: vec![("indices", format!("{:?}", failed))],
+                    }
+                    .into(),
+                );
+            }
 
+            let mut errors = errors.into_iter().map(Err::<(), _>);
             ValidationError::condense_errors(&mut errors)?;
         }
 
@@ -224,11 +482,67 @@ impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaArray<'schema> {
     }
 }
 
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaArray<'schema> {
+    fn validate_with(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        let items = yaml.as_type("array", Yaml::as_vec)?;
+
+        // Elements are left unchecked when the length or order is already wrong, unless asked
+        // otherwise, so that a too long array is not reported along with every one of its
+        // invalid elements.
+        let shape = self.validate_shape(items);
+        if shape.is_err() && !opts.report_all_array_errors {
+            return shape;
+        }
+
+        // Both checks run, so that elements failing 'items' are reported alongside a
+        // failing 'contains', which is reported on the array itself rather than an element.
+        let mut errors = std::iter::once(shape)
+            .chain(std::iter::once(self.validate_elements(ctx, items, opts)))
+            .chain(std::iter::once(self.validate_contains(ctx, items, opts)));
+
+        ValidationError::condense_errors(&mut errors)?;
+
+        Ok(())
+    }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        let items = match yaml.as_vec() {
+            Some(items) => items,
+            None => return false,
+        };
+
+        self.validate_shape(items).is_ok()
+            && self
+                .prefix_items
+                .iter()
+                .zip(items.iter())
+                .all(|(schema, item)| schema.matches(ctx, item, opts))
+            && self.items.as_ref().is_none_or(|schema| {
+                items
+                    .iter()
+                    .skip(self.prefix_items.len())
+                    .all(|item| schema.matches(ctx, item, opts))
+            })
+            && self.validate_contains(ctx, items, opts).is_ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::load_simple;
     use crate::SchemaArray;
+    use yaml_rust::YamlLoader;
 
     #[test]
     fn from_yaml() {
@@ -257,6 +571,52 @@ mod tests {
             }
             .with_path_name("items"),
         );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+            itemsAnyOf: []
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "itemsAnyOf must contain at least one schema".into()
+            }
+            .with_path_name("itemsAnyOf"),
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+            itemsAnyOf:
+              - type: string
+              - 10
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "hash",
+                actual: "integer"
+            }
+            .with_path(breadcrumb![1usize, "itemsAnyOf"]),
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+            items:
+              type: string
+            itemsAnyOf:
+              - type: integer
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "conflicting constraints: items, itemsAnyOf cannot be used at the same time"
+                    .into()
+            }
+            .into(),
+        );
     }
 
     #[test]
@@ -326,6 +686,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_misspelled_keyword() {
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+                minItms: 10
+                maxItems: 20
+                extra: field
+            "#
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::Multiple {
+                errors: vec![
+                    SchemaErrorKind::MisspelledField {
+                        field: "minItms",
+                        suggestion: "minItems"
+                    }
+                    .into(),
+                    SchemaErrorKind::ExtraField { field: "extra" }.into(),
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn with_negative_bounds() {
+        assert_eq!(
+            SchemaArray::try_from(&load_simple("maxItems: -1")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "must be a non-negative integer value".into()
+            }
+            .with_path_name("maxItems")
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+                minContains: -5
+                contains:
+                  type: integer
+            "#
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "must be a non-negative integer value".into()
+            }
+            .with_path_name("minContains")
+        );
+    }
+
     #[test]
     fn with_contains() {
         SchemaArray::try_from(&load_simple(
@@ -381,6 +792,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_items_and_contains() {
+        let yaml = load_simple(
+            r#"
+            items:
+              type: integer
+            contains:
+              type: integer
+              minimum: 10
+        "#,
+        );
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        let ctx = Context::default();
+        let document = load_simple("[1, hello, 3]");
+        let err = schema.validate(&ctx, &document).unwrap_err();
+
+        assert_eq!(
+            err,
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::Multiple {
+                        errors: vec![
+                            ValidationErrorKind::WrongType {
+                                expected: "integer",
+                                actual: "string"
+                            }
+                            .with_path_index(1),
+                            ValidationErrorKind::ParameterizedValidation {
+                                error: MessageId::ItemsMismatch,
+                                args: vec![("indices", "[1]".into())]
+                            }
+                            .into()
+                        ]
+                    }
+                    .into(),
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::ContainsNone
+                    }
+                    .into()
+                ]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            format!("{}", err),
+            "#[1]: wrong type, expected integer got string
+#: special requirements for field not met: items at indices [1] do not match the schema in 'items'
+#: special requirements for field not met: at least one item in the array must match the 'contains' schema
+"
+        );
+
+        // Elements failing 'items' are named even when 'contains' is met.
+        assert_eq!(
+            format!(
+                "{}",
+                schema
+                    .validate(&ctx, &load_simple("[1, hello, 30]"))
+                    .unwrap_err()
+            ),
+            "#[1]: wrong type, expected integer got string
+#: special requirements for field not met: items at indices [1] do not match the schema in 'items'
+"
+        );
+    }
+
     #[test]
     fn validate_string() {
         let schema = SchemaArray::default();
@@ -491,20 +969,151 @@ mod tests {
                 )
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "array contains duplicate key"
+                error: MessageId::DuplicateItem
             }
             .with_path_index(3)
         );
     }
 
     #[test]
-    fn validate_narrow_array() {
-        let yaml = load_simple(
-            r#"
-            minItems: 2
-            maxItems: 2
-            items:
-              type: integer
+    fn validate_max_item_errors() {
+        let yaml = load_simple("items:\n  type: integer\nmaxItemErrors: 2");
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        let wrong_type = |i: usize| {
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string",
+            }
+            .with_path_index(i)
+        };
+
+        let items = Yaml::Array(
+            std::iter::once(Yaml::Integer(1))
+                .chain((0..1000).map(|i| Yaml::String(format!("item{}", i))))
+                .collect(),
+        );
+
+        assert_eq!(
+            schema.validate(&ctx, &items).unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    wrong_type(1),
+                    wrong_type(2),
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::UncheckedItems,
+                        args: vec![("invalid", "2".into()), ("unchecked", "998".into())]
+                    }
+                    .into(),
+                ]
+            }
+            .into()
+        );
+
+        // Reaching the limit on the very last item leaves nothing unchecked.
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("[1, a, b]"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![wrong_type(1), wrong_type(2)]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple("maxItemErrors: 0")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "maxItemErrors must be at least 1".into()
+            }
+            .with_path_name("maxItemErrors")
+        );
+    }
+
+    #[test]
+    fn validate_unique_large_array() {
+        let yaml = load_simple("uniqueItems: true");
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        let person = |i: usize| {
+            YamlLoader::load_from_str(&format!(
+                "name: person{}\naddress:\n  street: street{}\n  numbers: [{}, {}]",
+                i,
+                i,
+                i,
+                i + 1
+            ))
+            .unwrap()
+            .remove(0)
+        };
+
+        let mut items: Vec<Yaml> = (0..10_000).map(person).collect();
+        schema
+            .validate(&Context::default(), &Yaml::Array(items.clone()))
+            .unwrap();
+
+        items.push(person(4321));
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &Yaml::Array(items))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::DuplicateItem
+            }
+            .with_path_index(10_000)
+        );
+    }
+
+    #[test]
+    fn validate_unique_item_count() {
+        let yaml = load_simple("minUniqueItems: 3\nmaxUniqueItems: 4");
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("[1, 2, 2, 3, 3, 3]"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[1, 2, 2, 1, 2]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::MinUniqueItems,
+                args: vec![("distinct", "2".into()), ("limit", "3".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[a, b, c, d, e, a]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::MaxUniqueItems,
+                args: vec![("distinct", "5".into()), ("limit", "4".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple("minUniqueItems: 3\nmaxUniqueItems: 2"))
+                .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "minUniqueItems cannot be greater than maxUniqueItems".into()
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_narrow_array() {
+        let yaml = load_simple(
+            r#"
+            minItems: 2
+            maxItems: 2
+            items:
+              type: integer
         "#,
         );
 
@@ -539,12 +1148,73 @@ mod tests {
                 )
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "array contains more than maxItems items"
+                error: MessageId::MaxItems
             }
             .into()
         )
     }
 
+    #[test]
+    fn validate_too_large_typed_array() {
+        let yaml = load_simple(
+            r#"
+            maxItems: 2
+            items:
+              type: integer
+            contains:
+              type: integer
+        "#,
+        );
+
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[a, b, c, d]"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxItems
+            }
+            .into()
+        );
+
+        let opts = ValidationOptions::default().report_all_array_errors(true);
+        let wrong_type = |i| {
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string",
+            }
+            .with_path_index(i)
+        };
+
+        assert_eq!(
+            schema
+                .validate_with(&Context::default(), &load_simple("[a, b, 3]"), &opts)
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::MaxItems
+                    }
+                    .into(),
+                    ValidationErrorKind::Multiple {
+                        errors: vec![
+                            wrong_type(0),
+                            wrong_type(1),
+                            ValidationErrorKind::ParameterizedValidation {
+                                error: MessageId::ItemsMismatch,
+                                args: vec![("indices", "[0, 1]".into())]
+                            }
+                            .into()
+                        ]
+                    }
+                    .into()
+                ]
+            }
+            .into()
+        );
+    }
+
     #[test]
     fn validate_too_small_array() {
         assert_eq!(
@@ -562,7 +1232,7 @@ mod tests {
                 )
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "array contains fewer than minItems items"
+                error: MessageId::MinItems
             }
             .into()
         )
@@ -664,6 +1334,112 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn validate_array_contains_violation() {
+        let yaml = load_simple(
+            r#"
+            minContains: 2
+            maxContains: 3
+            contains:
+              type: integer
+        "#,
+        );
+
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[a, 1, b]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ContainsBetween,
+                args: vec![
+                    ("max", "3".into()),
+                    ("min", "2".into()),
+                    ("found", "1".into()),
+                    ("matching", "[1]".into()),
+                    ("other", "[0, 2]".into())
+                ]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[1, 2, a, 3, 4]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ContainsBetween,
+                args: vec![
+                    ("max", "3".into()),
+                    ("min", "2".into()),
+                    ("found", "4".into()),
+                    ("matching", "[0, 1, 3, 4]".into()),
+                    ("other", "[2]".into())
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_array_contains_range() {
+        let ctx = Context::default();
+        let document = load_simple("[a, b, 1]");
+
+        for (schema, error, min) in &[
+            (
+                "minContains: 2\ncontains:\n  type: integer",
+                MessageId::ContainsAtLeast,
+                "2",
+            ),
+            (
+                "minContains: 0\nmaxContains: 0\ncontains:\n  type: integer",
+                MessageId::ContainsExactly,
+                "0",
+            ),
+        ] {
+            let yaml = load_simple(schema);
+
+            assert_eq!(
+                SchemaArray::try_from(&yaml)
+                    .unwrap()
+                    .validate(&ctx, &document)
+                    .unwrap_err(),
+                ValidationErrorKind::ParameterizedValidation {
+                    error: *error,
+                    args: vec![
+                        ("min", min.to_string()),
+                        ("found", "1".into()),
+                        ("matching", "[2]".into()),
+                        ("other", "[0, 1]".into())
+                    ]
+                }
+                .into()
+            );
+        }
+
+        // Without minContains, the implicit minimum of one is part of the range.
+        let yaml = load_simple("maxContains: 2\ncontains:\n  type: integer");
+        assert_eq!(
+            SchemaArray::try_from(&yaml)
+                .unwrap()
+                .validate(&ctx, &load_simple("[a, b]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ContainsBetween,
+                args: vec![
+                    ("max", "2".into()),
+                    ("min", "1".into()),
+                    ("found", "0".into()),
+                    ("matching", "[]".into()),
+                    ("other", "[0, 1]".into())
+                ]
+            }
+            .into()
+        );
+    }
+
     #[test]
     fn validate_hash() {
         let schema = SchemaArray::default();
@@ -679,4 +1455,256 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn validate_many_references() {
+        let schemas = yaml_rust::YamlLoader::load_from_str(
+            r#"
+            uri: person
+            schema:
+              type: object
+              items:
+                name:
+                  type: string
+                age:
+                  type: integer
+            "#,
+        )
+        .unwrap();
+
+        let yaml = load_simple(
+            r#"
+            items:
+              $ref: person
+            "#,
+        );
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        let mut document: String = (0..10_000)
+            .map(|i| format!("- name: person{}\n  age: {}\n", i, i % 100))
+            .collect();
+
+        let people = yaml_rust::YamlLoader::load_from_str(&document).unwrap();
+        schema.validate(&ctx, &people[0]).unwrap();
+
+        document.push_str("- name: latecomer\n  age: old\n");
+
+        let people = yaml_rust::YamlLoader::load_from_str(&document).unwrap();
+        assert_eq!(
+            schema.validate(&ctx, &people[0]).unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path(breadcrumb!["age", 10_000usize])
+        );
+    }
+
+    #[test]
+    fn validate_unknown_reference() {
+        let yaml = load_simple(
+            r#"
+            items:
+              $ref: person
+            "#,
+        );
+
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema.validate(&ctx, &load_simple("[]")).unwrap();
+
+        assert_eq!(
+            schema.validate(&ctx, &load_simple("- hello")).unwrap_err(),
+            ValidationErrorKind::UnknownSchema { uri: "person" }.into()
+        );
+    }
+
+    #[test]
+    fn validate_one_of_items() {
+        let yaml = load_simple(
+            r#"
+            items:
+              oneOf:
+                - type: integer
+                - type: string
+            "#,
+        );
+
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+        let document = load_simple("[1, hello, 1.5]");
+
+        let err = schema.validate(&ctx, &document).unwrap_err();
+
+        assert_eq!(
+            format!("{}", err),
+            "#[2].oneOf[0]: wrong type, expected integer got real\n\
+             #[2].oneOf[1]: wrong type, expected string got real\n"
+        );
+    }
+
+    #[test]
+    fn validate_sorted() {
+        let yaml = load_simple("sorted: asc");
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema
+            .validate(&ctx, &load_simple("[1, 2, 2.5, 10]"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("[1, 5, 3, 10]"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::NotAscending
+            }
+            .with_path_index(2)
+        );
+
+        let yaml = load_simple("sorted: desc");
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        schema.validate(&ctx, &load_simple("[c, b, a]")).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("[3, hello]"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::IncomparableItems,
+                args: vec![("left", "integer".into()), ("right", "string".into())]
+            }
+            .with_path_index(1)
+        );
+    }
+
+    #[test]
+    fn malformed_sorted() {
+        assert_eq!(
+            SchemaArray::try_from(&load_simple("sorted: up")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "sorted must be either 'asc' or 'desc', not 'up'".into()
+            }
+            .with_path_name("sorted")
+        );
+
+        assert_eq!(
+            SchemaArray::try_from(&load_simple("sorted: 1")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path_name("sorted")
+        );
+    }
+
+    #[test]
+    fn validate_prefix_items() {
+        let yaml = load_simple(
+            r#"
+            prefixItems:
+              - type: string
+              - type: integer
+            items:
+              type: boolean
+            "#,
+        );
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("[point, 10, true, false]"),
+            )
+            .unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("[point]"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[point, 10, 20]"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "bool",
+                actual: "integer"
+            }
+            .with_path_index(2)
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[10, 10]"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path_index(0)
+        );
+    }
+
+    #[test]
+    fn validate_items_union() {
+        let yaml = load_simple(
+            r#"
+            itemsAnyOf:
+              - type: string
+              - type: integer
+            "#,
+        );
+        let schema = SchemaArray::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("[point, 10, 20, line]"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("[point, 10, true]"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::WrongType {
+                        expected: "string",
+                        actual: "boolean"
+                    }
+                    .into(),
+                    ValidationErrorKind::WrongType {
+                        expected: "integer",
+                        actual: "boolean"
+                    }
+                    .into(),
+                ]
+            }
+            .with_path_index(2)
+        );
+    }
+
+    #[test]
+    fn prefix_items_without_room_for_items() {
+        assert_eq!(
+            SchemaArray::try_from(&load_simple(
+                r#"
+                prefixItems:
+                  - type: string
+                  - type: integer
+                items:
+                  type: boolean
+                maxItems: 2
+                "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "items only applies to elements after the 2 prefixItems, but maxItems of 2 leaves no room for any".into()
+            }
+            .with_path_name("items")
+        );
+    }
 }
@@ -1,27 +1,47 @@
-use crate::errors::{SchemaError, ValidationError};
-use crate::utils::YamlUtils;
-use crate::{Context, Validate};
+use crate::errors::{MessageId, SchemaError, ValidationError, ValidationErrorKind};
+use crate::utils::{type_keywords, OptionalLookup, YamlUtils};
+use crate::{Context, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug, Default)]
-pub(crate) struct SchemaBool {}
+pub(crate) struct SchemaBool {
+    must_be: Option<bool>,
+}
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaBool {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&[], &["type"])?;
-        Ok(SchemaBool {})
+        yaml.strict_schema_contents(&[], &type_keywords("boolean"))?;
+
+        let must_be = yaml
+            .lookup("mustBe", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("mustBe"))
+            .into_optional()?;
+
+        Ok(SchemaBool { must_be })
     }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaBool {
-    fn validate(
+    fn validate_with(
         &self,
         _: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        _: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
-        let _value = yaml.as_type("bool", Yaml::as_bool)?;
+        let value = yaml.as_type("bool", Yaml::as_bool)?;
+
+        if let Some(must_be) = self.must_be {
+            if value != must_be {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::MustBe,
+                    args: vec![("expected", must_be.to_string())],
+                }
+                .into());
+            }
+        }
 
         Ok(())
     }
@@ -145,4 +165,53 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn validate_must_be() {
+        let schema = SchemaBool::try_from(&load_simple("mustBe: true")).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("true"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("false"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::MustBe,
+                args: vec![("expected", "true".into())]
+            }
+            .into()
+        );
+
+        let schema = SchemaBool::try_from(&load_simple("mustBe: false")).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("false"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("true"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::MustBe,
+                args: vec![("expected", "false".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn with_malformed_must_be() {
+        assert_eq!(
+            SchemaBool::try_from(&load_simple("mustBe: yes")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "bool",
+                actual: "string"
+            }
+            .with_path_name("mustBe")
+        );
+    }
 }
@@ -0,0 +1,130 @@
+use crate::errors::{SchemaError, ValidationError, ValidationErrorKind};
+use crate::utils::YamlUtils;
+use crate::{Context, Validate, ValidationOptions};
+use std::convert::TryFrom;
+use yaml_rust::Yaml;
+
+/// A `type` not built into the crate, validated by whichever validator was
+/// registered under its name using [Context::register_type].
+#[derive(Debug)]
+pub(crate) struct SchemaCustom<'schema> {
+    pub(crate) typename: &'schema str,
+}
+
+impl<'schema> TryFrom<&'schema Yaml> for SchemaCustom<'schema> {
+    type Error = SchemaError<'schema>;
+    fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
+        yaml.strict_schema_contents(&["type"], &[])?;
+
+        let typename = yaml.lookup("type", "string", Yaml::as_str)?;
+        Ok(SchemaCustom { typename })
+    }
+}
+
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaCustom<'schema> {
+    fn validate_with(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        _: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        match ctx.custom_types.get(self.typename) {
+            Some(validator) => validator(yaml),
+            None => Err(ValidationErrorKind::UnknownType {
+                unknown_type: self.typename,
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::load_simple;
+    use crate::SchemaErrorKind;
+
+    fn semver(yaml: &Yaml) -> Result<(), ValidationError<'_>> {
+        let version = yaml.as_type("string", Yaml::as_str)?;
+
+        if version.split('.').all(|part| part.parse::<u64>().is_ok())
+            && version.split('.').count() == 3
+        {
+            Ok(())
+        } else {
+            Err(ValidationErrorKind::DetailedValidation {
+                message: "value is not a semantic version".into(),
+            }
+            .into())
+        }
+    }
+
+    #[test]
+    fn validate_custom_type() {
+        let schemas = yaml_rust::YamlLoader::load_from_str(
+            r#"
+            uri: release
+            schema:
+              type: object
+              items:
+                version:
+                  type: semver
+            "#,
+        )
+        .unwrap();
+
+        let mut ctx = Context::default();
+        ctx.register_type("semver", semver);
+        ctx.add_schemas(&schemas).unwrap();
+
+        let schema = ctx.get_schema("release").unwrap();
+
+        schema
+            .validate(&ctx, &load_simple("version: 1.20.3"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("version: 1.x.3"))
+                .unwrap_err(),
+            ValidationErrorKind::DetailedValidation {
+                message: "value is not a semantic version".into()
+            }
+            .with_path_name("version")
+        );
+    }
+
+    #[test]
+    fn unregistered_custom_type() {
+        let schemas = yaml_rust::YamlLoader::load_from_str(
+            r#"
+            uri: release
+            schema:
+              type: semver
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Context::default().add_schemas(&schemas).unwrap_err(),
+            SchemaErrorKind::UnknownType {
+                unknown_type: "semver"
+            }
+            .with_path_name("release")
+        );
+    }
+
+    #[test]
+    fn unregistered_at_validation() {
+        assert_eq!(
+            SchemaCustom::try_from(&load_simple("type: semver"))
+                .unwrap()
+                .validate(&Context::default(), &load_simple("1.2.3"))
+                .unwrap_err(),
+            ValidationErrorKind::UnknownType {
+                unknown_type: "semver"
+            }
+            .into()
+        );
+    }
+}
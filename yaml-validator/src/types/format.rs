@@ -0,0 +1,451 @@
+use crate::errors::MessageId;
+
+/// Formats which can be given by the `format` keyword of a string schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StringFormat {
+    /// An ISO 8601 duration, such as `PT1H30M`
+    Duration,
+    /// A JSON Pointer as described in RFC 6901, such as `/items/0`
+    JsonPointer,
+    /// An absolute or relative URI as described in RFC 3986, such as `../schema.yaml#/items`
+    UriReference,
+    /// A color given in hexadecimal, such as `#ff8800`, `#f80` or `#ff880080`
+    ColorHex,
+    /// A calendar date, such as `2020-01-31`
+    #[cfg(feature = "date")]
+    Date,
+}
+
+impl StringFormat {
+    pub(crate) fn from_name(name: &str) -> Option<StringFormat> {
+        match name {
+            "duration" => Some(StringFormat::Duration),
+            "json-pointer" => Some(StringFormat::JsonPointer),
+            "uri-reference" => Some(StringFormat::UriReference),
+            "color-hex" => Some(StringFormat::ColorHex),
+            #[cfg(feature = "date")]
+            "date" => Some(StringFormat::Date),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            StringFormat::Duration => "duration",
+            StringFormat::JsonPointer => "json-pointer",
+            StringFormat::UriReference => "uri-reference",
+            StringFormat::ColorHex => "color-hex",
+            #[cfg(feature = "date")]
+            StringFormat::Date => "date",
+        }
+    }
+
+    pub(crate) fn is_valid(self, value: &str) -> bool {
+        match self {
+            StringFormat::Duration => is_duration(value),
+            StringFormat::JsonPointer => is_json_pointer(value),
+            StringFormat::UriReference => is_uri_reference(value),
+            StringFormat::ColorHex => is_color_hex(value),
+            #[cfg(feature = "date")]
+            StringFormat::Date => Date::parse(value).is_some(),
+        }
+    }
+}
+
+/// A date in the proleptic gregorian calendar, ordered chronologically.
+#[cfg(feature = "date")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[cfg(feature = "date")]
+impl Date {
+    /// Parse a date of the form `YYYY-MM-DD`, rejecting days which do not exist, such as `2021-02-29`.
+    pub(crate) fn parse(value: &str) -> Option<Date> {
+        let mut parts = value.split('-');
+        let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+
+        if parts.next().is_some()
+            || year.len() != 4
+            || month.len() != 2
+            || day.len() != 2
+            || !value.chars().all(|c| c.is_ascii_digit() || c == '-')
+        {
+            return None;
+        }
+
+        let date = Date {
+            year: year.parse().ok()?,
+            month: month.parse().ok()?,
+            day: day.parse().ok()?,
+        };
+
+        let leap = date.year.is_multiple_of(4)
+            && (!date.year.is_multiple_of(100) || date.year.is_multiple_of(400));
+        let days_in_month = match date.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        if date.day == 0 || date.day > days_in_month {
+            return None;
+        }
+
+        Some(date)
+    }
+}
+
+#[cfg(feature = "date")]
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Formats which can be given by the `format` keyword of an integer schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IntegerFormat {
+    /// Seconds since the Unix epoch, up until the end of the year 9999
+    UnixTimestamp,
+}
+
+/// 9999-12-31T23:59:59Z, beyond which a timestamp is most likely given in the wrong unit.
+const MAX_UNIX_TIMESTAMP: i64 = 253_402_300_799;
+
+impl IntegerFormat {
+    pub(crate) fn from_name(name: &str) -> Option<IntegerFormat> {
+        match name {
+            "unix-timestamp" => Some(IntegerFormat::UnixTimestamp),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            IntegerFormat::UnixTimestamp => "unix-timestamp",
+        }
+    }
+
+    /// Describes why the value does not fit the format, if it doesn't.
+    pub(crate) fn check(self, value: i64) -> Option<MessageId> {
+        match self {
+            IntegerFormat::UnixTimestamp if value < 0 => Some(MessageId::NegativeTimestamp),
+            IntegerFormat::UnixTimestamp if value > MAX_UNIX_TIMESTAMP => {
+                Some(MessageId::TimestampTooLarge)
+            }
+            IntegerFormat::UnixTimestamp => None,
+        }
+    }
+}
+
+/// Checks a duration of the form `PnYnMnWnDTnHnMnS`, where every component is optional, but
+/// at least one must be given, and only the last one given may have a fractional part.
+fn is_duration(value: &str) -> bool {
+    let value = match value.strip_prefix('P') {
+        Some(value) => value,
+        None => return false,
+    };
+
+    let (date, time) = match value.split_once('T') {
+        Some((_, "")) => return false,
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut fractional = false;
+    let mut components = 0;
+
+    for (part, designators) in std::iter::once((date, "YMWD")).chain(time.map(|time| (time, "HMS")))
+    {
+        let mut designators = designators.chars();
+        let mut number = String::new();
+
+        for c in part.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                number.push(c);
+                continue;
+            }
+
+            // Designators must appear in order, each preceded by a number, and nothing
+            // may follow a component with a fractional part.
+            if number.is_empty() || fractional || !designators.any(|designator| designator == c) {
+                return false;
+            }
+
+            if let Some((integer, fraction)) = number.replace(',', ".").split_once('.') {
+                if integer.is_empty() || fraction.is_empty() || fraction.contains('.') {
+                    return false;
+                }
+                fractional = true;
+            }
+
+            components += 1;
+            number.clear();
+        }
+
+        if !number.is_empty() {
+            return false;
+        }
+    }
+
+    components > 0
+}
+
+/// Checks a JSON Pointer, which is either empty or a series of tokens each preceded by a `/`,
+/// and within which `~` may only appear as part of the escapes `~0` and `~1`.
+fn is_json_pointer(value: &str) -> bool {
+    if value.is_empty() {
+        return true;
+    }
+
+    value.starts_with('/')
+        && value
+            .split('~')
+            .skip(1)
+            .all(|escaped| escaped.starts_with('0') || escaped.starts_with('1'))
+}
+
+/// Checks a URI reference, made up of an optional scheme and authority followed by a path,
+/// query and fragment, in which all characters outside of those allowed by RFC 3986 are
+/// percent-encoded. Brackets are only allowed around the host of the authority.
+fn is_uri_reference(value: &str) -> bool {
+    const BRACKETS: [char; 2] = ['[', ']'];
+
+    let bytes = value.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if bytes.len() > i + 2
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                i += 3
+            }
+            c if c.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=".contains(&c) => i += 1,
+            _ => return false,
+        }
+    }
+
+    let (rest, fragment) = value.split_once('#').unwrap_or((value, ""));
+    let (mut hierarchy, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    if fragment.contains('#') || fragment.contains(BRACKETS) || query.contains(BRACKETS) {
+        return false;
+    }
+
+    // A colon ahead of the first slash ends the scheme, rather than being part of the path.
+    if let Some((scheme, remainder)) = hierarchy.split_once(':') {
+        if !scheme.contains('/') {
+            let mut chars = scheme.chars();
+
+            if !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+                || !chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            {
+                return false;
+            }
+
+            hierarchy = remainder;
+        }
+    }
+
+    let path = match hierarchy.strip_prefix("//") {
+        Some(authority) => {
+            let (authority, path) = match authority.find('/') {
+                Some(slash) => authority.split_at(slash),
+                None => (authority, ""),
+            };
+
+            let host = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+
+            // An IP literal is enclosed in brackets, optionally followed by a port.
+            let host = match host.strip_prefix('[') {
+                Some(literal) => match literal.split_once(']') {
+                    Some((_, port)) => port,
+                    None => return false,
+                },
+                None => host,
+            };
+
+            if host.contains(BRACKETS) {
+                return false;
+            }
+
+            path
+        }
+        None => hierarchy,
+    };
+
+    !path.contains(BRACKETS)
+}
+
+/// Checks a color of the form `#RGB`, `#RRGGBB` or `#RRGGBBAA`.
+fn is_color_hex(value: &str) -> bool {
+    match value.strip_prefix('#') {
+        Some(digits) => {
+            matches!(digits.len(), 3 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_color_hex, is_duration, is_json_pointer, is_uri_reference};
+
+    #[test]
+    #[cfg(feature = "date")]
+    fn dates() {
+        use super::Date;
+
+        for valid in &["2020-01-01", "2020-02-29", "2000-02-29", "1999-12-31"] {
+            assert_eq!(
+                Date::parse(valid).map(|date| date.to_string()).as_deref(),
+                Some(*valid)
+            );
+        }
+
+        for invalid in &[
+            "",
+            "2020-1-01",
+            "2021-02-29",
+            "1900-02-29",
+            "2020-13-01",
+            "2020-04-31",
+            "2020-01-00",
+            "2020-01-01-01",
+            "+020-01-01",
+        ] {
+            assert_eq!(
+                Date::parse(invalid),
+                None,
+                "{} should not be a valid date",
+                invalid
+            );
+        }
+
+        assert!(Date::parse("2019-12-31") < Date::parse("2020-01-01"));
+    }
+
+    #[test]
+    fn durations() {
+        for valid in &[
+            "PT1H30M",
+            "P1Y2M10DT2H30M",
+            "P3W",
+            "PT0.5S",
+            "P1DT12H",
+            "PT1,5H",
+        ] {
+            assert!(is_duration(valid), "{} should be a valid duration", valid);
+        }
+
+        for invalid in &[
+            "",
+            "P",
+            "PT",
+            "1H30M",
+            "PT1H30",
+            "PT30M1H",
+            "P1H",
+            "PT1.5H30M",
+            "PT.5S",
+            "P1DT",
+        ] {
+            assert!(
+                !is_duration(invalid),
+                "{} should not be a valid duration",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn json_pointers() {
+        for valid in &["", "/", "/items/0", "/a~1b", "/m~0n", "/~01", "//"] {
+            assert!(
+                is_json_pointer(valid),
+                "{} should be a valid json pointer",
+                valid
+            );
+        }
+
+        for invalid in &["items", "#/items", "/a~b", "/a~", "/~2"] {
+            assert!(
+                !is_json_pointer(invalid),
+                "{} should not be a valid json pointer",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn uri_references() {
+        for valid in &[
+            "",
+            "https://example.com/schemas/person.yaml",
+            "../schema.yaml#/items",
+            "#fragment",
+            "mailto:someone@example.com",
+            "urn:isbn:0451450523",
+            "http://user@[::1]:8080/path?query=1",
+            "path%20with%20spaces",
+            "./this:that",
+        ] {
+            assert!(
+                is_uri_reference(valid),
+                "{} should be a valid uri reference",
+                valid
+            );
+        }
+
+        for invalid in &[
+            "has space",
+            "1http://example.com",
+            "http://example.com/#a#b",
+            "%zz",
+            "%2",
+            "http://[::1/path",
+            "/path[0]",
+            "this:that/[0]",
+            "quote\"",
+        ] {
+            assert!(
+                !is_uri_reference(invalid),
+                "{} should not be a valid uri reference",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn color_hexes() {
+        for valid in &["#ff8800", "#FF8800", "#ff880080", "#f80"] {
+            assert!(is_color_hex(valid), "{} should be a valid color", valid);
+        }
+
+        for invalid in &[
+            "",
+            "#",
+            "ff8800",
+            "#ff88",
+            "#ff88000",
+            "#gg8800",
+            "#ff8800ff00",
+        ] {
+            assert!(
+                !is_color_hex(invalid),
+                "{} should not be a valid color",
+                invalid
+            );
+        }
+    }
+}
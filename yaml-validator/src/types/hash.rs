@@ -1,73 +1,100 @@
 use crate::errors::SchemaError;
 use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{OptionalLookup, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use crate::types::object::string_list;
+use crate::utils::{get_str, type_keywords, OptionalLookup, YamlUtils};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
 #[derive(Debug, Default)]
 pub(crate) struct SchemaHash<'schema> {
-    items: Option<Box<PropertyType<'schema>>>,
+    pub(crate) items: Option<Box<PropertyType<'schema>>>,
+    pub(crate) required_keys: Vec<&'schema str>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaHash<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&[], &["items", "type"])?;
+        SchemaHash::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> SchemaHash<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_schema_contents(&[], &type_keywords("hash"))?;
 
         // I'm using Option::from here because I don't actually want to transform
         // the resulting yaml object into a specific type, but need the yaml itself
-        // to be passed into PropertyType::try_from
-        yaml.lookup("items", "yaml", Option::from)
+        // to be passed into PropertyType::parse
+        let items = yaml
+            .lookup("items", "yaml", Option::from)
             .map_err(SchemaError::from)
+            .into_optional()?
             .map(|inner| {
                 yaml.lookup("items", "hash", Yaml::as_hash)
                     .map_err(SchemaError::from)
                     .map_err(SchemaError::add_path_name("items"))?;
 
-                Ok(SchemaHash {
-                    items: Some(Box::new(
-                        PropertyType::try_from(inner)
-                            .map_err(SchemaError::add_path_name("items"))?,
-                    )),
-                })
+                PropertyType::parse(inner, ctx)
+                    .map(Box::new)
+                    .map_err(SchemaError::add_path_name("items"))
             })
+            .transpose()?;
+
+        let required_keys = yaml
+            .lookup("requiredKeys", "yaml", Option::from)
+            .map_err(SchemaError::from)
             .into_optional()?
-            .unwrap_or(Ok(SchemaHash { items: None }))
+            .map(|keys| string_list(keys).map_err(SchemaError::add_path_name("requiredKeys")))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(SchemaHash {
+            items,
+            required_keys,
+        })
     }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaHash<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         let items = yaml.as_type("hash", Yaml::as_hash)?;
 
+        // Missing keys are reported ahead of any errors within the values.
+        let mut errors: Vec<ValidationError<'yaml>> = self
+            .required_keys
+            .iter()
+            .filter(|key| get_str(items, key).is_none())
+            .map(|field| ValidationErrorKind::FieldMissing { field }.into())
+            .collect();
+
         if let Some(schema) = &self.items {
-            let mut errors: Vec<ValidationError<'yaml>> = items
-                .values()
-                .enumerate()
-                .map(|(i, item)| {
-                    schema
-                        .validate(ctx, item)
-                        .map_err(ValidationError::add_path_index(i))
-                })
-                .filter(Result::is_err)
-                .map(Result::unwrap_err)
-                .collect();
-
-            return if errors.is_empty() {
-                Ok(())
-            } else if errors.len() == 1 {
-                Err(errors.pop().unwrap())
-            } else {
-                Err(ValidationErrorKind::Multiple { errors }.into())
-            };
+            errors.extend(
+                items
+                    .values()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        schema
+                            .validate_with(ctx, item, opts)
+                            .map_err(ValidationError::add_path_index(i))
+                    })
+                    .filter(Result::is_err)
+                    .map(Result::unwrap_err),
+            );
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else if errors.len() == 1 {
+            Err(errors.pop().unwrap())
+        } else {
+            Err(ValidationErrorKind::Multiple { errors }.into())
+        }
     }
 }
 
@@ -218,4 +245,56 @@ mod tests {
             .with_path_index(1)
         );
     }
+
+    #[test]
+    fn validate_required_keys() {
+        let yaml = load_simple(
+            r#"
+            type: hash
+            requiredKeys: [default, fallback]
+            items:
+              type: integer
+        "#,
+        );
+        let schema = SchemaHash::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("default: 1\nfallback: 2\nextra: 3"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("default: 1\nextra: three")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::FieldMissing { field: "fallback" }.into(),
+                    ValidationErrorKind::WrongType {
+                        expected: "integer",
+                        actual: "string"
+                    }
+                    .with_path_index(1)
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn malformed_required_keys() {
+        assert_eq!(
+            SchemaHash::try_from(&load_simple("requiredKeys: default")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "array",
+                actual: "string"
+            }
+            .with_path_name("requiredKeys"),
+        );
+    }
 }
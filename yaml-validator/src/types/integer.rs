@@ -1,56 +1,62 @@
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
 use crate::errors::{SchemaError, SchemaErrorKind};
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{Limit, OptionalLookup, YamlUtils};
-use crate::{Context, Validate};
+use crate::types::format::IntegerFormat;
+use crate::utils::{
+    enum_error, lookup_divisors, lookup_enum, type_keywords, Limit, OptionalLookup, YamlUtils,
+};
+use crate::{Context, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
+/// Schema for `type: integer` values. Built from a YAML schema node using [TryFrom].
 #[derive(Debug, Default)]
-pub(crate) struct SchemaInteger {
-    minimum: Option<Limit<i64>>,
-    maximum: Option<Limit<i64>>,
-    multiple_of: Option<i64>,
+pub struct SchemaInteger {
+    pub(crate) minimum: Option<Limit<i64>>,
+    pub(crate) maximum: Option<Limit<i64>>,
+    // Every one of these must divide the value.
+    pub(crate) multiple_of: Vec<i64>,
+    pub(crate) enumeration: Option<Vec<i64>>,
+    // Accept reals without a fractional part, such as `10.0`, as integers.
+    pub(crate) coerce: bool,
+    pub(crate) format: Option<IntegerFormat>,
+}
+
+/// Looks up an integer bound such as `minimum`. With the `lenient-schema` feature,
+/// bounds written as numeric strings are accepted too, for schemas imported from elsewhere.
+fn lookup_bound<'schema>(
+    yaml: &'schema Yaml,
+    field: &'schema str,
+) -> Result<Option<i64>, SchemaError<'schema>> {
+    #[cfg(feature = "lenient-schema")]
+    if let Some(value) = yaml[field].as_str() {
+        return value.parse::<i64>().map(Some).map_err(|_| {
+            SchemaErrorKind::MalformedField {
+                error: format!("'{}' is not a valid integer", value),
+            }
+            .with_path_name(field)
+        });
+    }
+
+    yaml.lookup(field, "integer", Yaml::as_i64)
+        .map_err(SchemaError::from)
+        .into_optional()
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaInteger {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(
-            &[],
-            &[
-                "type",
-                "minimum",
-                "exclusiveMinimum",
-                "maximum",
-                "exclusiveMaximum",
-                "multipleOf",
-            ],
-        )?;
+        yaml.strict_schema_contents(&[], &type_keywords("integer"))?;
 
         yaml.check_exclusive_fields(&["minimum", "exclusiveMinimum"])?;
         yaml.check_exclusive_fields(&["maximum", "exclusiveMaximum"])?;
 
-        let minimum = yaml
-            .lookup("minimum", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
+        let minimum = lookup_bound(yaml, "minimum")?
             .map(Limit::Inclusive)
-            .into_optional()?
-            .or(yaml
-                .lookup("exclusiveMinimum", "integer", Yaml::as_i64)
-                .map_err(SchemaError::from)
-                .map(Limit::Exclusive)
-                .into_optional()?);
-
-        let maximum = yaml
-            .lookup("maximum", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
+            .or(lookup_bound(yaml, "exclusiveMinimum")?.map(Limit::Exclusive));
+
+        let maximum = lookup_bound(yaml, "maximum")?
             .map(Limit::Inclusive)
-            .into_optional()?
-            .or(yaml
-                .lookup("exclusiveMaximum", "integer", Yaml::as_i64)
-                .map_err(SchemaError::from)
-                .map(Limit::Exclusive)
-                .into_optional()?);
+            .or(lookup_bound(yaml, "exclusiveMaximum")?.map(Limit::Exclusive));
 
         if let (Some(lower), Some(upper)) = (&minimum, &maximum) {
             if !lower.has_span(upper) {
@@ -61,64 +67,145 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaInteger {
             }
         }
 
-        let multiple_of = yaml
-            .lookup("multipleOf", "integer", Yaml::as_i64)
+        let multiple_of = lookup_divisors(yaml, "integer", Yaml::as_i64)?
+            .into_iter()
+            .map(|(divisor, _)| divisor)
+            .collect();
+
+        let enumeration = lookup_enum(yaml, "integer", Yaml::as_i64)?;
+
+        let coerce = yaml
+            .lookup("coerce", "bool", Yaml::as_bool)
             .map_err(SchemaError::from)
-            .and_then(|number| {
-                if number <= 0 {
-                    Err(SchemaErrorKind::MalformedField {
-                        error: "must be greater than zero".into(),
-                    }
-                    .with_path_name("multipleOf"))
-                } else {
-                    Ok(number)
-                }
+            .map_err(SchemaError::add_path_name("coerce"))
+            .into_optional()?
+            .unwrap_or(false);
+
+        let format = yaml
+            .lookup("format", "string", Yaml::as_str)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|name| {
+                IntegerFormat::from_name(name).ok_or_else(|| {
+                    SchemaError::from(SchemaErrorKind::MalformedField {
+                        error: format!("unknown format '{}'", name),
+                    })
+                })
             })
-            .into_optional()?;
+            .transpose()
+            .map_err(SchemaError::add_path_name("format"))?;
 
         Ok(SchemaInteger {
             minimum,
             maximum,
             multiple_of,
+            enumeration,
+            coerce,
+            format,
         })
     }
 }
 
+impl SchemaInteger {
+    /// The lower bound given by `minimum` or `exclusiveMinimum`, if any.
+    pub fn minimum(&self) -> Option<&Limit<i64>> {
+        self.minimum.as_ref()
+    }
+
+    /// The upper bound given by `maximum` or `exclusiveMaximum`, if any.
+    pub fn maximum(&self) -> Option<&Limit<i64>> {
+        self.maximum.as_ref()
+    }
+
+    /// The value given by `multipleOf` or `divisibleBy`, if it is a single divisor.
+    pub fn multiple_of(&self) -> Option<i64> {
+        match self.multiple_of[..] {
+            [divisor] => Some(divisor),
+            _ => None,
+        }
+    }
+
+    /// Every divisor given by `multipleOf` or `divisibleBy`, all of which must divide the value.
+    pub fn multiples_of(&self) -> &[i64] {
+        &self.multiple_of
+    }
+
+    /// Interpret a real as an integer, provided it has no fractional part.
+    pub(crate) fn coerce_real<'yaml>(
+        yaml: &'yaml Yaml,
+    ) -> Option<Result<i64, ValidationError<'yaml>>> {
+        let value = yaml.as_f64()?;
+
+        if value.fract() != 0.0 || !value.is_finite() {
+            return Some(Err(ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotAnInteger,
+                args: vec![("value", value.to_string())],
+            }
+            .into()));
+        }
+
+        // i64::MAX rounds up to 2^63 as a float, which is already out of range.
+        if value < i64::MIN as f64 || value >= i64::MAX as f64 {
+            return Some(Err(ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::IntegerOverflow,
+                args: vec![("value", value.to_string())],
+            }
+            .into()));
+        }
+
+        Some(Ok(value as i64))
+    }
+}
+
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaInteger {
-    fn validate(
+    fn validate_with(
         &self,
         _: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
-        let value = yaml.as_type("integer", Yaml::as_i64)?;
+        let value = match SchemaInteger::coerce_real(yaml) {
+            Some(value) if self.coerce || opts.coerce => value?,
+            _ => yaml.as_type("integer", Yaml::as_i64)?,
+        };
 
         if let Some(minimum) = &self.minimum {
             if !minimum.is_greater(&value) {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value violates lower limit constraint",
-                }
-                .into());
+                return Err(minimum.lower_violation(&value).into());
             }
         }
 
         if let Some(maximum) = &self.maximum {
             if !maximum.is_lesser(&value) {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value violates upper limit constraint",
-                }
-                .into());
+                return Err(maximum.upper_violation(&value).into());
             }
         }
 
-        if let Some(multiple_of) = &self.multiple_of {
-            if value.rem_euclid(*multiple_of) != 0 {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value must be a multiple of the multipleOf field",
-                }
-                .into());
+        if self
+            .multiple_of
+            .iter()
+            .any(|divisor| value.rem_euclid(*divisor) != 0)
+        {
+            return Err(ValidationErrorKind::ValidationError {
+                error: MessageId::MultipleOf,
+            }
+            .into());
+        }
+
+        if let Some(enumeration) = &self.enumeration {
+            if !enumeration.contains(&value) {
+                return Err(enum_error(value, enumeration));
             }
         }
 
+        if let Some(error) = self.format.and_then(|format| format.check(value)) {
+            return Err(ValidationErrorKind::ParameterizedValidation {
+                error,
+                args: vec![("value", value.to_string())],
+            }
+            .into());
+        }
+
         Ok(())
     }
 }
@@ -191,6 +278,37 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg(not(feature = "lenient-schema"))]
+    fn with_string_bounds() {
+        assert_eq!(
+            SchemaInteger::try_from(&load_simple("minimum: '10'")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lenient-schema")]
+    fn with_string_bounds() {
+        let yaml = load_simple("minimum: '10'\nexclusiveMaximum: \"20\"");
+        let schema = SchemaInteger::try_from(&yaml).unwrap();
+
+        assert_eq!(schema.minimum(), Some(&Limit::Inclusive(10)));
+        assert_eq!(schema.maximum(), Some(&Limit::Exclusive(20)));
+
+        assert_eq!(
+            SchemaInteger::try_from(&load_simple("maximum: ten")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "'ten' is not a valid integer".into()
+            }
+            .with_path_name("maximum")
+        );
+    }
+
     #[test]
     fn with_mixed_limits() {
         SchemaInteger::try_from(&load_simple(
@@ -203,6 +321,23 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn read_limits() {
+        let schema = SchemaInteger::try_from(&load_simple(
+            r#"
+                type: integer
+                exclusiveMaximum: 12
+                minimum: 10
+                multipleOf: 2
+            "#,
+        ))
+        .unwrap();
+
+        assert_eq!(schema.minimum(), Some(&Limit::Inclusive(10)));
+        assert_eq!(schema.maximum(), Some(&Limit::Exclusive(12)));
+        assert_eq!(schema.multiple_of(), Some(2));
+    }
+
     #[test]
     fn with_zero_width_limits() {
         assert_eq!(
@@ -221,6 +356,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn validate_bad_value() {
+        let schema = SchemaInteger::default();
+        let document = load_simple("count: 10");
+        let ctx = Context::default();
+
+        let err = schema.validate(&ctx, &document["missing"]).unwrap_err();
+
+        assert_eq!(
+            err,
+            ValidationErrorKind::BadValue {
+                expected: "integer"
+            }
+            .into()
+        );
+
+        assert_eq!(
+            format!("{}", err),
+            "#: value could not be parsed or is missing, expected integer\n"
+        );
+    }
+
     #[test]
     fn validate_string() {
         let schema = SchemaInteger::default();
@@ -308,8 +465,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("10"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates lower limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ExclusiveLowerLimit,
+                args: vec![("value", "10".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -344,8 +502,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("5"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates lower limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::LowerLimit,
+                args: vec![("value", "5".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -365,8 +524,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("10"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates upper limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ExclusiveUpperLimit,
+                args: vec![("value", "10".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -401,8 +561,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("20"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates upper limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::UpperLimit,
+                args: vec![("value", "20".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -423,7 +584,7 @@ mod tests {
                 .validate(&Context::default(), &load_simple("10"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "value must be a multiple of the multipleOf field"
+                error: MessageId::MultipleOf
             }
             .into()
         );
@@ -444,6 +605,56 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn validate_divisible_by() {
+        let schema = SchemaInteger::try_from(&load_simple("divisibleBy: 5")).unwrap();
+        assert_eq!(schema.multiple_of(), Some(5));
+
+        schema
+            .validate(&Context::default(), &load_simple("25"))
+            .unwrap();
+
+        assert!(schema
+            .validate(&Context::default(), &load_simple("26"))
+            .is_err());
+
+        assert_eq!(
+            SchemaInteger::try_from(&load_simple("multipleOf: 2\ndivisibleBy: 2")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "conflicting constraints: multipleOf, divisibleBy cannot be used at the same time".into()
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_multiple_divisors() {
+        let schema = SchemaInteger::try_from(&load_simple("multipleOf: [2, 3]")).unwrap();
+        assert_eq!(schema.multiples_of(), &[2, 3]);
+
+        schema
+            .validate(&Context::default(), &load_simple("12"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("8"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MultipleOf
+            }
+            .into()
+        );
+
+        assert_eq!(
+            SchemaInteger::try_from(&load_simple("multipleOf: [2, 0]")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "must be greater than zero".into()
+            }
+            .with_path(breadcrumb![1, "multipleOf"])
+        );
+    }
+
     #[test]
     fn validate_array() {
         let schema = SchemaInteger::default();
@@ -490,4 +701,125 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn validate_enum() {
+        let yaml = load_simple("enum: [1, 2, 3]");
+        let schema = SchemaInteger::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("2"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("4"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotInEnum,
+                args: vec![("value", "4".into()), ("allowed", "1, 2, 3".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_coerced_reals() {
+        let schema = SchemaInteger::try_from(&load_simple("minimum: 5\ncoerce: true")).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("10.0"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("10.5"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotAnInteger,
+                args: vec![("value", "10.5".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("2.0"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::LowerLimit,
+                args: vec![("value", "2".into()), ("limit", "5".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("9.223372036854775807e18"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::IntegerOverflow,
+                args: vec![("value", "9223372036854776000".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_uncoerced_reals() {
+        let schema = SchemaInteger::try_from(&load_simple("minimum: 5")).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("10.0"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "real"
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_unix_timestamp() {
+        let schema = SchemaInteger::try_from(&load_simple("format: unix-timestamp")).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("1700000000"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("-1"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NegativeTimestamp,
+                args: vec![("value", "-1".into())]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("1700000000000"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::TimestampTooLarge,
+                args: vec![("value", "1700000000000".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn with_unknown_format() {
+        assert_eq!(
+            SchemaInteger::try_from(&load_simple("format: epoch")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "unknown format 'epoch'".into()
+            }
+            .with_path_name("format")
+        );
+    }
 }
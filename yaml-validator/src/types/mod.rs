@@ -1,5 +1,7 @@
 pub(crate) mod array;
 pub(crate) mod bool;
+pub(crate) mod custom;
+pub(crate) mod format;
 pub(crate) mod hash;
 pub(crate) mod integer;
 pub(crate) mod object;
@@ -8,9 +10,10 @@ pub(crate) mod reference;
 pub(crate) mod string;
 
 pub(crate) use array::SchemaArray;
+pub(crate) use custom::SchemaCustom;
 pub(crate) use hash::SchemaHash;
-pub(crate) use integer::SchemaInteger;
+pub use integer::SchemaInteger;
 pub(crate) use object::SchemaObject;
-pub(crate) use real::SchemaReal;
+pub use real::SchemaReal;
 pub(crate) use reference::SchemaReference;
 pub(crate) use string::SchemaString;
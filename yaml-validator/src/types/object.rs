@@ -1,29 +1,234 @@
-use crate::errors::SchemaError;
-use crate::errors::ValidationError;
+#[cfg(feature = "regex")]
+use crate::errors::MessageId;
+use crate::errors::{SchemaError, SchemaErrorKind};
+use crate::errors::{ValidationError, ValidationErrorKind};
+use crate::modifiers::SchemaAllOf;
 use crate::utils::CondenseErrors;
-use crate::utils::{OptionalLookup, YamlUtils};
-use crate::{Context, PropertyType, Validate};
+use crate::utils::{get_str, type_keywords, OptionalLookup, YamlUtils};
+use crate::{Context, PropertyType, Validate, ValidationOptions};
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
-use yaml_rust::Yaml;
+use yaml_rust::{yaml::Hash, Yaml};
 
 #[derive(Debug, Default)]
 pub(crate) struct SchemaObject<'schema> {
-    items: BTreeMap<&'schema str, PropertyType<'schema>>,
-    required: Option<Vec<&'schema str>>,
+    pub(crate) items: BTreeMap<&'schema str, PropertyType<'schema>>,
+    pub(crate) required: Option<Vec<&'schema str>>,
+    // Treat required fields holding an empty string, array or hash as missing.
+    pub(crate) require_non_empty: bool,
+    // Required fields which are missing when holding a string of only whitespace.
+    pub(crate) require_non_blank: Vec<&'schema str>,
+    // Values given by the `default` keyword of properties, used when normalizing documents.
+    pub(crate) defaults: BTreeMap<&'schema str, &'schema Yaml>,
+    // Former names of properties, under which their values are still accepted.
+    pub(crate) aliases: BTreeMap<&'schema str, Vec<&'schema str>>,
+    // Properties whose value must equal that of another property, given by `equalTo`.
+    pub(crate) equal_to: BTreeMap<&'schema str, &'schema str>,
+    // Maps a property name to the fields required for each possible value of it.
+    pub(crate) property_dependencies:
+        BTreeMap<&'schema str, Vec<(&'schema Yaml, Vec<&'schema str>)>>,
+    // Fields required only when the named property holds the given value.
+    pub(crate) conditional_required: Vec<ConditionalRequired<'schema>>,
+    // Schemas applied to a property on top of its own, depending on the value of another.
+    pub(crate) conditionals: BTreeMap<&'schema str, Conditional<'schema>>,
+    // Whether properties not evaluated by the object, or by the object branches of a sibling
+    // allOf, are allowed. When false, the branches may each declare some of the properties.
+    pub(crate) unevaluated_properties: Option<bool>,
+    // Properties not listed in items are allowed, as long as their names match this.
+    #[cfg(feature = "regex")]
+    pub(crate) additional_property_name_pattern: Option<regex::Regex>,
+    // Each of these must match the name of at least one property.
+    #[cfg(feature = "regex")]
+    pub(crate) pattern_required: Vec<regex::Regex>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ConditionalRequired<'schema> {
+    pub(crate) field: &'schema str,
+    pub(crate) equals: &'schema Yaml,
+    pub(crate) require: Vec<&'schema str>,
+}
+
+impl<'schema> TryFrom<&'schema Yaml> for ConditionalRequired<'schema> {
+    type Error = SchemaError<'schema>;
+    fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
+        yaml.strict_contents(&["when", "require"], &[])?;
+
+        let (field, equals) = condition(yaml, "when")?;
+
+        let require =
+            string_list(&yaml["require"]).map_err(SchemaError::add_path_name("require"))?;
+
+        Ok(ConditionalRequired {
+            field,
+            equals,
+            require,
+        })
+    }
+}
+
+/// The field and value of a condition such as `when: { field: enabled, equals: true }`,
+/// which holds when the named property of the object has the given value.
+fn condition<'schema>(
+    yaml: &'schema Yaml,
+    keyword: &'static str,
+) -> Result<(&'schema str, &'schema Yaml), SchemaError<'schema>> {
+    let condition = yaml
+        .lookup(keyword, "hash", Option::from)
+        .map_err(SchemaError::from)?;
+
+    condition
+        .strict_contents(&["field", "equals"], &[])
+        .map_err(SchemaError::from)
+        .map_err(SchemaError::add_path_name(keyword))?;
+
+    let field = condition
+        .lookup("field", "string", Yaml::as_str)
+        .map_err(SchemaError::from)
+        .map_err(SchemaError::add_path_name("field"))
+        .map_err(SchemaError::add_path_name(keyword))?;
+
+    Ok((field, &condition["equals"]))
+}
+
+#[derive(Debug)]
+pub(crate) struct Conditional<'schema> {
+    pub(crate) field: &'schema str,
+    pub(crate) equals: &'schema Yaml,
+    pub(crate) then: PropertyType<'schema>,
+    pub(crate) otherwise: Option<PropertyType<'schema>>,
+}
+
+impl<'schema> Conditional<'schema> {
+    fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_contents(&["if", "then"], &["else"])?;
+
+        let (field, equals) = condition(yaml, "if")?;
+
+        let then =
+            PropertyType::parse(&yaml["then"], ctx).map_err(SchemaError::add_path_name("then"))?;
+
+        let otherwise = yaml
+            .lookup("else", "yaml", Option::from)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|otherwise| PropertyType::parse(otherwise, ctx))
+            .transpose()
+            .map_err(SchemaError::add_path_name("else"))?;
+
+        Ok(Conditional {
+            field,
+            equals,
+            then,
+            otherwise,
+        })
+    }
+}
+
+pub(crate) fn string_list<'schema>(
+    yaml: &'schema Yaml,
+) -> Result<Vec<&'schema str>, SchemaError<'schema>> {
+    SchemaError::condense_errors(&mut yaml.as_type("array", Yaml::as_vec)?.iter().map(
+        |field| -> Result<&'schema str, SchemaError<'schema>> {
+            field
+                .as_type("string", Yaml::as_str)
+                .map_err(SchemaError::from)
+        },
+    ))
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaObject<'schema> {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(&["items"], &["type", "required"])?;
+        SchemaObject::parse(yaml, &Context::default())
+    }
+}
+
+impl<'schema> SchemaObject<'schema> {
+    pub(crate) fn parse(yaml: &'schema Yaml, ctx: &Context) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_schema_contents(&["items"], &type_keywords("object"))?;
 
         let items = yaml.lookup("items", "hash", Yaml::as_hash)?;
 
+        let defaults = items
+            .iter()
+            .filter_map(|(name, property)| {
+                Some((
+                    name.as_str()?,
+                    property.lookup("default", "yaml", Option::from).ok()?,
+                ))
+            })
+            .collect();
+
+        let aliases: BTreeMap<_, _> =
+            SchemaError::condense_errors(&mut items.iter().map(|(name, property)| {
+                let name = name.as_type("string", Yaml::as_str)?;
+                let aliases = property
+                    .lookup("aliases", "array", Option::from)
+                    .map_err(SchemaError::from)
+                    .into_optional()?
+                    .map(string_list)
+                    .transpose()
+                    .map_err(SchemaError::add_path_name("aliases"))
+                    .map_err(SchemaError::add_path_name(name))
+                    .map_err(SchemaError::add_path_name("items"))?
+                    .unwrap_or_default();
+
+                Ok((name, aliases))
+            }))?
+            .into_iter()
+            .filter(|(_, aliases)| !aliases.is_empty())
+            .collect();
+
+        SchemaError::condense_errors(&mut aliases.iter().flat_map(|(name, aliases)| {
+            aliases
+                .iter()
+                .filter(|alias| items.contains_key(&Yaml::String((**alias).to_string())))
+                .map(move |alias| {
+                    Err::<(), _>(
+                        SchemaErrorKind::MalformedField {
+                            error: format!("alias '{}' is already the name of a property", alias),
+                        }
+                        .with_path_name("aliases"),
+                    )
+                    .map_err(SchemaError::add_path_name(name))
+                    .map_err(SchemaError::add_path_name("items"))
+                })
+        }))?;
+
+        let equal_to: BTreeMap<_, _> =
+            SchemaError::condense_errors(&mut items.iter().map(|(name, property)| {
+                let name = name.as_type("string", Yaml::as_str)?;
+                let other = property
+                    .lookup("equalTo", "string", Yaml::as_str)
+                    .map_err(SchemaError::from)
+                    .into_optional()
+                    .and_then(|other| match other {
+                        Some(other) if !items.contains_key(&Yaml::String(other.to_string())) => {
+                            Err(SchemaErrorKind::MalformedField {
+                                error: format!(
+                                    "equalTo refers to '{}', which is not one of the items",
+                                    other
+                                ),
+                            }
+                            .into())
+                        }
+                        other => Ok(other),
+                    })
+                    .map_err(SchemaError::add_path_name("equalTo"))
+                    .map_err(SchemaError::add_path_name(name))
+                    .map_err(SchemaError::add_path_name("items"))?;
+
+                Ok((name, other))
+            }))?
+            .into_iter()
+            .filter_map(|(name, other)| Some((name, other?)))
+            .collect();
+
         let items: BTreeMap<_, _> =
             SchemaError::condense_errors(&mut items.iter().map(|property| {
                 let name = property.0.as_type("string", Yaml::as_str)?;
-                PropertyType::try_from(property.1)
+                PropertyType::parse_property(property.1, ctx)
                     .map_err(SchemaError::add_path_name(name))
                     .map_err(SchemaError::add_path_name("items"))
                     .map(|prop| (name, prop))
@@ -32,66 +237,504 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaObject<'schema> {
             .collect();
 
         let required = yaml
-            .lookup("required", "array", Yaml::as_vec)
+            .lookup("required", "yaml", Option::from)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|fields| string_list(fields).map_err(SchemaError::add_path_name("required")))
+            .transpose()?;
+
+        let require_non_empty = yaml
+            .lookup("requireNonEmpty", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("requireNonEmpty"))
+            .into_optional()?
+            .unwrap_or(false);
+
+        let require_non_blank = yaml
+            .lookup("requireNonBlank", "yaml", Option::from)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(string_list)
+            .transpose()
+            .map_err(SchemaError::add_path_name("requireNonBlank"))?
+            .unwrap_or_default();
+
+        SchemaError::condense_errors(&mut require_non_blank.iter().enumerate().map(
+            |(i, field)| {
+                if required.iter().flatten().any(|required| required == field) {
+                    Ok(())
+                } else {
+                    Err(SchemaErrorKind::MalformedField {
+                        error: format!("'{}' cannot be non-blank without being required", field),
+                    }
+                    .with_path_index(i))
+                }
+            },
+        ))
+        .map_err(SchemaError::add_path_name("requireNonBlank"))?;
+
+        let property_dependencies = yaml
+            .lookup("propertyDependencies", "hash", Yaml::as_hash)
             .map_err(SchemaError::from)
-            .map_err(SchemaError::add_path_name("required"))
+            .map_err(SchemaError::add_path_name("propertyDependencies"))
             .into_optional()?
-            .map(|fields| {
-                SchemaError::condense_errors(&mut fields.iter().map(
-                    |field| -> Result<&'schema str, Self::Error> {
-                        field
-                            .as_type("string", Yaml::as_str)
-                            .map_err(SchemaError::from)
+            .map(|dependencies| {
+                SchemaError::condense_errors(&mut dependencies.iter().map(|(name, values)| {
+                    let name = name.as_type("string", Yaml::as_str)?;
+
+                    if !items.contains_key(name) {
+                        return Err(SchemaErrorKind::MalformedField {
+                            error: format!(
+                                "property dependency '{}' is not one of the items",
+                                name
+                            ),
+                        }
+                        .with_path_name(name));
+                    }
+
+                    let values = SchemaError::condense_errors(
+                        &mut values
+                            .as_type("hash", Yaml::as_hash)
+                            .map_err(SchemaError::from)?
+                            .iter()
+                            .map(|(value, fields)| Ok((value, string_list(fields)?))),
+                    )
+                    .map_err(SchemaError::add_path_name(name))?;
+
+                    Ok((name, values))
+                }))
+                .map_err(SchemaError::add_path_name("propertyDependencies"))
+            })
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let conditional_required = yaml
+            .lookup("conditionalRequired", "array", Yaml::as_vec)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|conditions| {
+                SchemaError::condense_errors(&mut conditions.iter().enumerate().map(
+                    |(i, condition)| {
+                        ConditionalRequired::try_from(condition)
+                            .map_err(SchemaError::add_path_index(i))
+                    },
+                ))
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("conditionalRequired"))?
+            .unwrap_or_default();
+
+        let conditionals = yaml
+            .lookup("conditional", "hash", Yaml::as_hash)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("conditional"))
+            .into_optional()?
+            .map(|conditionals| {
+                SchemaError::condense_errors(&mut conditionals.iter().map(|(name, conditional)| {
+                    let name = name.as_type("string", Yaml::as_str)?;
+
+                    if !items.contains_key(name) {
+                        return Err(SchemaErrorKind::MalformedField {
+                            error: format!(
+                                "conditional property '{}' is not one of the items",
+                                name
+                            ),
+                        }
+                        .with_path_name(name));
+                    }
+
+                    Conditional::parse(conditional, ctx)
+                        .map_err(SchemaError::add_path_name(name))
+                        .map(|conditional| (name, conditional))
+                }))
+                .map_err(SchemaError::add_path_name("conditional"))
+            })
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let unevaluated_properties = yaml
+            .lookup("unevaluatedProperties", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("unevaluatedProperties"))
+            .into_optional()?;
+
+        #[cfg(feature = "regex")]
+        let additional_property_name_pattern = yaml
+            .lookup("additionalPropertyNamePattern", "string", Yaml::as_str)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("additionalPropertyNamePattern"))
+            .into_optional()?
+            .map(|inner| {
+                regex::Regex::new(inner).map_err(|e| {
+                    SchemaErrorKind::MalformedField {
+                        error: format!("{}", e),
+                    }
+                    .with_path_name("additionalPropertyNamePattern")
+                })
+            })
+            .transpose()?;
+
+        #[cfg(feature = "regex")]
+        let pattern_required = yaml
+            .lookup("patternRequired", "array", Yaml::as_vec)
+            .map_err(SchemaError::from)
+            .into_optional()?
+            .map(|patterns| {
+                SchemaError::condense_errors(&mut patterns.iter().enumerate().map(
+                    |(i, pattern)| {
+                        let pattern = pattern.as_type("string", Yaml::as_str)?;
+                        regex::Regex::new(pattern).map_err(|e| {
+                            SchemaErrorKind::MalformedField {
+                                error: format!("{}", e),
+                            }
+                            .with_path_index(i)
+                        })
                     },
                 ))
-            });
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("patternRequired"))?
+            .unwrap_or_default();
 
         Ok(SchemaObject {
             items,
-            required: required.transpose()?,
+            required,
+            require_non_empty,
+            require_non_blank,
+            defaults,
+            aliases,
+            equal_to,
+            property_dependencies,
+            conditional_required,
+            conditionals,
+            unevaluated_properties,
+            #[cfg(feature = "regex")]
+            additional_property_name_pattern,
+            #[cfg(feature = "regex")]
+            pattern_required,
         })
     }
 }
 
-impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaObject<'schema> {
-    fn validate(
+impl<'schema> SchemaObject<'schema> {
+    /// Looks up a property in the document under its own name, or failing that any of its
+    /// aliases, returning the name it was found under alongside its value.
+    pub(crate) fn property<'a>(&self, hash: &'a Hash, name: &'a str) -> Option<(&'a str, &'a Yaml)>
+    where
+        'schema: 'a,
+    {
+        std::iter::once(name)
+            .chain(self.aliases.get(name).into_iter().flatten().copied())
+            .find_map(|key| Some((key, get_str(hash, key)?)))
+    }
+
+    /// The name and schema of the property which a key in the document stands for,
+    /// being either its own name or one of its aliases.
+    fn declaring(&self, key: &str) -> Option<(&'schema str, &PropertyType<'schema>)> {
+        if let Some((name, item)) = self.items.get_key_value(key) {
+            return Some((name, item));
+        }
+
+        let (name, _) = self
+            .aliases
+            .iter()
+            .find(|(_, aliases)| aliases.contains(&key))?;
+
+        self.items
+            .get_key_value(name)
+            .map(|(name, item)| (*name, item))
+    }
+
+    /// Whether the property is declared by the object, under its own name or an alias,
+    /// or is otherwise allowed by it.
+    fn evaluates(&self, name: &str) -> bool {
+        if self.items.contains_key(name)
+            || self.aliases.values().flatten().any(|alias| *alias == name)
+        {
+            return true;
+        }
+
+        #[cfg(feature = "regex")]
+        if let Some(pattern) = &self.additional_property_name_pattern {
+            return pattern.is_match(name);
+        }
+
+        false
+    }
+
+    /// Validates the object alongside the object branches of a sibling allOf, each of which
+    /// only checks the properties it declares. Properties evaluated by none of them are rejected.
+    pub(crate) fn validate_with_all_of<'yaml>(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
-    ) -> Result<(), ValidationError<'yaml>> {
-        yaml.as_type("hash", Yaml::as_hash)?;
+        opts: &ValidationOptions,
+        all_of: &SchemaAllOf<'schema>,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        let hash = yaml.as_type("hash", Yaml::as_hash)?;
+
+        let (branches, others) = all_of.partition_objects(ctx);
+        let objects: Vec<&SchemaObject<'schema>> = std::iter::once(self).chain(branches).collect();
+
+        let mut errors: Vec<ValidationError<'yaml>> = hash
+            .keys()
+            .filter_map(Yaml::as_str)
+            .filter(|name| !objects.iter().any(|object| object.evaluates(name)))
+            .map(|field| ValidationErrorKind::ExtraField { field }.into())
+            .collect();
+
+        errors.extend(
+            objects
+                .iter()
+                .filter_map(|object| object.validate_properties(ctx, yaml, opts, true).err()),
+        );
+
+        errors.extend(
+            others
+                .into_iter()
+                .filter_map(|schema| schema.validate_with(ctx, yaml, opts).err()),
+        );
+
+        ValidationError::condense_errors(&mut errors.into_iter().map(Err::<(), _>))?;
+        Ok(())
+    }
+
+    fn validate_properties<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+        allow_unevaluated: bool,
+    ) -> Result<(), ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        let hash = yaml.as_type("hash", Yaml::as_hash)?;
+
+        // Whether properties declared nowhere in the schema are accepted.
+        #[allow(unused_mut)]
+        let mut unrestricted = allow_unevaluated;
+
+        // Errors about the names of properties are collected rather than returned right
+        // away, so that they do not hide any missing required fields.
+        #[allow(unused_mut)]
+        let mut name_errors: Vec<ValidationError<'yaml>> = Vec::new();
+
+        #[cfg(feature = "regex")]
+        if let Some(pattern) = &self.additional_property_name_pattern {
+            // Names not matching the pattern are reported here, rather than as extra fields.
+            name_errors.extend(
+                hash.keys()
+                    .filter_map(Yaml::as_str)
+                    .filter(|name| !self.items.contains_key(name) && !pattern.is_match(name))
+                    .map(|name| {
+                        ValidationErrorKind::ValidationError {
+                            error: MessageId::AdditionalPropertyName,
+                        }
+                        .with_path_name(name)
+                    }),
+            );
+
+            unrestricted = true;
+        }
+
+        #[cfg(feature = "regex")]
+        name_errors.extend(
+            self.pattern_required
+                .iter()
+                .filter(|pattern| {
+                    !hash
+                        .keys()
+                        .filter_map(Yaml::as_str)
+                        .any(|name| pattern.is_match(name))
+                })
+                .map(|pattern| {
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::PatternRequired,
+                        args: vec![("pattern", pattern.to_string())],
+                    }
+                    .into()
+                }),
+        );
+
+        // Fields which are missing or not part of the schema are reported ahead
+        // of any errors within the properties, rather than buried among them.
+        let missing = self
+            .required
+            .iter()
+            .flatten()
+            // Fields given under one of their aliases are not missing.
+            .filter(|field| self.property(hash, field).is_none())
+            .map(|field| ValidationErrorKind::FieldMissing { field }.into());
+
+        let extra = hash
+            .keys()
+            .filter_map(Yaml::as_str)
+            .filter(|field| {
+                !unrestricted
+                    && !self.evaluates(field)
+                    && !self
+                        .required
+                        .iter()
+                        .flatten()
+                        .any(|required| required == field)
+            })
+            .map(|field| ValidationErrorKind::ExtraField { field }.into());
 
-        let items: Vec<&'schema str> = self.items.keys().copied().collect();
-        let required = self.required.as_ref().cloned().unwrap_or_default();
-        yaml.strict_contents(&required, &items)?;
+        // Nothing is allocated unless an error is found.
+        let mut errors: Vec<ValidationError<'yaml>> = missing.chain(extra).collect();
 
-        let mut errors = self.items.iter().map(|(name, schema_item)| {
-            let item = yaml
-                .lookup(name, "yaml", Option::from)
-                .into_optional()
-                .map(Option::Some)
-                .map_err(ValidationError::from)
-                .map_err(ValidationError::add_path_name(name))?
-                .flatten();
+        errors.extend(name_errors);
 
-            if let Some(item) = item {
-                schema_item
-                    .validate(ctx, item)
-                    .map_err(ValidationError::add_path_name(name))?;
+        errors.extend(
+            self.required
+                .iter()
+                .flatten()
+                .filter(|field| match self.property(hash, field) {
+                    Some((_, Yaml::String(value))) => {
+                        (self.require_non_empty && value.is_empty())
+                            || (self.require_non_blank.contains(field) && value.trim().is_empty())
+                    }
+                    Some((_, Yaml::Array(value))) => self.require_non_empty && value.is_empty(),
+                    Some((_, Yaml::Hash(value))) => self.require_non_empty && value.is_empty(),
+                    _ => false,
+                })
+                .map(|field| ValidationErrorKind::FieldMissing { field }.into()),
+        );
+
+        errors.extend(self.aliases.iter().flat_map(|(name, aliases)| {
+            aliases
+                .iter()
+                .filter(move |alias| {
+                    get_str(hash, name).is_some() && get_str(hash, alias).is_some()
+                })
+                .map(move |alias| {
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::AliasConflict,
+                        args: vec![("name", name.to_string())],
+                    }
+                    .with_path_name(alias)
+                })
+        }));
+
+        if let Some(coverage) = opts.coverage {
+            for name in self.items.keys() {
+                if self.property(hash, name).is_some() {
+                    coverage.record_property(ctx, self, name);
+                }
+            }
+        }
+
+        // Properties are validated in the order they appear in the document, so that
+        // errors within them are reported in that order too.
+        errors.extend(hash.iter().filter_map(|(key, item)| {
+            let key = key.as_str()?;
+            let (name, schema_item) = self.declaring(key)?;
+
+            // Values given under an alias are ignored when the property is also given
+            // under its own name, which is reported as an error above.
+            if key != name && self.property(hash, name)?.0 != key {
+                return None;
             }
 
-            Ok(())
-        });
+            // Null values are passed on to the property's schema, rather than
+            // treated as missing, so that only nullable properties accept them.
+            schema_item
+                .validate_with(ctx, item, opts)
+                .map_err(ValidationError::add_path_name(key))
+                .err()
+        }));
+
+        errors.extend(self.equal_to.iter().filter_map(|(name, other)| {
+            let (key, value) = self.property(hash, name)?;
+            let (other_key, other_value) = self.property(hash, other)?;
+
+            if value == other_value {
+                return None;
+            }
+
+            Some(
+                ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::NotEqualTo,
+                    args: vec![("key", key.to_string()), ("other", other_key.to_string())],
+                }
+                .with_path_name(key),
+            )
+        }));
+
+        errors.extend(self.conditionals.iter().filter_map(|(name, conditional)| {
+            let (key, item) = self.property(hash, name)?;
+            let holds = self
+                .property(hash, conditional.field)
+                .map(|(_, value)| value)
+                == Some(conditional.equals);
+
+            let schema = if holds {
+                Some(&conditional.then)
+            } else {
+                conditional.otherwise.as_ref()
+            }?;
+
+            schema
+                .validate_with(ctx, item, opts)
+                .map_err(ValidationError::add_path_name(key))
+                .err()
+        }));
+
+        // Fields required by the values of other properties are reported alongside
+        // every other error, so that a single pass lists all missing fields.
+        errors.extend(
+            self.property_dependencies
+                .iter()
+                .filter_map(|(name, values)| {
+                    let (_, value) = self.property(hash, name)?;
+                    values.iter().find(|(expected, _)| *expected == value)
+                })
+                .flat_map(|(_, fields)| fields.iter())
+                .chain(
+                    self.conditional_required
+                        .iter()
+                        .filter(|condition| {
+                            self.property(hash, condition.field).map(|(_, value)| value)
+                                == Some(condition.equals)
+                        })
+                        .flat_map(|condition| condition.require.iter()),
+                )
+                .filter(|field| self.property(hash, field).is_none())
+                .map(|field| ValidationErrorKind::FieldMissing { field }.into()),
+        );
 
-        ValidationError::condense_errors(&mut errors)?;
+        ValidationError::condense_errors(&mut errors.into_iter().map(Err::<(), _>))?;
         Ok(())
     }
 }
 
+impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaObject<'schema> {
+    fn validate_with(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> Result<(), ValidationError<'yaml>> {
+        let allow_unevaluated = self
+            .unevaluated_properties
+            .unwrap_or(ctx.permissive_objects);
+
+        self.validate_properties(ctx, yaml, opts, allow_unevaluated)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::errors::ValidationErrorKind;
+    use crate::errors::MessageId;
     use crate::utils::load_simple;
     use crate::{SchemaErrorKind, SchemaObject};
 
@@ -341,6 +984,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_in_document_order() {
+        let yaml = load_simple(
+            r#"
+            items:
+              alpha:
+                type: string
+              beta:
+                type: string
+                aliases: [oldBeta]
+              gamma:
+                type: string
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+        let wrong_type = |name| {
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer",
+            }
+            .with_path_name(name)
+        };
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("gamma: 1\nalpha: 2\noldBeta: 3"),
+                )
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    wrong_type("gamma"),
+                    wrong_type("alpha"),
+                    wrong_type("oldBeta")
+                ]
+            }
+            .into()
+        );
+    }
+
     #[test]
     fn validate_optional() {
         let yaml = load_simple(
@@ -424,4 +1109,862 @@ mod tests {
             ValidationErrorKind::FieldMissing { field: "world" }.into()
         );
     }
+
+    #[test]
+    fn validate_nullable() {
+        let yaml = load_simple(
+            r#"
+            items:
+              hello:
+                type: string
+                nullable: true
+              world:
+                type: string
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("hello: ~\nworld: text"))
+            .unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("hello: text"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("world: ~"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "null"
+            }
+            .with_path_name("world")
+        );
+    }
+
+    #[test]
+    fn validate_null_is_not_missing() {
+        let yaml = load_simple(
+            r#"
+            items:
+              hello:
+                type: string
+                nullable: true
+              world:
+                type: string
+            required:
+              - hello
+              - world
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        // A null value counts as given, so required nullable properties may be null.
+        schema
+            .validate(&Context::default(), &load_simple("hello: ~\nworld: text"))
+            .unwrap();
+
+        // Required properties which are not nullable report the null as a wrong type,
+        // rather than as a missing field.
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("hello: text\nworld: ~"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "null"
+            }
+            .with_path_name("world")
+        );
+    }
+
+    #[test]
+    fn validate_property_dependencies() {
+        let yaml = load_simple(
+            r#"
+            items:
+              type:
+                type: string
+              bucket:
+                type: string
+              path:
+                type: string
+            propertyDependencies:
+              type:
+                s3:
+                  - bucket
+                local:
+                  - path
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("type: s3\nbucket: logs"))
+            .unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("type: local\npath: /logs"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("type: s3\npath: /logs"))
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "bucket" }.into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("type: local\nbucket: logs")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "path" }.into()
+        );
+
+        // Missing dependencies are reported together with errors within the properties.
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("type: s3\npath: 10"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::WrongType {
+                        expected: "string",
+                        actual: "integer"
+                    }
+                    .with_path_name("path"),
+                    ValidationErrorKind::FieldMissing { field: "bucket" }.into()
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn malformed_property_dependencies() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              type:
+                type: string
+            propertyDependencies:
+              type:
+                s3: bucket
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "array",
+                actual: "string"
+            }
+            .with_path(breadcrumb!["type", "propertyDependencies"]),
+        );
+
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              bucket:
+                type: string
+            propertyDependencies:
+              type:
+                s3:
+                  - bucket
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "property dependency 'type' is not one of the items".into()
+            }
+            .with_path(breadcrumb!["type", "propertyDependencies"]),
+        );
+    }
+
+    #[test]
+    fn validate_require_non_empty() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+              tags:
+                type: array
+              labels:
+                type: hash
+            required:
+              - name
+              - tags
+              - labels
+            requireNonEmpty: true
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("name: John\ntags: [a]\nlabels: { a: b }"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("name: ''\ntags: [a]\nlabels: { a: b }")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "name" }.into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("name: John\ntags: []\nlabels: { a: b }")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "tags" }.into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("name: John\ntags: [a]\nlabels: {}")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "labels" }.into()
+        );
+    }
+
+    #[test]
+    fn validate_require_non_blank() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+              nickname:
+                type: string
+            required:
+              - name
+              - nickname
+            requireNonBlank:
+              - name
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("name: ' John '\nnickname: '  '"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("name: \" \\t \"\nnickname: Johnny")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "name" }.into()
+        );
+
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+                items:
+                  name:
+                    type: string
+                requireNonBlank:
+                  - name
+                "#
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "'name' cannot be non-blank without being required".into()
+            }
+            .with_path(breadcrumb![0, "requireNonBlank"])
+        );
+    }
+
+    #[test]
+    fn validate_empty_required_fields() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+            required:
+              - name
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("name: ''"))
+            .unwrap();
+    }
+
+    #[test]
+    fn validate_conditional_required() {
+        let yaml = load_simple(
+            r#"
+            items:
+              enabled:
+                type: boolean
+              config:
+                type: string
+            conditionalRequired:
+              - when:
+                  field: enabled
+                  equals: true
+                require:
+                  - config
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("enabled: false"))
+            .unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple("enabled: true\nconfig: debug"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("enabled: true"))
+                .unwrap_err(),
+            ValidationErrorKind::FieldMissing { field: "config" }.into()
+        );
+    }
+
+    #[test]
+    fn malformed_conditional_required() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              enabled:
+                type: boolean
+            conditionalRequired:
+              - when:
+                  equals: true
+                require:
+                  - config
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::FieldMissing { field: "field" }.with_path(breadcrumb![
+                "when",
+                0usize,
+                "conditionalRequired"
+            ]),
+        );
+
+        // Conditions are not schemas, and so do not accept keywords such as title.
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              enabled:
+                type: boolean
+            conditionalRequired:
+              - when:
+                  field: enabled
+                  equals: true
+                  title: Enabled
+                require:
+                  - config
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::ExtraField { field: "title" }.with_path(breadcrumb![
+                "when",
+                0usize,
+                "conditionalRequired"
+            ]),
+        );
+
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              kind:
+                type: string
+            conditional:
+              kind:
+                if:
+                  field: kind
+                  equals: code
+                then:
+                  type: string
+                $comment: only codes
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::ExtraField { field: "$comment" }
+                .with_path(breadcrumb!["kind", "conditional"]),
+        );
+    }
+
+    #[test]
+    fn validate_conditional() {
+        let yaml = load_simple(
+            r#"
+            items:
+              kind:
+                type: string
+              value:
+                type: string
+            conditional:
+              value:
+                if:
+                  field: kind
+                  equals: code
+                then:
+                  type: string
+                  minLength: 6
+                else:
+                  type: string
+                  maxLength: 5
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema
+            .validate(&ctx, &load_simple("kind: code\nvalue: ABCDEFG"))
+            .unwrap();
+
+        schema
+            .validate(&ctx, &load_simple("kind: label\nvalue: abc"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("kind: code\nvalue: abc"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MinLength
+            }
+            .with_path_name("value")
+        );
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("kind: label\nvalue: ABCDEFG"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxLength
+            }
+            .with_path_name("value")
+        );
+    }
+
+    #[test]
+    fn malformed_conditional() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              kind:
+                type: string
+            conditional:
+              value:
+                if:
+                  field: kind
+                  equals: code
+                then:
+                  type: integer
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "conditional property 'value' is not one of the items".into()
+            }
+            .with_path(breadcrumb!["value", "conditional"]),
+        );
+    }
+
+    #[test]
+    fn validate_equal_to() {
+        let yaml = load_simple(
+            r#"
+            items:
+              password:
+                type: string
+              confirmPassword:
+                type: string
+                equalTo: password
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+        let ctx = Context::default();
+
+        schema
+            .validate(
+                &ctx,
+                &load_simple("password: hunter2\nconfirmPassword: hunter2"),
+            )
+            .unwrap();
+
+        schema
+            .validate(&ctx, &load_simple("password: hunter2"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &ctx,
+                    &load_simple("password: hunter2\nconfirmPassword: hunter3"),
+                )
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotEqualTo,
+                args: vec![
+                    ("key", "confirmPassword".into()),
+                    ("other", "password".into())
+                ]
+            }
+            .with_path_name("confirmPassword")
+        );
+    }
+
+    #[test]
+    fn malformed_equal_to() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+            items:
+              confirmPassword:
+                type: string
+                equalTo: password
+        "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "equalTo refers to 'password', which is not one of the items".into()
+            }
+            .with_path(breadcrumb!["equalTo", "confirmPassword", "items"]),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_additional_property_names() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+            additionalPropertyNamePattern: "^x-"
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple(
+                    r#"
+                    name: service
+                    x-owner: infra
+                    "#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple(
+                        r#"
+                        name: service
+                        x-owner: infra
+                        owner: infra
+                        "#,
+                    ),
+                )
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::AdditionalPropertyName
+            }
+            .with_path_name("owner")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn malformed_additional_property_name_pattern() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+                items:
+                  name:
+                    type: string
+                additionalPropertyNamePattern: "x-("
+                "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "regex parse error:\n    x-(\n      ^\nerror: unclosed group".into()
+            }
+            .with_path_name("additionalPropertyNamePattern")
+        );
+    }
+
+    #[test]
+    fn validate_missing_fields_first() {
+        let yaml = load_simple(
+            r#"
+            items:
+              age:
+                type: integer
+              name:
+                type: string
+              email:
+                type: string
+            required:
+              - email
+              - name
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("age: old"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::FieldMissing { field: "email" }.into(),
+                    ValidationErrorKind::FieldMissing { field: "name" }.into(),
+                    ValidationErrorKind::WrongType {
+                        expected: "integer",
+                        actual: "string"
+                    }
+                    .with_path_name("age"),
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_all_missing_fields() {
+        let yaml = load_simple(
+            r#"
+            items:
+              owner:
+                type: object
+                items:
+                  name:
+                    type: string
+                  email:
+                    type: string
+                  phone:
+                    type: string
+                  age:
+                    type: integer
+                required: [name, email, phone]
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("owner:\n  age: 30"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::FieldMissing { field: "name" }.into(),
+                    ValidationErrorKind::FieldMissing { field: "email" }.into(),
+                    ValidationErrorKind::FieldMissing { field: "phone" }.into(),
+                ]
+            }
+            .with_path_name("owner")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_missing_fields_alongside_names() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+              email:
+                type: string
+              phone:
+                type: string
+            required: [name, email, phone]
+            additionalPropertyNamePattern: "^x-"
+            patternRequired: ["^x-"]
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("extra: 1"))
+                .unwrap_err(),
+            ValidationErrorKind::Multiple {
+                errors: vec![
+                    ValidationErrorKind::FieldMissing { field: "name" }.into(),
+                    ValidationErrorKind::FieldMissing { field: "email" }.into(),
+                    ValidationErrorKind::FieldMissing { field: "phone" }.into(),
+                    ValidationErrorKind::ValidationError {
+                        error: MessageId::AdditionalPropertyName
+                    }
+                    .with_path_name("extra"),
+                    ValidationErrorKind::ParameterizedValidation {
+                        error: MessageId::PatternRequired,
+                        args: vec![("pattern", "^x-".into())]
+                    }
+                    .into(),
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_pattern_required() {
+        let yaml = load_simple(
+            r#"
+            items:
+              name:
+                type: string
+            additionalPropertyNamePattern: "^handler_"
+            patternRequired:
+              - "^handler_"
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(
+                &Context::default(),
+                &load_simple(
+                    r#"
+                    name: service
+                    handler_http: yes
+                    "#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("name: service"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::PatternRequired,
+                args: vec![("pattern", "^handler_".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_aliases() {
+        let yaml = load_simple(
+            r#"
+            items:
+              fullName:
+                type: string
+                aliases: [name]
+              age:
+                type: integer
+            required: [fullName]
+            "#,
+        );
+
+        let schema = SchemaObject::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("name: John\nage: 30"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("name: 30"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path_name("name")
+        );
+
+        assert_eq!(
+            schema
+                .validate(
+                    &Context::default(),
+                    &load_simple("fullName: John\nname: John")
+                )
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::AliasConflict,
+                args: vec![("name", "fullName".into())]
+            }
+            .with_path_name("name")
+        );
+    }
+
+    #[test]
+    fn alias_of_existing_property() {
+        assert_eq!(
+            SchemaObject::try_from(&load_simple(
+                r#"
+                items:
+                  fullName:
+                    type: string
+                    aliases: [name]
+                  name:
+                    type: string
+                "#,
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "alias 'name' is already the name of a property".into()
+            }
+            .with_path(breadcrumb!["aliases", "fullName", "items"])
+        );
+    }
 }
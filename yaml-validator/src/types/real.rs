@@ -1,31 +1,38 @@
+#[cfg(feature = "decimal")]
+use crate::decimal::Decimal;
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
 use crate::errors::{SchemaError, SchemaErrorKind};
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{Limit, OptionalLookup, YamlUtils};
-use crate::{Context, Validate};
+use crate::utils::{
+    enum_error, lookup_divisors, lookup_enum, type_keywords, Limit, OptionalLookup, YamlUtils,
+};
+use crate::{Context, Validate, ValidationOptions};
+#[cfg(feature = "decimal")]
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
+/// Schema for `type: real` values. Built from a YAML schema node using [TryFrom].
 #[derive(Debug, Default)]
-pub(crate) struct SchemaReal {
-    minimum: Option<Limit<f64>>,
-    maximum: Option<Limit<f64>>,
-    multiple_of: Option<f64>,
+pub struct SchemaReal {
+    pub(crate) minimum: Option<Limit<f64>>,
+    pub(crate) maximum: Option<Limit<f64>>,
+    // Every one of these must divide the value.
+    pub(crate) multiple_of: Vec<f64>,
+    pub(crate) enumeration: Option<Vec<f64>>,
+    // Each multipleOf value as written in the schema, for checking it exactly.
+    #[cfg(feature = "decimal")]
+    exact_multiple_of: Vec<Option<Decimal>>,
+    // The bounds as written in the schema, for comparing against them exactly.
+    #[cfg(feature = "decimal")]
+    exact_minimum: Option<Decimal>,
+    #[cfg(feature = "decimal")]
+    exact_maximum: Option<Decimal>,
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaReal {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        yaml.strict_contents(
-            &[],
-            &[
-                "type",
-                "minimum",
-                "exclusiveMinimum",
-                "maximum",
-                "exclusiveMaximum",
-                "multipleOf",
-            ],
-        )?;
+        yaml.strict_schema_contents(&[], &type_keywords("real"))?;
 
         yaml.check_exclusive_fields(&["minimum", "exclusiveMinimum"])?;
         yaml.check_exclusive_fields(&["maximum", "exclusiveMaximum"])?;
@@ -52,20 +59,7 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaReal {
                 .map(Limit::Exclusive)
                 .into_optional()?);
 
-        let multiple_of = yaml
-            .lookup("multipleOf", "real", Yaml::as_f64)
-            .map_err(SchemaError::from)
-            .and_then(|number| {
-                if number <= 0.0 {
-                    Err(SchemaErrorKind::MalformedField {
-                        error: "must be greater than zero".into(),
-                    }
-                    .with_path_name("multipleOf"))
-                } else {
-                    Ok(number)
-                }
-            })
-            .into_optional()?;
+        let divisors = lookup_divisors(yaml, "real", Yaml::as_f64)?;
 
         if let (Some(lower), Some(upper)) = (&minimum, &maximum) {
             if !lower.has_span(upper) {
@@ -76,46 +70,157 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaReal {
             }
         }
 
+        let enumeration = lookup_enum(yaml, "real", Yaml::as_f64)?;
+
+        #[cfg(feature = "decimal")]
+        let exact_multiple_of = divisors
+            .iter()
+            .map(|(_, node)| match node {
+                Yaml::Real(source) => Decimal::parse(source),
+                _ => None,
+            })
+            .collect();
+
+        #[cfg(feature = "decimal")]
+        let exact = |keys: [&str; 2]| {
+            keys.iter().find_map(|key| match &yaml[*key] {
+                Yaml::Real(source) => Decimal::parse(source),
+                _ => None,
+            })
+        };
+
         Ok(SchemaReal {
             minimum,
             maximum,
-            multiple_of,
+            multiple_of: divisors.into_iter().map(|(divisor, _)| divisor).collect(),
+            enumeration,
+            #[cfg(feature = "decimal")]
+            exact_multiple_of,
+            #[cfg(feature = "decimal")]
+            exact_minimum: exact(["minimum", "exclusiveMinimum"]),
+            #[cfg(feature = "decimal")]
+            exact_maximum: exact(["maximum", "exclusiveMaximum"]),
         })
     }
 }
 
+impl SchemaReal {
+    /// The lower bound given by `minimum` or `exclusiveMinimum`, if any.
+    pub fn minimum(&self) -> Option<&Limit<f64>> {
+        self.minimum.as_ref()
+    }
+
+    /// The upper bound given by `maximum` or `exclusiveMaximum`, if any.
+    pub fn maximum(&self) -> Option<&Limit<f64>> {
+        self.maximum.as_ref()
+    }
+
+    /// The value given by `multipleOf` or `divisibleBy`, if it is a single divisor.
+    pub fn multiple_of(&self) -> Option<f64> {
+        match self.multiple_of[..] {
+            [divisor] => Some(divisor),
+            _ => None,
+        }
+    }
+
+    /// Every divisor given by `multipleOf` or `divisibleBy`, all of which must divide the value.
+    pub fn multiples_of(&self) -> &[f64] {
+        &self.multiple_of
+    }
+
+    /// Whether the divisor at the given position divides the value. Values are compared using
+    /// their exact decimal representation when possible, falling back to floating point for
+    /// values too large or precise to fit.
+    #[cfg(feature = "decimal")]
+    fn divides(&self, i: usize, yaml: &Yaml, value: f64) -> bool {
+        match (&self.exact_multiple_of[i], yaml) {
+            (Some(divisor), Yaml::Real(source)) => {
+                Decimal::parse(source).and_then(|value| value.is_multiple_of(divisor))
+            }
+            _ => None,
+        }
+        .unwrap_or_else(|| value.rem_euclid(self.multiple_of[i]) == 0.0)
+    }
+
+    /// Whether the divisor at the given position divides the value.
+    #[cfg(not(feature = "decimal"))]
+    fn divides(&self, i: usize, _: &Yaml, value: f64) -> bool {
+        value.rem_euclid(self.multiple_of[i]) == 0.0
+    }
+
+    /// Whether the value is within the lower bound, compared exactly when possible, as with `divides`.
+    #[cfg(feature = "decimal")]
+    fn above_minimum(&self, minimum: &Limit<f64>, yaml: &Yaml, value: f64) -> bool {
+        match compare_exact(yaml, &self.exact_minimum) {
+            Some(ordering) if minimum.is_inclusive() => ordering != Ordering::Less,
+            Some(ordering) => ordering == Ordering::Greater,
+            None => minimum.is_greater(&value),
+        }
+    }
+
+    /// Whether the value is within the upper bound, compared exactly when possible, as with `divides`.
+    #[cfg(feature = "decimal")]
+    fn below_maximum(&self, maximum: &Limit<f64>, yaml: &Yaml, value: f64) -> bool {
+        match compare_exact(yaml, &self.exact_maximum) {
+            Some(ordering) if maximum.is_inclusive() => ordering != Ordering::Greater,
+            Some(ordering) => ordering == Ordering::Less,
+            None => maximum.is_lesser(&value),
+        }
+    }
+
+    /// Whether the value is within the lower bound.
+    #[cfg(not(feature = "decimal"))]
+    fn above_minimum(&self, minimum: &Limit<f64>, _: &Yaml, value: f64) -> bool {
+        minimum.is_greater(&value)
+    }
+
+    /// Whether the value is within the upper bound.
+    #[cfg(not(feature = "decimal"))]
+    fn below_maximum(&self, maximum: &Limit<f64>, _: &Yaml, value: f64) -> bool {
+        maximum.is_lesser(&value)
+    }
+}
+
+/// Compares the value as written in the document to a bound as written in the schema.
+#[cfg(feature = "decimal")]
+fn compare_exact(yaml: &Yaml, limit: &Option<Decimal>) -> Option<Ordering> {
+    match (yaml, limit) {
+        (Yaml::Real(source), Some(limit)) => Decimal::parse(source)?.compare(limit),
+        _ => None,
+    }
+}
+
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaReal {
-    fn validate(
+    fn validate_with(
         &self,
         _: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        _: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         let value = yaml.as_type("real", Yaml::as_f64)?;
 
         if let Some(minimum) = &self.minimum {
-            if !minimum.is_greater(&value) {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value violates lower limit constraint",
-                }
-                .into());
+            if !self.above_minimum(minimum, yaml, value) {
+                return Err(minimum.lower_violation(&value).into());
             }
         }
 
         if let Some(maximum) = &self.maximum {
-            if !maximum.is_lesser(&value) {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value violates upper limit constraint",
-                }
-                .into());
+            if !self.below_maximum(maximum, yaml, value) {
+                return Err(maximum.upper_violation(&value).into());
             }
         }
 
-        if let Some(multiple_of) = &self.multiple_of {
-            if value.rem_euclid(*multiple_of) != 0.0 {
-                return Err(ValidationErrorKind::ValidationError {
-                    error: "value must be a multiple of the multipleOf field",
-                }
-                .into());
+        if !(0..self.multiple_of.len()).all(|i| self.divides(i, yaml, value)) {
+            return Err(ValidationErrorKind::ValidationError {
+                error: MessageId::MultipleOf,
+            }
+            .into());
+        }
+
+        if let Some(enumeration) = &self.enumeration {
+            if !enumeration.contains(&value) {
+                return Err(enum_error(value, enumeration));
             }
         }
 
@@ -203,6 +308,23 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn read_limits() {
+        let schema = SchemaReal::try_from(&load_simple(
+            r#"
+                type: real
+                exclusiveMaximum: 12.0
+                minimum: 10.0
+                multipleOf: 0.5
+            "#,
+        ))
+        .unwrap();
+
+        assert_eq!(schema.minimum(), Some(&Limit::Inclusive(10.0)));
+        assert_eq!(schema.maximum(), Some(&Limit::Exclusive(12.0)));
+        assert_eq!(schema.multiple_of(), Some(0.5));
+    }
+
     #[test]
     fn with_zero_width_limits() {
         assert_eq!(
@@ -308,8 +430,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("10.0"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates lower limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ExclusiveLowerLimit,
+                args: vec![("value", "10".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -344,8 +467,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("5.0"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates lower limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::LowerLimit,
+                args: vec![("value", "5".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -365,8 +489,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("10.0"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates upper limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ExclusiveUpperLimit,
+                args: vec![("value", "10".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -401,8 +526,9 @@ mod tests {
             schema
                 .validate(&Context::default(), &load_simple("20.0"))
                 .unwrap_err(),
-            ValidationErrorKind::ValidationError {
-                error: "value violates upper limit constraint"
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::UpperLimit,
+                args: vec![("value", "20".into()), ("limit", "10".into())]
             }
             .into()
         );
@@ -423,7 +549,7 @@ mod tests {
                 .validate(&Context::default(), &load_simple("10.0"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "value must be a multiple of the multipleOf field"
+                error: MessageId::MultipleOf
             }
             .into()
         );
@@ -483,4 +609,98 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn validate_enum() {
+        let yaml = load_simple("enum: [0.5, 1.5]");
+        let schema = SchemaReal::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("1.5"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("2.5"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::NotInEnum,
+                args: vec![("value", "2.5".into()), ("allowed", "0.5, 1.5".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_multiple_divisors() {
+        let schema = SchemaReal::try_from(&load_simple("divisibleBy: [0.5, 1.5]")).unwrap();
+        assert_eq!(schema.multiple_of(), None);
+
+        schema
+            .validate(&Context::default(), &load_simple("3.0"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("2.0"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MultipleOf
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn validate_exact_multiple_of() {
+        let yaml = load_simple("multipleOf: 0.1");
+        let schema = SchemaReal::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("0.3"))
+            .unwrap();
+
+        // Zero is a multiple of everything, however large its exponent.
+        schema
+            .validate(&Context::default(), &load_simple("0e999999999"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("0.35"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MultipleOf
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn validate_exact_bounds() {
+        let yaml = load_simple("maximum: 0.3\nexclusiveMinimum: 0.1");
+        let schema = SchemaReal::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("0.3"))
+            .unwrap();
+
+        // Equal to the bound as a float, but just above it as written.
+        schema
+            .validate(&Context::default(), &load_simple("0.10000000000000001"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("0.30000000000000001"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::UpperLimit,
+                args: vec![("value", "0.3".into()), ("limit", "0.3".into())]
+            }
+            .into()
+        );
+    }
 }
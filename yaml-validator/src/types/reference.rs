@@ -1,23 +1,269 @@
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::{Context, Validate};
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
+use crate::{Context, PropertyType, Schema, Validate, ValidationOptions};
+use std::cell::RefCell;
+use std::sync::OnceLock;
 use yaml_rust::Yaml;
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub(crate) struct SchemaReference<'schema> {
     pub(crate) uri: &'schema str,
+    // Path to a node within the referenced schema, as given after the '#' of the reference.
+    pub(crate) pointer: Option<&'schema str>,
+    // The id of the context the schema was first looked up in, and its position within it.
+    // A OnceLock rather than a Cell, so that schemas can still be shared between threads.
+    resolved: OnceLock<(usize, usize)>,
+}
+
+// References are printed by uri, never by what they resolve to, so that printing
+// schemas which refer to each other terminates. The cached lookup is left out as well.
+impl<'schema> std::fmt::Debug for SchemaReference<'schema> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaReference")
+            .field("uri", &self.uri)
+            .field("pointer", &self.pointer)
+            .finish()
+    }
+}
+
+impl<'schema> std::fmt::Display for SchemaReference<'schema> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pointer {
+            Some(pointer) => write!(f, "{}#{}", self.uri, pointer),
+            None => write!(f, "{}", self.uri),
+        }
+    }
+}
+
+impl<'schema> SchemaReference<'schema> {
+    /// Create a reference to the schema with the given uri, or to a node within it if the
+    /// uri is followed by a pointer such as `person#/items/address`. A reference consisting of
+    /// only a pointer refers to the schema it is part of, once anchored using [anchor_local_references].
+    pub(crate) fn new(reference: &'schema str) -> Self {
+        let (uri, pointer) = match reference.split_once('#') {
+            Some((uri, pointer)) => (uri, Some(pointer)),
+            None => (reference, None),
+        };
+
+        SchemaReference {
+            uri,
+            pointer,
+            resolved: OnceLock::new(),
+        }
+    }
+
+    /// Look up the referenced schema in the context, so it can be reused when validating
+    /// many values against the same reference.
+    ///
+    /// Since schemas never move within a context, the position of the schema within the first
+    /// context it is looked up in is remembered, and the lookup only repeated for other contexts.
+    pub(crate) fn resolve<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+    ) -> Result<&'schema Schema<'schema>, ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        if let Some((id, index)) = self.resolved.get() {
+            if *id == ctx.id() {
+                return Ok(ctx.schema_at(*index));
+            }
+        }
+
+        let (index, schema) = ctx
+            .find_schema(self.uri)
+            .ok_or(ValidationErrorKind::UnknownSchema { uri: self.uri })?;
+
+        // Lookups in any other context than the first are simply not remembered.
+        let _ = self.resolved.set((ctx.id(), index));
+        Ok(schema)
+    }
+
+    /// Like [resolve](SchemaReference::resolve), but following the pointer of the reference
+    /// to the node within the schema, if it has one.
+    pub(crate) fn resolve_node<'yaml>(
+        &self,
+        ctx: &'schema Context<'schema>,
+    ) -> Result<&'schema PropertyType<'schema>, ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        let schema = self.resolve(ctx)?;
+
+        match self.pointer {
+            None => Ok(&schema.schema),
+            Some(pointer) => find_node(&schema.schema, pointer).ok_or_else(|| {
+                ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::PointerNotFound,
+                    args: vec![
+                        ("pointer", pointer.to_string()),
+                        ("uri", self.uri.to_string()),
+                    ],
+                }
+                .into()
+            }),
+        }
+    }
+}
+
+/// Finds the node at a pointer such as `/items/address`, made up of the keywords
+/// leading to it from the given node, and the names or indices following them.
+pub(crate) fn find_node<'a, 'schema>(
+    node: &'a PropertyType<'schema>,
+    pointer: &str,
+) -> Option<&'a PropertyType<'schema>> {
+    let segments: Vec<&str> = pointer.split('/').filter(|s| !s.is_empty()).collect();
+    walk(node, &segments)
+}
+
+fn walk<'a, 'schema>(
+    node: &'a PropertyType<'schema>,
+    segments: &[&str],
+) -> Option<&'a PropertyType<'schema>> {
+    let index = |i: &str| i.parse::<usize>().ok();
+
+    match (node, segments) {
+        (_, []) => Some(node),
+        (PropertyType::Nullable(inner), _) => walk(inner, segments),
+        (PropertyType::Composite { base, modifiers }, _) => base
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(modifiers)
+            .find_map(|node| walk(node, segments)),
+        (PropertyType::Object(object), ["items", name, rest @ ..]) => {
+            walk(object.items.get(*name)?, rest)
+        }
+        (PropertyType::Array(array), ["items", rest @ ..]) => walk(array.items.as_ref()?, rest),
+        (PropertyType::Array(array), ["contains", rest @ ..]) => {
+            walk(array.contains.as_ref()?, rest)
+        }
+        (PropertyType::Array(array), ["prefixItems", i, rest @ ..]) => {
+            walk(array.prefix_items.get(index(i)?)?, rest)
+        }
+        (PropertyType::Hash(hash), ["items", rest @ ..]) => walk(hash.items.as_ref()?, rest),
+        (PropertyType::Not(not), ["not", rest @ ..]) => walk(&not.item, rest),
+        (PropertyType::OneOf(one_of), ["oneOf", i, rest @ ..]) => {
+            walk(one_of.items.get(index(i)?)?, rest)
+        }
+        (PropertyType::AllOf(all_of), ["allOf", i, rest @ ..]) => {
+            walk(all_of.items.get(index(i)?)?, rest)
+        }
+        (PropertyType::AnyOf(any_of), ["anyOf", i, rest @ ..]) => {
+            walk(any_of.items.get(index(i)?)?, rest)
+        }
+        _ => None,
+    }
+}
+
+/// Points references without a uri at the schema they are part of, collecting their pointers.
+pub(crate) fn anchor_local_references<'schema>(
+    node: &mut PropertyType<'schema>,
+    uri: &'schema str,
+    pointers: &mut Vec<&'schema str>,
+) {
+    let mut anchor =
+        |node: &mut PropertyType<'schema>| anchor_local_references(node, uri, pointers);
+
+    match node {
+        PropertyType::Reference(reference) => {
+            if reference.uri.is_empty() {
+                reference.uri = uri;
+                pointers.extend(reference.pointer);
+            }
+        }
+        PropertyType::Object(object) => object.items.values_mut().for_each(anchor),
+        PropertyType::Array(array) => array
+            .items
+            .iter_mut()
+            .chain(array.contains.iter_mut())
+            .map(AsMut::as_mut)
+            .chain(array.prefix_items.iter_mut())
+            .for_each(anchor),
+        PropertyType::Hash(hash) => hash.items.iter_mut().map(AsMut::as_mut).for_each(anchor),
+        PropertyType::Not(not) => anchor(&mut not.item),
+        PropertyType::OneOf(one_of) => one_of.items.iter_mut().for_each(anchor),
+        PropertyType::AllOf(all_of) => all_of.items.iter_mut().for_each(anchor),
+        PropertyType::AnyOf(any_of) => any_of.items.iter_mut().for_each(anchor),
+        PropertyType::Nullable(inner) => anchor(inner),
+        PropertyType::Composite { base, modifiers } => base
+            .iter_mut()
+            .map(AsMut::as_mut)
+            .chain(modifiers.iter_mut())
+            .for_each(anchor),
+        PropertyType::String(_)
+        | PropertyType::Integer(_)
+        | PropertyType::Real(_)
+        | PropertyType::Bool(_)
+        | PropertyType::Const(_)
+        | PropertyType::Tag(_)
+        | PropertyType::Custom(_) => {}
+    }
+}
+
+/// Removes a pair from the references being followed once the reference has been followed,
+/// even if validation panics.
+struct Following<'a>(&'a RefCell<Vec<(usize, usize)>>);
+
+impl<'a> Drop for Following<'a> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().pop();
+    }
+}
+
+impl<'schema> SchemaReference<'schema> {
+    /// Follows the reference to validate the value, unless the same node is already
+    /// validating the same value further up, in which case the schemas loop forever.
+    ///
+    /// Since every value is finite, recursive schemas always terminate if they descend into
+    /// the value, so revisiting a pair of node and value means they never will.
+    fn follow<'yaml, T>(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+        validate: impl FnOnce(&'schema PropertyType<'schema>) -> T,
+    ) -> Result<T, ValidationError<'yaml>>
+    where
+        'schema: 'yaml,
+    {
+        let node = self.resolve_node(ctx)?;
+        let pair = (
+            node as *const PropertyType as usize,
+            yaml as *const Yaml as usize,
+        );
+
+        if opts.following.borrow().contains(&pair) {
+            return Err(ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ReferenceLoop,
+                args: vec![("uri", self.uri.to_string())],
+            }
+            .into());
+        }
+
+        opts.following.borrow_mut().push(pair);
+        let _following = Following(&opts.following);
+
+        Ok(validate(node))
+    }
 }
 
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaReference<'schema> {
-    fn validate(
+    fn validate_with(
         &self,
         ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
-        if let Some(schema) = ctx.get_schema(self.uri) {
-            schema.validate(ctx, yaml)
-        } else {
-            Err(ValidationErrorKind::UnknownSchema { uri: self.uri }.into())
-        }
+        self.follow(ctx, yaml, opts, |node| node.validate_with(ctx, yaml, opts))?
+    }
+
+    fn matches(
+        &self,
+        ctx: &'schema Context<'schema>,
+        yaml: &'yaml Yaml,
+        opts: &ValidationOptions,
+    ) -> bool {
+        self.follow(ctx, yaml, opts, |node| node.matches(ctx, yaml, opts))
+            .unwrap_or(false)
     }
 }
 
@@ -26,14 +272,264 @@ mod tests {
     use super::*;
     use crate::utils::load_simple;
     use crate::SchemaReference;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
 
     #[test]
     fn validate_string() {
         assert_eq!(
-            SchemaReference { uri: "test" }
+            SchemaReference::new("test")
                 .validate(&Context::default(), &load_simple("hello"))
                 .unwrap_err(),
             ValidationErrorKind::UnknownSchema { uri: "test" }.into()
         );
     }
+
+    #[test]
+    fn validate_many_documents() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+            uri: number
+            schema:
+              type: integer
+            "#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let reference = SchemaReference::new("number");
+        let documents: Vec<Yaml> = (0..10_000).map(Yaml::Integer).collect();
+
+        for document in &documents {
+            reference.validate(&ctx, document).unwrap();
+        }
+
+        assert_eq!(reference.resolved.get(), Some(&(ctx.id(), 0)));
+
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<SchemaReference>();
+
+        // A context without the schema must not be served the cached lookup.
+        assert_eq!(
+            reference
+                .validate(&Context::default(), &documents[0])
+                .unwrap_err(),
+            ValidationErrorKind::UnknownSchema { uri: "number" }.into()
+        );
+    }
+
+    #[test]
+    fn validate_mutually_recursive() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    pet:
+      $ref: pet
+---
+uri: pet
+schema:
+  type: object
+  items:
+    species:
+      type: string
+    owner:
+      nullable: true
+      $ref: person
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let reference = SchemaReference::new("person");
+
+        let document = load_simple(
+            r#"
+name: alice
+pet:
+  species: cat
+  owner:
+    name: bob
+    pet:
+      species: dog
+      owner: ~
+"#,
+        );
+        reference.validate(&ctx, &document).unwrap();
+
+        let document = load_simple(
+            r#"
+name: alice
+pet:
+  species: cat
+  owner:
+    name: bob
+    pet:
+      species: 10
+"#,
+        );
+        assert_eq!(
+            reference.validate(&ctx, &document).unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path(breadcrumb!["species", "pet", "owner", "pet"])
+        );
+    }
+
+    #[test]
+    fn validate_unbounded_recursion() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: a
+schema:
+  $ref: b
+---
+uri: b
+schema:
+  allOf:
+    - type: integer
+    - $ref: a
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let reference = SchemaReference::new("a");
+        let document = load_simple("10");
+
+        assert_eq!(
+            reference.validate(&ctx, &document).unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::ReferenceLoop,
+                args: vec![("uri", "a".into())]
+            }
+            .into()
+        );
+        assert!(!reference.matches(&ctx, &document, &Default::default()));
+    }
+
+    #[test]
+    fn validate_local_reference() {
+        let schemas = YamlLoader::load_from_str(
+            r##"
+            uri: person
+            schema:
+              type: object
+              items:
+                home:
+                  type: object
+                  items:
+                    street:
+                      type: string
+                    number:
+                      type: integer
+                work:
+                  $ref: "#/items/home"
+            "##,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let schema = ctx.get_schema("person").unwrap();
+
+        schema
+            .validate(
+                &ctx,
+                &load_simple(
+                    r#"
+                    home:
+                      street: Main Street
+                      number: 12
+                    work:
+                      street: Side Street
+                      number: 3
+                    "#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&ctx, &load_simple("work:\n  number: three"))
+                .unwrap_err(),
+            ValidationErrorKind::WrongType {
+                expected: "integer",
+                actual: "string"
+            }
+            .with_path(breadcrumb!["number", "work"])
+        );
+    }
+
+    #[test]
+    fn dangling_local_reference() {
+        let schemas = YamlLoader::load_from_str(
+            r##"
+            uri: person
+            schema:
+              type: object
+              items:
+                work:
+                  $ref: "#/items/home"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Schema::try_from(&schemas[0]).unwrap_err(),
+            crate::SchemaErrorKind::MalformedField {
+                error: "local reference '#/items/home' does not point to a node within the schema"
+                    .into()
+            }
+            .with_path_name("person")
+        );
+    }
+
+    #[test]
+    fn debug_mutually_recursive() {
+        let schemas = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    pet:
+      $ref: pet
+---
+uri: pet
+schema:
+  type: object
+  items:
+    owner:
+      $ref: person#/items/pet
+"#,
+        )
+        .unwrap();
+
+        let ctx = Context::try_from(&schemas[..]).unwrap();
+        let before = format!("{:?}", ctx);
+
+        ctx.get_schema("person")
+            .unwrap()
+            .validate(&ctx, &load_simple("pet:\n  owner: {}"))
+            .unwrap();
+
+        // Resolving the references must not change how they are printed.
+        assert_eq!(format!("{:?}", ctx), before);
+        assert!(before.contains(r#"SchemaReference { uri: "pet", pointer: None }"#));
+        assert!(
+            before.contains(r#"SchemaReference { uri: "person", pointer: Some("/items/pet") }"#)
+        );
+
+        assert_eq!(
+            SchemaReference::new("person#/items/pet").to_string(),
+            "person#/items/pet"
+        );
+        assert_eq!(SchemaReference::new("pet").to_string(), "pet");
+    }
 }
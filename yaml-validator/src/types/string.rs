@@ -1,7 +1,14 @@
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
 use crate::errors::{SchemaError, SchemaErrorKind};
-use crate::errors::{ValidationError, ValidationErrorKind};
-use crate::utils::{try_into_usize, OptionalLookup, YamlUtils};
-use crate::{Context, Validate};
+#[cfg(feature = "date")]
+use crate::types::format::Date;
+use crate::types::format::StringFormat;
+#[cfg(feature = "regex")]
+use crate::utils::try_into_usize;
+use crate::utils::{
+    enum_error, lookup_enum, lookup_usize, type_keywords, CondenseErrors, OptionalLookup, YamlUtils,
+};
+use crate::{Context, Validate, ValidationOptions};
 use std::convert::TryFrom;
 use yaml_rust::Yaml;
 
@@ -10,35 +17,153 @@ pub(crate) struct SchemaString {
     // The yaml_rust library uses i64 internally, but we cast to usize
     // while building the schema, since we'll need to compare them to
     // string lengths later and we want to fail as early as possible.
-    max_length: Option<usize>,
-    min_length: Option<usize>,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) min_length: Option<usize>,
+    // Limits on the size of the value in bytes once encoded as UTF-8, rather than in characters.
+    pub(crate) min_byte_length: Option<usize>,
+    pub(crate) max_byte_length: Option<usize>,
+    // Rejects values containing a line break.
+    pub(crate) single_line: bool,
+    // Rejects values which differ from their NFC normalized form.
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) require_nfc: bool,
 
     #[cfg(feature = "regex")]
-    pattern: Option<regex::Regex>,
+    pub(crate) pattern: Option<regex::Regex>,
+    // Capture group of the pattern to apply minLength and maxLength to, instead of the whole value.
+    #[cfg(feature = "regex")]
+    pub(crate) length_of: Option<CaptureGroup>,
+    // The value must match every one of these.
+    #[cfg(feature = "regex")]
+    pub(crate) all_patterns: Vec<regex::Regex>,
+    // The value must match at least one of these, unless empty.
+    #[cfg(feature = "regex")]
+    pub(crate) any_pattern: Vec<regex::Regex>,
+
+    pub(crate) enumeration: Option<Vec<String>>,
+    // Accepted values, each paired with the canonical value it is normalized into,
+    // which is itself one of the accepted values.
+    pub(crate) enum_map: Option<Vec<(String, String)>>,
+
+    pub(crate) format: Option<StringFormat>,
+    // Name of a format registered with the context, when it is not one of the built in formats.
+    pub(crate) custom_format: Option<String>,
+
+    // The earliest and latest dates allowed, when the format is a date.
+    #[cfg(feature = "date")]
+    pub(crate) minimum: Option<Date>,
+    #[cfg(feature = "date")]
+    pub(crate) maximum: Option<Date>,
+}
+
+#[cfg(feature = "regex")]
+#[derive(Debug)]
+pub(crate) enum CaptureGroup {
+    Index(usize),
+    Name(String),
+}
+
+#[cfg(feature = "regex")]
+impl CaptureGroup {
+    fn parse<'schema>(
+        yaml: &'schema Yaml,
+        pattern: Option<&regex::Regex>,
+    ) -> Result<Self, SchemaError<'schema>> {
+        let pattern = pattern.ok_or_else(|| SchemaErrorKind::MalformedField {
+            error: "lengthOf requires a pattern to capture from".into(),
+        })?;
+
+        let group = match yaml {
+            Yaml::Integer(index) => CaptureGroup::Index(try_into_usize(*index)?),
+            Yaml::String(name) => CaptureGroup::Name(name.clone()),
+            _ => {
+                return Err(SchemaErrorKind::WrongType {
+                    expected: "integer or string",
+                    actual: yaml.type_to_str(),
+                }
+                .into())
+            }
+        };
+
+        let exists = match &group {
+            CaptureGroup::Index(index) => *index < pattern.captures_len(),
+            CaptureGroup::Name(name) => pattern.capture_names().any(|n| n == Some(name.as_str())),
+        };
+
+        if !exists {
+            return Err(SchemaErrorKind::MalformedField {
+                error: format!("pattern has no capture group {}", group),
+            }
+            .into());
+        }
+
+        Ok(group)
+    }
+
+    fn get<'t>(&self, captures: &regex::Captures<'t>) -> Option<regex::Match<'t>> {
+        match self {
+            CaptureGroup::Index(index) => captures.get(*index),
+            CaptureGroup::Name(name) => captures.name(name),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl std::fmt::Display for CaptureGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptureGroup::Index(index) => write!(f, "{}", index),
+            CaptureGroup::Name(name) => write!(f, "'{}'", name),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+fn lookup_patterns<'schema>(
+    yaml: &'schema Yaml,
+    field: &'schema str,
+) -> Result<Vec<regex::Regex>, SchemaError<'schema>> {
+    yaml.lookup(field, "array", Yaml::as_vec)
+        .map_err(SchemaError::from)
+        .into_optional()?
+        .map(|patterns| {
+            SchemaError::condense_errors(&mut patterns.iter().enumerate().map(|(i, pattern)| {
+                let pattern = pattern
+                    .as_type("string", Yaml::as_str)
+                    .map_err(SchemaError::from)
+                    .map_err(SchemaError::add_path_index(i))?;
+
+                regex::Regex::new(pattern).map_err(|e| {
+                    SchemaErrorKind::MalformedField {
+                        error: format!("{}", e),
+                    }
+                    .with_path_index(i)
+                })
+            }))
+        })
+        .transpose()
+        .map_err(SchemaError::add_path_name(field))
+        .map(Option::unwrap_or_default)
 }
 
 impl<'schema> TryFrom<&'schema Yaml> for SchemaString {
     type Error = SchemaError<'schema>;
     fn try_from(yaml: &'schema Yaml) -> Result<Self, Self::Error> {
-        #[cfg(feature = "regex")]
-        yaml.strict_contents(&[], &["type", "minLength", "maxLength", "pattern"])?;
+        SchemaString::parse(yaml, &Context::default())
+    }
+}
 
-        #[cfg(not(feature = "regex"))]
-        yaml.strict_contents(&[], &["type", "minLength", "maxLength"])?;
+impl SchemaString {
+    /// Parse a string schema, consulting the context for any custom formats.
+    pub(crate) fn parse<'schema>(
+        yaml: &'schema Yaml,
+        ctx: &Context,
+    ) -> Result<Self, SchemaError<'schema>> {
+        yaml.strict_schema_contents(&[], &type_keywords("string"))?;
 
-        let min_length = yaml
-            .lookup("minLength", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("minLength"))
-            .into_optional()?;
+        let min_length = lookup_usize(yaml, "minLength")?;
 
-        let max_length = yaml
-            .lookup("maxLength", "integer", Yaml::as_i64)
-            .map_err(SchemaError::from)
-            .and_then(try_into_usize)
-            .map_err(SchemaError::add_path_name("maxLength"))
-            .into_optional()?;
+        let max_length = lookup_usize(yaml, "maxLength")?;
 
         if let (Some(min_length), Some(max_length)) = (min_length, max_length) {
             if min_length > max_length {
@@ -49,6 +174,153 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaString {
             }
         }
 
+        let min_byte_length = lookup_usize(yaml, "minByteLength")?;
+
+        let max_byte_length = lookup_usize(yaml, "maxByteLength")?;
+
+        if let (Some(min_byte_length), Some(max_byte_length)) = (min_byte_length, max_byte_length) {
+            if min_byte_length > max_byte_length {
+                return Err(SchemaErrorKind::MalformedField {
+                    error: "minByteLength cannot be greater than maxByteLength".into(),
+                }
+                .into());
+            }
+        }
+
+        let single_line = yaml
+            .lookup("singleLine", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("singleLine"))
+            .into_optional()?
+            .unwrap_or(false);
+
+        #[cfg(feature = "unicode-normalization")]
+        let require_nfc = yaml
+            .lookup("requireNfc", "bool", Yaml::as_bool)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("requireNfc"))
+            .into_optional()?
+            .unwrap_or(false);
+
+        let enumeration = lookup_enum(yaml, "string", Yaml::as_str)?
+            .map(|values| values.into_iter().map(str::to_owned).collect());
+
+        yaml.check_exclusive_fields(&["enum", "enumMap"])?;
+
+        let enum_map = yaml
+            .lookup("enumMap", "hash", Yaml::as_hash)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("enumMap"))
+            .into_optional()?
+            .map(|values| {
+                if values.is_empty() {
+                    return Err(SchemaErrorKind::MalformedField {
+                        error: "enumMap must contain at least one value".into(),
+                    }
+                    .into());
+                }
+
+                SchemaError::condense_errors(&mut values.iter().map(|(input, canonical)| {
+                    let input = input
+                        .as_type("string", Yaml::as_str)
+                        .map_err(SchemaError::from)?;
+
+                    let canonical = canonical
+                        .as_type("string", Yaml::as_str)
+                        .map_err(SchemaError::from)
+                        .map_err(SchemaError::add_path_name(input))?;
+
+                    Ok((input.to_owned(), canonical.to_owned()))
+                }))
+            })
+            .transpose()
+            .map_err(SchemaError::add_path_name("enumMap"))?;
+
+        // Canonical values must map onto themselves, so that normalized documents still validate.
+        if let Some(enum_map) = &enum_map {
+            let mut unmapped: Vec<&str> = Vec::new();
+            for (_, canonical) in enum_map {
+                if enum_map.iter().any(|(input, _)| input == canonical)
+                    || unmapped.contains(&canonical.as_str())
+                {
+                    continue;
+                }
+                unmapped.push(canonical);
+            }
+
+            if !unmapped.is_empty() {
+                let inputs: Vec<&str> = enum_map.iter().map(|(input, _)| input.as_str()).collect();
+
+                return Err(SchemaErrorKind::MalformedField {
+                    error: format!(
+                        "canonical values [{}] are not inputs of the enumMap [{}]",
+                        unmapped.join(", "),
+                        inputs.join(", ")
+                    ),
+                }
+                .with_path_name("enumMap"));
+            }
+        }
+
+        let format_name = yaml
+            .lookup("format", "string", Yaml::as_str)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_name("format"))
+            .into_optional()?;
+
+        let format = format_name.and_then(StringFormat::from_name);
+
+        let custom_format = match format_name {
+            Some(name) if format.is_none() => {
+                if !ctx.custom_formats.contains_key(name) {
+                    return Err(SchemaErrorKind::MalformedField {
+                        error: format!("unknown format '{}'", name),
+                    }
+                    .with_path_name("format"));
+                }
+
+                Some(name.to_owned())
+            }
+            _ => None,
+        };
+
+        #[cfg(feature = "date")]
+        let (minimum, maximum) = {
+            let lookup_date = |field: &'schema str| -> Result<Option<Date>, SchemaError<'schema>> {
+                yaml.lookup(field, "string", Yaml::as_str)
+                    .map_err(SchemaError::from)
+                    .into_optional()?
+                    .map(|value| {
+                        if format != Some(StringFormat::Date) {
+                            return Err(SchemaError::from(SchemaErrorKind::MalformedField {
+                                error: format!("{} requires format: date", field),
+                            }));
+                        }
+
+                        Date::parse(value).ok_or_else(|| {
+                            SchemaErrorKind::MalformedField {
+                                error: format!("'{}' is not a valid date", value),
+                            }
+                            .into()
+                        })
+                    })
+                    .transpose()
+                    .map_err(SchemaError::add_path_name(field))
+            };
+
+            (lookup_date("minimum")?, lookup_date("maximum")?)
+        };
+
+        #[cfg(feature = "date")]
+        if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+            if minimum > maximum {
+                return Err(SchemaErrorKind::MalformedField {
+                    error: "minimum cannot be a later date than maximum".into(),
+                }
+                .into());
+            }
+        }
+
         #[cfg(feature = "regex")]
         {
             let pattern = yaml
@@ -65,10 +337,40 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaString {
                 })
                 .transpose()?;
 
+            let length_of = yaml
+                .lookup("lengthOf", "yaml", Option::from)
+                .map_err(SchemaError::from)
+                .into_optional()?
+                .map(|group| CaptureGroup::parse(group, pattern.as_ref()))
+                .transpose()
+                .map_err(SchemaError::add_path_name("lengthOf"))?;
+
+            yaml.check_exclusive_fields(&["pattern", "allPatterns"])?;
+            yaml.check_exclusive_fields(&["pattern", "anyPattern"])?;
+
+            let all_patterns = lookup_patterns(yaml, "allPatterns")?;
+            let any_pattern = lookup_patterns(yaml, "anyPattern")?;
+
             Ok(SchemaString {
                 max_length,
                 min_length,
+                min_byte_length,
+                max_byte_length,
+                single_line,
+                #[cfg(feature = "unicode-normalization")]
+                require_nfc,
                 pattern,
+                length_of,
+                all_patterns,
+                any_pattern,
+                enumeration,
+                enum_map,
+                format,
+                custom_format,
+                #[cfg(feature = "date")]
+                minimum,
+                #[cfg(feature = "date")]
+                maximum,
             })
         }
 
@@ -76,46 +378,210 @@ impl<'schema> TryFrom<&'schema Yaml> for SchemaString {
         Ok(SchemaString {
             min_length,
             max_length,
+            min_byte_length,
+            max_byte_length,
+            single_line,
+            #[cfg(feature = "unicode-normalization")]
+            require_nfc,
+            enumeration,
+            enum_map,
+            format,
+            custom_format,
+            #[cfg(feature = "date")]
+            minimum,
+            #[cfg(feature = "date")]
+            maximum,
         })
     }
 }
 
+impl SchemaString {
+    /// The canonical value an accepted value of the `enumMap` is normalized into.
+    pub(crate) fn canonical(&self, value: &str) -> Option<&str> {
+        self.enum_map
+            .iter()
+            .flatten()
+            .find(|(input, _)| input == value)
+            .map(|(_, canonical)| canonical.as_str())
+    }
+}
+
 impl<'yaml, 'schema: 'yaml> Validate<'yaml, 'schema> for SchemaString {
-    fn validate(
+    fn validate_with(
         &self,
-        _: &'schema Context<'schema>,
+        ctx: &'schema Context<'schema>,
         yaml: &'yaml Yaml,
+        _: &ValidationOptions,
     ) -> Result<(), ValidationError<'yaml>> {
         let value = yaml.as_type("string", Yaml::as_str)?;
 
+        #[cfg(feature = "regex")]
+        let measured = match (&self.length_of, &self.pattern) {
+            (Some(group), Some(regex)) => match regex.captures(value) {
+                Some(captures) => group.get(&captures).map_or("", |group| group.as_str()),
+                None => value,
+            },
+            _ => value,
+        };
+
+        #[cfg(not(feature = "regex"))]
+        let measured = value;
+
+        // Lengths are counted in characters, so that multibyte characters count only once.
+        let length = measured.chars().count();
+
         if let Some(min_length) = self.min_length {
-            if value.len() < min_length {
+            if length < min_length {
                 return Err(ValidationErrorKind::ValidationError {
-                    error: "string length is less than minLength",
+                    error: MessageId::MinLength,
                 }
                 .into());
             }
         }
 
         if let Some(max_length) = self.max_length {
-            if value.len() > max_length {
+            if length > max_length {
+                return Err(ValidationErrorKind::ValidationError {
+                    error: MessageId::MaxLength,
+                }
+                .into());
+            }
+        }
+
+        if let Some(min_byte_length) = self.min_byte_length {
+            if value.len() < min_byte_length {
+                return Err(ValidationErrorKind::ValidationError {
+                    error: MessageId::MinByteLength,
+                }
+                .into());
+            }
+        }
+
+        if let Some(max_byte_length) = self.max_byte_length {
+            if value.len() > max_byte_length {
                 return Err(ValidationErrorKind::ValidationError {
-                    error: "string length is greater than maxLength",
+                    error: MessageId::MaxByteLength,
                 }
                 .into());
             }
         }
 
+        if self.single_line && value.contains(&['\n', '\r'][..]) {
+            return Err(ValidationErrorKind::ValidationError {
+                error: MessageId::LineBreak,
+            }
+            .into());
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        if self.require_nfc && !unicode_normalization::is_nfc(value) {
+            return Err(ValidationErrorKind::ValidationError {
+                error: MessageId::NotNfc,
+            }
+            .into());
+        }
+
         #[cfg(feature = "regex")]
         {
             if let Some(regex) = &self.pattern {
                 if !regex.is_match(value) {
                     return Err(ValidationErrorKind::ValidationError {
-                        error: "supplied value does not match regex pattern for field",
+                        error: MessageId::PatternMismatch,
                     }
                     .into());
                 }
             }
+
+            if let Some(regex) = self
+                .all_patterns
+                .iter()
+                .find(|regex| !regex.is_match(value))
+            {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::AllPatterns,
+                    args: vec![("pattern", regex.to_string())],
+                }
+                .into());
+            }
+
+            if !self.any_pattern.is_empty()
+                && !self.any_pattern.iter().any(|regex| regex.is_match(value))
+            {
+                let patterns: Vec<String> = self
+                    .any_pattern
+                    .iter()
+                    .map(|regex| format!("'{}'", regex))
+                    .collect();
+
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::AnyPattern,
+                    args: vec![("patterns", patterns.join(", "))],
+                }
+                .into());
+            }
+        }
+
+        if let Some(enumeration) = &self.enumeration {
+            if !enumeration.iter().any(|allowed| allowed == value) {
+                return Err(enum_error(format!("'{}'", value), enumeration));
+            }
+        }
+
+        if let Some(enum_map) = &self.enum_map {
+            if self.canonical(value).is_none() {
+                let inputs: Vec<&str> = enum_map.iter().map(|(input, _)| input.as_str()).collect();
+                return Err(enum_error(format!("'{}'", value), &inputs));
+            }
+        }
+
+        if let Some(format) = self.format {
+            if !format.is_valid(value) {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::InvalidFormat,
+                    args: vec![
+                        ("value", value.to_string()),
+                        ("format", format.name().to_string()),
+                    ],
+                }
+                .into());
+            }
+        }
+
+        if let Some(name) = &self.custom_format {
+            let validator = ctx.custom_formats.get(name).ok_or_else(|| {
+                ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::UnregisteredFormat,
+                    args: vec![("format", name.to_string())],
+                }
+            })?;
+
+            validator(value).map_err(|reason| ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::CustomFormat,
+                args: vec![
+                    ("value", value.to_string()),
+                    ("format", name.to_string()),
+                    ("reason", reason.to_string()),
+                ],
+            })?;
+        }
+
+        #[cfg(feature = "date")]
+        if let Some(date) = Date::parse(value).filter(|_| self.format == Some(StringFormat::Date)) {
+            if let Some(minimum) = self.minimum.filter(|minimum| date < *minimum) {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::DateBeforeMinimum,
+                    args: vec![("value", date.to_string()), ("limit", minimum.to_string())],
+                }
+                .into());
+            }
+
+            if let Some(maximum) = self.maximum.filter(|maximum| date > *maximum) {
+                return Err(ValidationErrorKind::ParameterizedValidation {
+                    error: MessageId::DateAfterMaximum,
+                    args: vec![("value", date.to_string()), ("limit", maximum.to_string())],
+                }
+                .into());
+            }
         }
 
         Ok(())
@@ -188,6 +654,17 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn with_comment() {
+        SchemaString::try_from(&load_simple(
+            r#"
+                type: string
+                $comment: this is only here for the maintainers
+            "#,
+        ))
+        .unwrap();
+    }
+
     #[test]
     fn with_malformed_max_length() {
         assert_eq!(
@@ -337,7 +814,7 @@ mod tests {
                 .validate(&Context::default(), &load_simple("hello"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "string length is less than minLength"
+                error: MessageId::MinLength
             }
             .into()
         );
@@ -350,7 +827,7 @@ mod tests {
                 )
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "string length is greater than maxLength"
+                error: MessageId::MaxLength
             }
             .into()
         );
@@ -377,9 +854,493 @@ mod tests {
                 .validate(&Context::default(), &load_simple("world"))
                 .unwrap_err(),
             ValidationErrorKind::ValidationError {
-                error: "supplied value does not match regex pattern for field",
+                error: MessageId::PatternMismatch,
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_all_patterns() {
+        let yaml = load_simple(
+            r#"
+            allPatterns:
+              - "[0-9]"
+              - "[A-Z]"
+            "#,
+        );
+
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("Password1"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("password1"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::AllPatterns,
+                args: vec![("pattern", "[A-Z]".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_any_pattern() {
+        let yaml = load_simple(
+            r#"
+            anyPattern:
+              - "^https://"
+              - "^ssh://"
+            "#,
+        );
+
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("ssh://example.com"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("ftp://example.com"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::AnyPattern,
+                args: vec![("patterns", "'^https://', '^ssh://'".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn with_pattern_and_all_patterns() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple(
+                r#"
+                pattern: "[0-9]"
+                allPatterns:
+                  - "[A-Z]"
+                "#
+            ))
+            .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error:
+                    "conflicting constraints: pattern, allPatterns cannot be used at the same time"
+                        .into()
             }
             .into()
         );
     }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn validate_length_of_group() {
+        let yaml = load_simple(
+            r#"
+            pattern: "^[a-z]+-(?P<id>[0-9]+)$"
+            lengthOf: id
+            minLength: 2
+            maxLength: 4
+            "#,
+        );
+
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("averylongprefix-123"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("short-12345"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxLength
+            }
+            .into()
+        );
+
+        let yaml = load_simple(
+            r#"
+            pattern: "^[a-z]+-([0-9]+)$"
+            lengthOf: 1
+            minLength: 2
+            "#,
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&yaml)
+                .unwrap()
+                .validate(&Context::default(), &load_simple("averylongprefix-1"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MinLength
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn with_malformed_length_of() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple("lengthOf: 1")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "lengthOf requires a pattern to capture from".into()
+            }
+            .with_path_name("lengthOf")
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("pattern: \"(a)\"\nlengthOf: name")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "pattern has no capture group 'name'".into()
+            }
+            .with_path_name("lengthOf")
+        );
+    }
+
+    #[test]
+    fn validate_duration() {
+        let yaml = load_simple("format: duration");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("PT1H30M"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("1h30m"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::InvalidFormat,
+                args: vec![("value", "1h30m".into()), ("format", "duration".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_color_hex() {
+        let yaml = load_simple("format: color-hex");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        for valid in &["'#1e90ff'", "'#1e90ff80'"] {
+            schema
+                .validate(&Context::default(), &load_simple(valid))
+                .unwrap();
+        }
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("'#1e90f'"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::InvalidFormat,
+                args: vec![("value", "#1e90f".into()), ("format", "color-hex".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn with_unknown_format() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple("format: timespan")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "unknown format 'timespan'".into()
+            }
+            .with_path_name("format")
+        );
+    }
+
+    #[test]
+    fn validate_custom_format() {
+        let mut ctx = Context::default();
+        ctx.register_format("semver", |value| {
+            if value.split('.').all(|part| part.parse::<u64>().is_ok())
+                && value.split('.').count() == 3
+            {
+                Ok(())
+            } else {
+                Err("expected three numeric components".into())
+            }
+        });
+
+        let yaml = load_simple("format: semver");
+        let schema = SchemaString::parse(&yaml, &ctx).unwrap();
+
+        schema.validate(&ctx, &load_simple("'1.2.3'")).unwrap();
+
+        assert_eq!(
+            schema.validate(&ctx, &load_simple("'1.2'")).unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::CustomFormat,
+                args: vec![
+                    ("value", "1.2".into()),
+                    ("format", "semver".into()),
+                    ("reason", "expected three numeric components".into())
+                ]
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("'1.2.3'"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::UnregisteredFormat,
+                args: vec![("format", "semver".into())]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_enum() {
+        let yaml = load_simple(
+            r#"
+            enum: [red, green, blue]
+            "#,
+        );
+
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        let ctx = Context::default();
+        schema.validate(&ctx, &load_simple("green")).unwrap();
+
+        let document = load_simple("purple");
+        let err = schema.validate(&ctx, &document).unwrap_err();
+
+        assert_eq!(
+            format!("{}", err),
+            "#: special requirements for field not met: value 'purple' is not one of [red, green, blue]\n"
+        );
+    }
+
+    #[test]
+    fn with_malformed_enum() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enum: [red, 10]")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path(breadcrumb![1usize, "enum"])
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enum: []")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "enum must contain at least one value".into()
+            }
+            .with_path_name("enum")
+        );
+    }
+
+    #[test]
+    fn validate_enum_map() {
+        let yaml = load_simple(
+            r#"
+            enumMap:
+              "yes": "true"
+              y: "true"
+              "true": "true"
+              "no": "false"
+              n: "false"
+              "false": "false"
+            "#,
+        );
+
+        let schema = SchemaString::try_from(&yaml).unwrap();
+        assert_eq!(schema.canonical("y"), Some("true"));
+
+        let ctx = Context::default();
+        schema.validate(&ctx, &load_simple("'y'")).unwrap();
+        schema.validate(&ctx, &load_simple("'false'")).unwrap();
+
+        let document = load_simple("maybe");
+        assert_eq!(
+            format!("{}", schema.validate(&ctx, &document).unwrap_err()),
+            "#: special requirements for field not met: value 'maybe' is not one of [yes, y, true, no, n, false]\n"
+        );
+    }
+
+    #[test]
+    fn with_malformed_enum_map() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enumMap: {y: 10}")).unwrap_err(),
+            SchemaErrorKind::WrongType {
+                expected: "string",
+                actual: "integer"
+            }
+            .with_path(breadcrumb!["y", "enumMap"])
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enumMap: {y: 'yes', n: 'no', 'no': 'no'}"))
+                .unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "canonical values [yes] are not inputs of the enumMap [y, n, no]".into()
+            }
+            .with_path_name("enumMap")
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enumMap: {}")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "enumMap must contain at least one value".into()
+            }
+            .with_path_name("enumMap")
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("enum: [y]\nenumMap: {y: 'yes'}")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "conflicting constraints: enum, enumMap cannot be used at the same time"
+                    .into()
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_single_line() {
+        let yaml = load_simple("singleLine: true");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("Quarterly report"))
+            .unwrap();
+
+        for value in &["first\nsecond", "first\r\nsecond", "trailing\r"] {
+            assert_eq!(
+                schema
+                    .validate(&Context::default(), &Yaml::String(value.to_string()))
+                    .unwrap_err(),
+                ValidationErrorKind::ValidationError {
+                    error: MessageId::LineBreak
+                }
+                .into()
+            );
+        }
+
+        let yaml = load_simple("singleLine: false");
+        SchemaString::try_from(&yaml)
+            .unwrap()
+            .validate(&Context::default(), &load_simple("|\n  first\n  second"))
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn validate_require_nfc() {
+        let yaml = load_simple("requireNfc: true");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        // 'é' as a single precomposed character.
+        schema
+            .validate(&Context::default(), &Yaml::String("caf\u{e9}".into()))
+            .unwrap();
+
+        // 'e' followed by a combining acute accent, which NFC composes into the above.
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &Yaml::String("cafe\u{301}".into()))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::NotNfc
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn validate_byte_length() {
+        let yaml = load_simple("maxLength: 5\nmaxByteLength: 8\nminByteLength: 2");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("hello"))
+            .unwrap();
+
+        // Five characters, but ten bytes.
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("æææææ"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MaxByteLength
+            }
+            .into()
+        );
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("a"))
+                .unwrap_err(),
+            ValidationErrorKind::ValidationError {
+                error: MessageId::MinByteLength
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "date")]
+    fn validate_date_range() {
+        let yaml = load_simple("format: date\nminimum: 2020-01-01\nmaximum: 2020-12-31");
+        let schema = SchemaString::try_from(&yaml).unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("2020-06-15"))
+            .unwrap();
+
+        schema
+            .validate(&Context::default(), &load_simple("2020-01-01"))
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .validate(&Context::default(), &load_simple("2019-12-31"))
+                .unwrap_err(),
+            ValidationErrorKind::ParameterizedValidation {
+                error: MessageId::DateBeforeMinimum,
+                args: vec![
+                    ("value", "2019-12-31".into()),
+                    ("limit", "2020-01-01".into())
+                ]
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "date")]
+    fn malformed_date_range() {
+        assert_eq!(
+            SchemaString::try_from(&load_simple("format: date\nminimum: 2020-02-30")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "'2020-02-30' is not a valid date".into()
+            }
+            .with_path_name("minimum")
+        );
+
+        assert_eq!(
+            SchemaString::try_from(&load_simple("minimum: 2020-01-01")).unwrap_err(),
+            SchemaErrorKind::MalformedField {
+                error: "minimum requires format: date".into()
+            }
+            .with_path_name("minimum")
+        );
+    }
 }
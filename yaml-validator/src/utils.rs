@@ -1,4 +1,5 @@
 use crate::errors::{GenericError, SchemaError, SchemaErrorKind};
+use crate::errors::{MessageId, ValidationError, ValidationErrorKind};
 use std::convert::TryInto;
 use std::fmt::{Debug, Display};
 use std::ops::{Index, Sub};
@@ -12,7 +13,7 @@ pub trait UnitValue: Sub + Copy + PartialOrd + Default + Display {
 
 impl UnitValue for f64 {
     const ZERO: f64 = 0.0;
-    const UNIT: f64 = std::f64::MIN_POSITIVE;
+    const UNIT: f64 = f64::MIN_POSITIVE;
 }
 
 impl UnitValue for i64 {
@@ -20,7 +21,9 @@ impl UnitValue for i64 {
     const UNIT: i64 = 1;
 }
 
-#[derive(Debug)]
+/// A lower or upper bound on a numeric value, as given by `minimum`,
+/// `exclusiveMinimum`, `maximum` or `exclusiveMaximum` in a schema.
+#[derive(Debug, PartialEq)]
 pub enum Limit<T: UnitValue>
 where
     <T as Sub>::Output: UnitValue,
@@ -33,6 +36,19 @@ impl<T: UnitValue> Limit<T>
 where
     <T as Sub>::Output: UnitValue,
 {
+    /// The threshold value of the limit.
+    pub fn value(&self) -> &T {
+        match self {
+            Limit::Inclusive(threshold) => threshold,
+            Limit::Exclusive(threshold) => threshold,
+        }
+    }
+
+    /// Whether the threshold value itself is within the limit.
+    pub fn is_inclusive(&self) -> bool {
+        matches!(self, Limit::Inclusive(_))
+    }
+
     pub fn is_lesser(&self, value: &T) -> bool {
         match self {
             Limit::Inclusive(threshold) => value <= threshold,
@@ -47,6 +63,36 @@ where
         }
     }
 
+    /// Describes the value falling short of the limit, when it is used as a lower bound.
+    pub(crate) fn lower_violation<'a>(&self, value: &T) -> ValidationErrorKind<'a> {
+        let error = match self {
+            Limit::Inclusive(_) => MessageId::LowerLimit,
+            Limit::Exclusive(_) => MessageId::ExclusiveLowerLimit,
+        };
+
+        self.violation(error, value)
+    }
+
+    /// Describes the value exceeding the limit, when it is used as an upper bound.
+    pub(crate) fn upper_violation<'a>(&self, value: &T) -> ValidationErrorKind<'a> {
+        let error = match self {
+            Limit::Inclusive(_) => MessageId::UpperLimit,
+            Limit::Exclusive(_) => MessageId::ExclusiveUpperLimit,
+        };
+
+        self.violation(error, value)
+    }
+
+    fn violation<'a>(&self, error: MessageId, value: &T) -> ValidationErrorKind<'a> {
+        ValidationErrorKind::ParameterizedValidation {
+            error,
+            args: vec![
+                ("value", value.to_string()),
+                ("limit", self.value().to_string()),
+            ],
+        }
+    }
+
     pub fn has_span(&self, upper: &Self) -> bool {
         let zero = <<T as Sub>::Output as UnitValue>::ZERO;
         let unit = <<T as Sub>::Output as UnitValue>::UNIT;
@@ -60,6 +106,34 @@ where
     }
 }
 
+/// Keywords accepted on every schema node regardless of its type. These are
+/// either annotations which do not affect validation, or handled by
+/// [PropertyType](crate::PropertyType) itself rather than the individual types,
+/// such as the modifiers which may be combined with a base `type`.
+pub(crate) const UNIVERSAL_KEYWORDS: &[&str] = &[
+    "$comment",
+    "title",
+    "$ref",
+    "nullable",
+    "default",
+    "not",
+    "oneOf",
+    "exclusive",
+    "allOf",
+    "anyOf",
+    "const",
+    "tag",
+];
+
+/// Keywords read by an object from the schemas of its `items`, rather than by the schema
+/// node they appear in. They are rejected anywhere else.
+pub(crate) const PROPERTY_KEYWORDS: &[&str] = &["aliases", "equalTo"];
+
+/// Keywords which describe a schema node rather than restricting the value, along with `$ref` itself.
+/// A reference with nothing else alongside it is used as is.
+pub(crate) const ANNOTATION_KEYWORDS: &[&str] =
+    &["$comment", "title", "$ref", "nullable", "default"];
+
 pub fn try_into_usize<'a, N: Default + PartialOrd + TryInto<usize>>(
     number: N,
 ) -> Result<usize, SchemaError<'a>> {
@@ -78,6 +152,129 @@ pub fn try_into_usize<'a, N: Default + PartialOrd + TryInto<usize>>(
     })
 }
 
+/// Look up an optional non-negative integer field such as `minItems`, making sure
+/// that any error, including negative or overflowing values, is tagged with the
+/// name of the field.
+pub fn lookup_usize<'a>(yaml: &'a Yaml, field: &'a str) -> Result<Option<usize>, SchemaError<'a>> {
+    yaml.lookup(field, "integer", Yaml::as_i64)
+        .map_err(SchemaError::from)
+        .and_then(try_into_usize)
+        .map_err(SchemaError::add_path_name(field))
+        .into_optional()
+}
+
+/// Look up the optional `enum` field listing the only values allowed, each of
+/// which must be of the `expected` type.
+pub fn lookup_enum<'a, T: Debug>(
+    yaml: &'a Yaml,
+    expected: &'static str,
+    cast: fn(&'a Yaml) -> Option<T>,
+) -> Result<Option<Vec<T>>, SchemaError<'a>> {
+    let values = match yaml
+        .lookup("enum", "array", Yaml::as_vec)
+        .map_err(SchemaError::from)
+        .map_err(SchemaError::add_path_name("enum"))
+        .into_optional()?
+    {
+        Some(values) => values,
+        None => return Ok(None),
+    };
+
+    if values.is_empty() {
+        return Err(SchemaErrorKind::MalformedField {
+            error: "enum must contain at least one value".into(),
+        }
+        .with_path_name("enum"));
+    }
+
+    SchemaError::condense_errors(&mut values.iter().enumerate().map(|(i, value)| {
+        value
+            .as_type(expected, cast)
+            .map_err(SchemaError::from)
+            .map_err(SchemaError::add_path_index(i))
+    }))
+    .map_err(SchemaError::add_path_name("enum"))
+    .map(Some)
+}
+
+/// Look up the divisors given by `multipleOf`, or its alias `divisibleBy`, as a single number
+/// or an array of them, alongside the nodes they were read from. Every divisor must be of the
+/// `expected` type and greater than zero.
+pub fn lookup_divisors<'a, T: Debug + PartialOrd + Default>(
+    yaml: &'a Yaml,
+    expected: &'static str,
+    cast: fn(&'a Yaml) -> Option<T>,
+) -> Result<Vec<(T, &'a Yaml)>, SchemaError<'a>> {
+    yaml.check_exclusive_fields(&["multipleOf", "divisibleBy"])?;
+
+    let field = if yaml["divisibleBy"].is_badvalue() {
+        "multipleOf"
+    } else {
+        "divisibleBy"
+    };
+
+    let (nodes, in_array): (Vec<&'a Yaml>, bool) = match yaml
+        .lookup(field, "yaml", Option::from)
+        .map_err(SchemaError::from)
+        .into_optional()?
+    {
+        Some(Yaml::Array(items)) => (items.iter().collect(), true),
+        Some(divisor) => (vec![divisor], false),
+        None => return Ok(Vec::new()),
+    };
+
+    if nodes.is_empty() {
+        return Err(SchemaErrorKind::MalformedField {
+            error: "must contain at least one divisor".into(),
+        }
+        .with_path_name(field));
+    }
+
+    SchemaError::condense_errors(&mut nodes.into_iter().enumerate().map(|(i, node)| {
+        node.as_type(expected, cast)
+            .map_err(SchemaError::from)
+            .and_then(|divisor| {
+                if divisor <= T::default() {
+                    Err(SchemaErrorKind::MalformedField {
+                        error: "must be greater than zero".into(),
+                    }
+                    .into())
+                } else {
+                    Ok((divisor, node))
+                }
+            })
+            .map_err(|err| {
+                if in_array {
+                    SchemaError::add_path_index(i)(err)
+                } else {
+                    err
+                }
+            })
+    }))
+    .map_err(SchemaError::add_path_name(field))
+}
+
+/// Error for a value which is not among those allowed by `enum`, naming both.
+pub fn enum_error<'a, T: Display>(value: impl Display, allowed: &[T]) -> ValidationError<'a> {
+    let allowed: Vec<String> = allowed.iter().map(ToString::to_string).collect();
+
+    ValidationErrorKind::ParameterizedValidation {
+        error: MessageId::NotInEnum,
+        args: vec![
+            ("value", value.to_string()),
+            ("allowed", allowed.join(", ")),
+        ],
+    }
+    .into()
+}
+
+/// Looks up the value of a string key, without allocating a key to look it up by.
+pub(crate) fn get_str<'a>(hash: &'a Hash, key: &str) -> Option<&'a Yaml> {
+    hash.iter()
+        .find(|(other, _)| other.as_str() == Some(key))
+        .map(|(_, value)| value)
+}
+
 #[cfg(test)]
 pub(crate) fn load_simple(source: &'static str) -> Yaml {
     yaml_rust::YamlLoader::load_from_str(source)
@@ -105,7 +302,13 @@ pub trait YamlUtils {
         &'schema self,
         required: &[&'schema str],
         optional: &[&'schema str],
-    ) -> Result<&Hash, GenericError<'schema>>;
+    ) -> Result<&'schema Hash, GenericError<'schema>>;
+
+    fn strict_schema_contents<'schema>(
+        &'schema self,
+        required: &[&'schema str],
+        optional: &[&'schema str],
+    ) -> Result<&'schema Hash, SchemaError<'schema>>;
 
     fn check_exclusive_fields<'schema>(
         &'schema self,
@@ -132,9 +335,12 @@ impl YamlUtils for Yaml {
     where
         F: FnOnce(&'a Yaml) -> Option<T>,
     {
-        cast(self).ok_or_else(|| GenericError::WrongType {
-            expected,
-            actual: self.type_to_str(),
+        cast(self).ok_or_else(|| match self {
+            Yaml::BadValue => GenericError::BadValue { expected },
+            _ => GenericError::WrongType {
+                expected,
+                actual: self.type_to_str(),
+            },
         })
     }
 
@@ -159,7 +365,7 @@ impl YamlUtils for Yaml {
         &'schema self,
         required: &[&'schema str],
         optional: &[&'schema str],
-    ) -> Result<&Hash, GenericError<'schema>> {
+    ) -> Result<&'schema Hash, GenericError<'schema>> {
         let hash = self.as_type("hash", Yaml::as_hash)?;
 
         let missing = required
@@ -184,6 +390,24 @@ impl YamlUtils for Yaml {
         }
     }
 
+    fn strict_schema_contents<'schema>(
+        &'schema self,
+        required: &[&'schema str],
+        optional: &[&'schema str],
+    ) -> Result<&'schema Hash, SchemaError<'schema>> {
+        let optional: Vec<&'schema str> = optional
+            .iter()
+            .chain(UNIVERSAL_KEYWORDS)
+            .chain(PROPERTY_KEYWORDS)
+            .copied()
+            .collect();
+
+        self.strict_contents(required, &optional).map_err(|err| {
+            let keywords: Vec<&'schema str> = required.iter().chain(&optional).copied().collect();
+            suggest_keywords(err, &keywords)
+        })
+    }
+
     fn check_exclusive_fields<'schema>(
         &'schema self,
         exclusive_keys: &[&'static str],
@@ -210,6 +434,190 @@ impl YamlUtils for Yaml {
     }
 }
 
+/// Keywords accepted by each of the built-in types, besides the [UNIVERSAL_KEYWORDS].
+pub(crate) const TYPE_KEYWORDS: &[(&str, &[&str])] = &[
+    (
+        "object",
+        &[
+            "items",
+            "required",
+            "requireNonEmpty",
+            "requireNonBlank",
+            "propertyDependencies",
+            "conditionalRequired",
+            "conditional",
+            "unevaluatedProperties",
+            #[cfg(feature = "regex")]
+            "additionalPropertyNamePattern",
+            #[cfg(feature = "regex")]
+            "patternRequired",
+        ],
+    ),
+    (
+        "array",
+        &[
+            "items",
+            "itemsAnyOf",
+            "prefixItems",
+            "maxItems",
+            "minItems",
+            "uniqueItems",
+            "minUniqueItems",
+            "maxUniqueItems",
+            "contains",
+            "minContains",
+            "maxContains",
+            "sorted",
+            "maxItemErrors",
+        ],
+    ),
+    ("hash", &["items", "requiredKeys"]),
+    (
+        "string",
+        &[
+            "minLength",
+            "maxLength",
+            "minByteLength",
+            "maxByteLength",
+            "singleLine",
+            #[cfg(feature = "unicode-normalization")]
+            "requireNfc",
+            #[cfg(feature = "regex")]
+            "pattern",
+            #[cfg(feature = "regex")]
+            "lengthOf",
+            #[cfg(feature = "regex")]
+            "allPatterns",
+            #[cfg(feature = "regex")]
+            "anyPattern",
+            "enum",
+            "enumMap",
+            "format",
+            #[cfg(feature = "date")]
+            "minimum",
+            #[cfg(feature = "date")]
+            "maximum",
+        ],
+    ),
+    (
+        "integer",
+        &[
+            "minimum",
+            "exclusiveMinimum",
+            "maximum",
+            "exclusiveMaximum",
+            "multipleOf",
+            "divisibleBy",
+            "enum",
+            "coerce",
+            "format",
+        ],
+    ),
+    (
+        "real",
+        &[
+            "minimum",
+            "exclusiveMinimum",
+            "maximum",
+            "exclusiveMaximum",
+            "multipleOf",
+            "divisibleBy",
+            "enum",
+        ],
+    ),
+    ("boolean", &["mustBe"]),
+];
+
+/// Keywords accepted by the given built-in type, including `type` itself.
+pub(crate) fn type_keywords(typename: &str) -> Vec<&'static str> {
+    let keywords = TYPE_KEYWORDS
+        .iter()
+        .find(|(name, _)| *name == typename)
+        .map_or(&[][..], |(_, keywords)| *keywords);
+
+    std::iter::once("type")
+        .chain(keywords.iter().copied())
+        .collect()
+}
+
+/// Rejects keywords which only apply to built-in types other than the one declared for
+/// the node, such as `minItems` on a string, naming the types they do apply to. Keywords
+/// unknown to every type are left for the type itself to reject.
+pub(crate) fn check_vocabulary<'schema>(
+    yaml: &'schema Yaml,
+    typename: &str,
+) -> Result<(), SchemaError<'schema>> {
+    let hash = yaml.as_type("hash", Yaml::as_hash)?;
+
+    SchemaError::condense_errors(&mut hash.keys().filter_map(Yaml::as_str).map(|field| {
+        let types: Vec<&str> = TYPE_KEYWORDS
+            .iter()
+            .filter(|(_, keywords)| keywords.contains(&field))
+            .map(|(name, _)| *name)
+            .collect();
+
+        if types.is_empty() || types.contains(&typename) {
+            return Ok(());
+        }
+
+        Err(SchemaErrorKind::MalformedField {
+            error: format!(
+                "{} only applies to {} schemas, not {}",
+                field,
+                types.join(" or "),
+                typename
+            ),
+        }
+        .with_path_name(field))
+    }))?;
+
+    Ok(())
+}
+
+/// Number of single-character insertions, deletions or substitutions needed to
+/// turn one string into another.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Replace errors about unknown fields with suggestions of the closest known
+/// keyword, when one is near enough to likely be a misspelling.
+fn suggest_keywords<'schema>(
+    error: GenericError<'schema>,
+    keywords: &[&'schema str],
+) -> SchemaError<'schema> {
+    match error {
+        GenericError::ExtraField { field } => keywords
+            .iter()
+            .map(|keyword| (edit_distance(field, keyword), *keyword))
+            .filter(|(distance, _)| *distance <= 2 && *distance < field.len())
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, suggestion)| SchemaErrorKind::MisspelledField { field, suggestion })
+            .unwrap_or(SchemaErrorKind::ExtraField { field })
+            .into(),
+        GenericError::Multiple { errors } => SchemaErrorKind::Multiple {
+            errors: errors
+                .into_iter()
+                .map(|err| suggest_keywords(err, keywords))
+                .collect(),
+        }
+        .into(),
+        err => err.into(),
+    }
+}
+
 pub trait OptionalLookup<'a, T, E> {
     fn into_optional(self) -> Result<Option<T>, E>;
 }
@@ -262,25 +670,70 @@ where
     E: From<Vec<Self>> + Debug,
 {
     fn condense_errors(results: &mut dyn Iterator<Item = Result<T, Self>>) -> Result<Vec<T>, Self> {
-        let (values, mut errors): (Vec<_>, Vec<_>) = results.partition(Result::is_ok);
+        // Errors are only collected once one occurs, so that the common case of
+        // every result being valid does not allocate, as long as T is zero-sized.
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in results {
+            match result {
+                Ok(value) if errors.is_empty() => values.push(value),
+                Ok(_) => {}
+                Err(error) => errors.push(error),
+            }
+        }
 
         if errors.is_empty() {
-            Ok(values.into_iter().map(Result::unwrap).collect())
+            Ok(values)
         } else if errors.len() == 1 {
-            errors.pop().unwrap().map(|v| vec![v])
+            Err(errors.pop().unwrap())
         } else {
-            Err(errors
-                .into_iter()
-                .map(Result::unwrap_err)
-                .collect::<Vec<_>>()
-                .into())
+            Err(errors.into())
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Limit;
+    use super::{edit_distance, CondenseErrors, Limit};
+    use crate::errors::{ValidationError, ValidationErrorKind};
+
+    #[test]
+    fn verify_condense_errors() {
+        let missing =
+            |field| -> ValidationError { ValidationErrorKind::FieldMissing { field }.into() };
+
+        assert_eq!(
+            ValidationError::condense_errors(&mut vec![Ok(1), Ok(2)].into_iter()),
+            Ok(vec![1, 2])
+        );
+
+        assert_eq!(
+            ValidationError::condense_errors(
+                &mut vec![Ok(1), Err(missing("a")), Ok(2)].into_iter()
+            ),
+            Err(missing("a"))
+        );
+
+        assert_eq!(
+            ValidationError::condense_errors(
+                &mut vec![Err(missing("a")), Ok(1), Err(missing("b"))].into_iter()
+            ),
+            Err(ValidationErrorKind::Multiple {
+                errors: vec![missing("a"), missing("b")]
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn verify_edit_distance() {
+        assert_eq!(edit_distance("minItems", "minItems"), 0);
+        assert_eq!(edit_distance("minItms", "minItems"), 1);
+        assert_eq!(edit_distance("maxitems", "maxItems"), 1);
+        assert_eq!(edit_distance("", "type"), 4);
+        assert_eq!(edit_distance("items", "type"), 4);
+    }
 
     #[test]
     fn verify_limit_logic_f64() {
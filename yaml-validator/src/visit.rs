@@ -0,0 +1,284 @@
+use crate::{PropertyType, Schema};
+
+/// The kind of a schema node which has no nested schemas, as passed to [SchemaVisitor::scalar].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScalarKind<'a> {
+    String,
+    Integer,
+    Real,
+    Bool,
+    Const,
+    Tag,
+    /// A type registered with [Context::register_type](crate::Context::register_type), by name
+    Custom(&'a str),
+}
+
+/// Callbacks invoked while walking a schema with [Schema::visit], so that documentation
+/// generators and linters can traverse schemas without depending on their internal layout.
+///
+/// Every callback does nothing by default, and receives the names leading from the uri of
+/// the schema to the node, named the same way as in [LintWarning](crate::LintWarning).
+/// References are reported but not followed.
+#[allow(unused_variables)]
+pub trait SchemaVisitor {
+    /// Called before the properties of an object are visited.
+    fn enter_object(&mut self, path: &[String], properties: &[&str], required: &[&str]) {}
+    fn exit_object(&mut self, path: &[String]) {}
+
+    /// Called before the `prefixItems`, `items` and `contains` of an array are visited.
+    fn enter_array(&mut self, path: &[String]) {}
+    fn exit_array(&mut self, path: &[String]) {}
+
+    /// Called before the `items` of a hash are visited.
+    fn enter_hash(&mut self, path: &[String]) {}
+    fn exit_hash(&mut self, path: &[String]) {}
+
+    /// Called before the branches of `not`, `oneOf`, `allOf` or `anyOf`, given as the keyword.
+    fn enter_modifier(&mut self, path: &[String], keyword: &str) {}
+    fn exit_modifier(&mut self, path: &[String], keyword: &str) {}
+
+    fn scalar(&mut self, path: &[String], kind: ScalarKind) {}
+
+    /// Called for references, with the uri of the referenced schema and the pointer
+    /// following its `#`, if any.
+    fn reference(&mut self, path: &[String], uri: &str, pointer: Option<&str>) {}
+}
+
+/// How a schema is nested within the node containing it. Every walk over schemas, such as
+/// [Schema::visit], [Schema::lint] and [Schema::diff], names nested schemas the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Child<'a> {
+    /// A property of an object, by name
+    Property(&'a str),
+    PrefixItem(usize),
+    Items,
+    Contains,
+    Not,
+    /// A branch of `oneOf`, `allOf` or `anyOf`, given as the keyword
+    Branch(&'static str, usize),
+}
+
+impl<'a> std::fmt::Display for Child<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Child::Property(name) => write!(f, "{}", name),
+            Child::PrefixItem(i) => write!(f, "prefixItems[{}]", i),
+            Child::Items => write!(f, "items"),
+            Child::Contains => write!(f, "contains"),
+            Child::Not => write!(f, "not"),
+            Child::Branch(keyword, i) => write!(f, "{}[{}]", keyword, i),
+        }
+    }
+}
+
+/// The schemas nested directly within the node, in the order they are walked.
+///
+/// Nullable and composite nodes have no children of their own, and are instead looked
+/// through by the callers, since their parts describe the same value as they do.
+pub(crate) fn children<'a, 'schema>(
+    node: &'a PropertyType<'schema>,
+) -> Vec<(Child<'a>, &'a PropertyType<'schema>)> {
+    let branches = |keyword, items: &'a [PropertyType<'schema>]| {
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Child::Branch(keyword, i), item))
+            .collect()
+    };
+
+    match node {
+        PropertyType::Object(object) => object
+            .items
+            .iter()
+            .map(|(name, item)| (Child::Property(name), item))
+            .collect(),
+        PropertyType::Array(array) => array
+            .prefix_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Child::PrefixItem(i), item))
+            .chain(array.items.as_deref().map(|item| (Child::Items, item)))
+            .chain(
+                array
+                    .contains
+                    .as_deref()
+                    .map(|item| (Child::Contains, item)),
+            )
+            .collect(),
+        PropertyType::Hash(hash) => hash
+            .items
+            .as_deref()
+            .map(|item| (Child::Items, item))
+            .into_iter()
+            .collect(),
+        PropertyType::Not(not) => vec![(Child::Not, &not.item)],
+        PropertyType::OneOf(one_of) => branches("oneOf", &one_of.items),
+        PropertyType::AllOf(all_of) => branches("allOf", &all_of.items),
+        PropertyType::AnyOf(any_of) => branches("anyOf", &any_of.items),
+        _ => Vec::new(),
+    }
+}
+
+fn visit_children<V: SchemaVisitor>(node: &PropertyType, path: &mut Vec<String>, v: &mut V) {
+    for (child, item) in children(node) {
+        path.push(child.to_string());
+        visit(item, path, v);
+        path.pop();
+    }
+}
+
+fn visit_modifier<V: SchemaVisitor>(
+    node: &PropertyType,
+    keyword: &str,
+    path: &mut Vec<String>,
+    v: &mut V,
+) {
+    v.enter_modifier(path, keyword);
+    visit_children(node, path, v);
+    v.exit_modifier(path, keyword);
+}
+
+fn visit<V: SchemaVisitor>(node: &PropertyType, path: &mut Vec<String>, v: &mut V) {
+    match node {
+        PropertyType::Object(object) => {
+            let properties: Vec<&str> = object.items.keys().copied().collect();
+            let required = object.required.as_deref().unwrap_or_default();
+            v.enter_object(path, &properties, required);
+            visit_children(node, path, v);
+            v.exit_object(path);
+        }
+        PropertyType::Array(_) => {
+            v.enter_array(path);
+            visit_children(node, path, v);
+            v.exit_array(path);
+        }
+        PropertyType::Hash(_) => {
+            v.enter_hash(path);
+            visit_children(node, path, v);
+            v.exit_hash(path);
+        }
+        PropertyType::Not(_) => visit_modifier(node, "not", path, v),
+        PropertyType::OneOf(_) => visit_modifier(node, "oneOf", path, v),
+        PropertyType::AllOf(_) => visit_modifier(node, "allOf", path, v),
+        PropertyType::AnyOf(_) => visit_modifier(node, "anyOf", path, v),
+        PropertyType::Nullable(inner) => visit(inner, path, v),
+        PropertyType::Composite { base, modifiers } => {
+            for node in base.iter().map(AsRef::as_ref).chain(modifiers) {
+                visit(node, path, v);
+            }
+        }
+        PropertyType::Reference(reference) => v.reference(path, reference.uri, reference.pointer),
+        PropertyType::String(_) => v.scalar(path, ScalarKind::String),
+        PropertyType::Integer(_) => v.scalar(path, ScalarKind::Integer),
+        PropertyType::Real(_) => v.scalar(path, ScalarKind::Real),
+        PropertyType::Bool(_) => v.scalar(path, ScalarKind::Bool),
+        PropertyType::Const(_) => v.scalar(path, ScalarKind::Const),
+        PropertyType::Tag(_) => v.scalar(path, ScalarKind::Tag),
+        PropertyType::Custom(custom) => v.scalar(path, ScalarKind::Custom(custom.typename)),
+    }
+}
+
+impl<'schema> Schema<'schema> {
+    /// Walk every node of the schema depth-first, invoking the callbacks of the visitor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use yaml_rust::YamlLoader;
+    /// # use std::convert::TryFrom;
+    /// # use yaml_validator::{Context, ScalarKind, SchemaVisitor};
+    /// #
+    /// struct Leaves(Vec<String>);
+    ///
+    /// impl SchemaVisitor for Leaves {
+    ///     fn scalar(&mut self, path: &[String], kind: ScalarKind) {
+    ///         self.0.push(format!("{}: {:?}", path.join(" -> "), kind));
+    ///     }
+    /// }
+    ///
+    /// let schemas = YamlLoader::load_from_str(r#"
+    ///     uri: person
+    ///     schema:
+    ///         type: object
+    ///         items:
+    ///             name:
+    ///                 type: string
+    ///             age:
+    ///                 type: integer
+    /// "#).unwrap();
+    ///
+    /// let context = Context::try_from(&schemas[..]).unwrap();
+    ///
+    /// let mut leaves = Leaves(Vec::new());
+    /// context.get_schema("person").unwrap().visit(&mut leaves);
+    ///
+    /// assert_eq!(leaves.0, vec!["person -> age: Integer", "person -> name: String"]);
+    /// ```
+    pub fn visit<V: SchemaVisitor>(&self, visitor: &mut V) {
+        visit(&self.schema, &mut vec![self.uri.to_string()], visitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+    use yaml_rust::YamlLoader;
+
+    #[derive(Default)]
+    struct StringCounter {
+        strings: usize,
+        references: Vec<String>,
+    }
+
+    impl SchemaVisitor for StringCounter {
+        fn scalar(&mut self, _: &[String], kind: ScalarKind) {
+            if kind == ScalarKind::String {
+                self.strings += 1;
+            }
+        }
+
+        fn reference(&mut self, path: &[String], uri: &str, _: Option<&str>) {
+            self.references
+                .push(format!("{} -> {}", path.join(" -> "), uri));
+        }
+    }
+
+    #[test]
+    fn count_string_fields() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+uri: person
+schema:
+  type: object
+  items:
+    name:
+      type: string
+    age:
+      type: integer
+    nicknames:
+      type: array
+      items:
+        type: string
+    contact:
+      oneOf:
+        - type: string
+          nullable: true
+        - $ref: phone
+"#,
+        )
+        .unwrap();
+
+        let schema = Schema::try_from(&yaml[0]).unwrap();
+
+        let mut counter = StringCounter::default();
+        schema.visit(&mut counter);
+
+        assert_eq!(counter.strings, 3);
+        assert_eq!(
+            counter.references,
+            vec!["person -> contact -> oneOf[1] -> phone"]
+        );
+    }
+}